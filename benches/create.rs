@@ -0,0 +1,74 @@
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
+use std::fs::{self, File};
+use std::io;
+use std::path::PathBuf;
+use tempfile::{TempDir, tempdir};
+use zip::ZipWriter;
+use zip::write::{FileOptions, SimpleFileOptions};
+use zipoxide::create_zip_from_files;
+
+const LARGE_FILE_COUNT: usize = 4;
+const LARGE_FILE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Writes `LARGE_FILE_COUNT` files of `LARGE_FILE_SIZE` bytes and returns their directory and paths.
+fn large_files_fixture() -> (TempDir, Vec<PathBuf>) {
+    let dir: TempDir = tempdir().unwrap();
+    let paths: Vec<PathBuf> = (0..LARGE_FILE_COUNT)
+        .map(|index| {
+            let path = dir.path().join(format!("large_{index}.bin"));
+            let data: Vec<u8> = (0..LARGE_FILE_SIZE).map(|byte| (byte % 251) as u8).collect();
+            fs::write(&path, data).unwrap();
+            path
+        })
+        .collect();
+    (dir, paths)
+}
+
+/// Baseline without a `BufReader`: source files are copied straight from the raw `File`.
+fn create_zip_unbuffered(output: PathBuf, paths: &[PathBuf], zip_options: SimpleFileOptions) {
+    let mut zip_writer = ZipWriter::new(File::create(output).unwrap());
+    for path in paths {
+        zip_writer.start_file(path.file_name().unwrap().to_str().unwrap(), zip_options).unwrap();
+        io::copy(&mut File::open(path).unwrap(), &mut zip_writer).unwrap();
+    }
+    zip_writer.finish().unwrap();
+}
+
+fn bench_create_large_files(c: &mut Criterion) {
+    let (_fixture_dir, paths) = large_files_fixture();
+    // Stored entries keep the benchmark focused on I/O rather than compression.
+    let zip_options: SimpleFileOptions = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    let mut group = c.benchmark_group("create_zip_from_files");
+    group.throughput(Throughput::Bytes((LARGE_FILE_COUNT * LARGE_FILE_SIZE) as u64));
+    group.sample_size(10);
+    group.bench_function("buffered_reader", |b| {
+        b.iter_batched(
+            || tempdir().unwrap(),
+            |output_dir| {
+                create_zip_from_files(
+                    output_dir.path().join("out.zip").to_str().unwrap().to_string(),
+                    paths.iter().map(|path| path.to_str().unwrap().to_string()).collect(),
+                    zip_options,
+                )
+                .unwrap();
+                output_dir
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.bench_function("unbuffered_reader", |b| {
+        b.iter_batched(
+            || tempdir().unwrap(),
+            |output_dir| {
+                create_zip_unbuffered(output_dir.path().join("out.zip"), &paths, zip_options);
+                output_dir
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_create_large_files);
+criterion_main!(benches);
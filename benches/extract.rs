@@ -0,0 +1,47 @@
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
+use std::fs;
+use tempfile::{TempDir, tempdir};
+use zip::write::SimpleFileOptions;
+use zipoxide::{create_zip_from_folder, extract_zip};
+
+const TINY_FILE_COUNT: usize = 1000;
+
+/// Builds an archive of `TINY_FILE_COUNT` small files and returns the directory holding it.
+fn tiny_files_archive() -> TempDir {
+    let dir: TempDir = tempdir().unwrap();
+    let folder = dir.path().join("tiny");
+    fs::create_dir(&folder).unwrap();
+    for index in 0..TINY_FILE_COUNT {
+        fs::write(folder.join(format!("file_{index:04}.txt")), format!("tiny file number {index}\n").repeat(8)).unwrap();
+    }
+    create_zip_from_folder(
+        dir.path().join("tiny.zip").to_str().unwrap().to_string(),
+        folder.to_str().unwrap().to_string(),
+        SimpleFileOptions::default(),
+    )
+    .unwrap();
+    dir
+}
+
+fn bench_extract_tiny_files(c: &mut Criterion) {
+    let archive_dir: TempDir = tiny_files_archive();
+    let zip_path: String = archive_dir.path().join("tiny.zip").to_str().unwrap().to_string();
+
+    let mut group = c.benchmark_group("extract_zip");
+    group.throughput(Throughput::Elements(TINY_FILE_COUNT as u64));
+    group.sample_size(20);
+    group.bench_function("1000_tiny_files", |b| {
+        b.iter_batched(
+            || tempdir().unwrap(),
+            |output_dir| {
+                extract_zip(zip_path.clone(), output_dir.path().to_str().unwrap().to_string(), None).unwrap();
+                output_dir
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_extract_tiny_files);
+criterion_main!(benches);
@@ -0,0 +1,150 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use zip::write::SimpleFileOptions;
+use zipoxide::{
+    CompressionBackend, CreationOptions, ExtractionOptions, ExtractionReport, ZipEntry, ZipoxideError,
+    create_zip_from_files_with_options, create_zip_from_folder_with_options, extract_zip_with_options,
+    list_zip_entries, read_zip_contents_lenient,
+};
+
+/// A Rust-powered, blazing fast ZIP utility.
+#[derive(Parser)]
+#[command(name = "zipoxide", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create an archive from a folder or a list of files and folders.
+    Create {
+        /// Path of the archive to create; must not exist yet.
+        output: PathBuf,
+        /// A single folder (its contents are archived) or several files and folders.
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        /// Store entries relative to this prefix instead of the default root.
+        #[arg(long)]
+        strip_prefix: Option<PathBuf>,
+        /// Compression backend used for every file.
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+    },
+    /// Extract an archive into a directory.
+    Extract {
+        archive: PathBuf,
+        destination: PathBuf,
+        #[arg(long)]
+        password: Option<String>,
+        /// Refuse archives with absolute, `..`, drive-letter or backslash entry names.
+        #[arg(long)]
+        strict_names: bool,
+        /// Number of worker threads (defaults to the Rayon global pool).
+        #[arg(long)]
+        threads: Option<usize>,
+    },
+    /// List the entries of an archive.
+    List { archive: PathBuf },
+    /// Decompress every entry and check its CRC32, reporting entries that fail.
+    Verify {
+        archive: PathBuf,
+        #[arg(long)]
+        password: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Backend {
+    Stored,
+    Deflate,
+    Bzip2,
+    Zstd,
+}
+
+impl From<Backend> for CompressionBackend {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Stored => CompressionBackend::Stored,
+            Backend::Deflate => CompressionBackend::Deflate,
+            Backend::Bzip2 => CompressionBackend::Bzip2,
+            Backend::Zstd => CompressionBackend::Zstd,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let result: Result<ExitCode, Box<dyn std::error::Error>> = match Cli::parse().command {
+        Command::Create { output, inputs, strip_prefix, backend } => create(output, inputs, strip_prefix, backend),
+        Command::Extract { archive, destination, password, strict_names, threads } => {
+            let options = ExtractionOptions { password, strict_names, threads, ..Default::default() };
+            extract(archive, destination, options)
+        }
+        Command::List { archive } => list(archive).map_err(Into::into),
+        Command::Verify { archive, password } => verify(archive, password).map_err(Into::into),
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("zipoxide: {e}");
+        ExitCode::FAILURE
+    })
+}
+
+fn create(
+    output: PathBuf,
+    inputs: Vec<PathBuf>,
+    strip_prefix: Option<PathBuf>,
+    backend: Option<Backend>,
+) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    if output.exists() {
+        return Err(format!("{} already exists", output.display()).into());
+    }
+    let creation_options = CreationOptions { strip_prefix, backend: backend.map(Into::into), ..Default::default() };
+    let output: String = path_string(&output)?;
+    if let [folder] = inputs.as_slice()
+        && folder.is_dir()
+    {
+        let folder: String = path_string(folder)?;
+        create_zip_from_folder_with_options(output, folder, SimpleFileOptions::default(), creation_options)?;
+    } else {
+        let inputs: Vec<String> = inputs.iter().map(|input| path_string(input)).collect::<Result<_, _>>()?;
+        create_zip_from_files_with_options(output, inputs, SimpleFileOptions::default(), creation_options)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn extract(
+    archive: PathBuf,
+    destination: PathBuf,
+    options: ExtractionOptions,
+) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let report: ExtractionReport = extract_zip_with_options(archive, destination, options)?;
+    for (name, path) in &report.renamed {
+        eprintln!("renamed {name} -> {}", path.display());
+    }
+    println!("extracted {} entries using {} threads", report.extracted.len(), report.threads);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn list(archive: PathBuf) -> Result<ExitCode, ZipoxideError> {
+    let entries: Vec<ZipEntry> = list_zip_entries(archive)?;
+    for entry in entries {
+        let kind: &str = if entry.is_directory() { "d" } else { "-" };
+        println!("{kind} {:>12} {:>12} {}", entry.size, entry.compressed_size, entry.name);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn verify(archive: PathBuf, password: Option<String>) -> Result<ExitCode, ZipoxideError> {
+    let (contents, failures) = read_zip_contents_lenient(archive, password.as_deref())?;
+    for (name, error) in &failures {
+        eprintln!("FAILED {name}: {error}");
+    }
+    println!("{} entries ok, {} failed", contents.len(), failures.len());
+    Ok(if failures.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+/// The writer functions take `String` paths.
+fn path_string(path: &std::path::Path) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(path.to_str().ok_or_else(|| format!("non-UTF8 path: {}", path.display()))?.to_string())
+}
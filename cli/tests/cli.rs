@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+
+fn zipoxide(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_zipoxide")).args(args).output().unwrap()
+}
+
+#[test]
+fn test_create_list_verify_extract_round_trip() {
+    let dir = tempdir().unwrap();
+    let folder = dir.path().join("data");
+    fs::create_dir_all(folder.join("nested")).unwrap();
+    fs::write(folder.join("a.txt"), b"alpha").unwrap();
+    fs::write(folder.join("nested").join("b.txt"), b"beta").unwrap();
+    let archive = dir.path().join("data.zip");
+
+    let output = zipoxide(&[Path::new("create"), &archive, &folder]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let output = zipoxide(&[Path::new("list"), &archive]);
+    let listing = String::from_utf8(output.stdout).unwrap();
+    assert!(listing.contains("a.txt"));
+    assert!(listing.contains("nested/b.txt"));
+
+    let output = zipoxide(&[Path::new("verify"), &archive]);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("2 entries ok, 0 failed"));
+
+    let destination = dir.path().join("out");
+    let output = zipoxide(&[Path::new("extract"), &archive, &destination]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(fs::read(destination.join("nested").join("b.txt")).unwrap(), b"beta");
+}
+
+#[test]
+fn test_create_refuses_existing_output() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("a.txt");
+    fs::write(&file, b"alpha").unwrap();
+    let archive = dir.path().join("existing.zip");
+    fs::write(&archive, b"").unwrap();
+
+    let output = zipoxide(&[Path::new("create"), &archive, &file]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("already exists"));
+}
@@ -0,0 +1,10 @@
+//! Archives the files listed on standard input: `find . -name '*.rs' | cargo run --example zip_from_stdin -- out.zip`
+
+use zipoxide::{CreationOptions, create_zip_from_stdin};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let output: String = std::env::args().nth(1).ok_or("usage: zip_from_stdin <output.zip>")?;
+    let stats = create_zip_from_stdin(&output, CreationOptions::default())?;
+    println!("{output}: {} entries, {} bytes", stats.entries, stats.archive_size);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<ZipOxideConfig> = RefCell::new(ZipOxideConfig::default());
+}
+
+/// Library-wide defaults for settings that callers would otherwise repeat at every call site.
+///
+/// Option fields left as `None` in [`ExtractionOptions`](crate::ExtractionOptions) or
+/// [`CreationOptions`](crate::CreationOptions) fall back to the configuration active on the calling
+/// thread, which is [`ZipOxideConfig::default`] unless overridden with [`ZipOxideConfig::with_thread_local`].
+///
+/// # Fields
+/// - `max_entries`: Archives with more entries are refused on extraction
+///   ([`ZipoxideError::TooManyEntries`](crate::ZipoxideError::TooManyEntries)). `None` means no limit.
+/// - `max_compression_ratio`: Entries whose declared uncompressed size exceeds their compressed size by
+///   more than this factor are refused on extraction
+///   ([`ZipoxideError::CompressionRatioExceeded`](crate::ZipoxideError::CompressionRatioExceeded)),
+///   guarding against ZIP bombs. `None` means no limit.
+/// - `compression_level`: Compression level applied by the writer functions when
+///   `CreationOptions::compression_level` is `None`, replacing the level of their `FileOptions`.
+///   `None` leaves the `FileOptions` untouched.
+///
+/// # Example
+/// ```rust
+/// use zipoxide::ZipOxideConfig;
+///
+/// let strict = ZipOxideConfig {
+///     max_entries: Some(10_000),
+///     max_compression_ratio: Some(100.0),
+///     ..Default::default()
+/// };
+/// ZipOxideConfig::with_thread_local(strict, || {
+///     assert_eq!(ZipOxideConfig::current().max_entries, Some(10_000));
+/// });
+/// assert_eq!(ZipOxideConfig::current().max_entries, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZipOxideConfig {
+    pub max_entries: Option<usize>,
+    pub max_compression_ratio: Option<f64>,
+    pub compression_level: Option<i64>,
+}
+
+/// Puts the previous configuration back when a [`ZipOxideConfig::with_thread_local`] scope ends, even by panic.
+struct RestoreOnDrop(ZipOxideConfig);
+
+impl Drop for RestoreOnDrop {
+    fn drop(&mut self) {
+        let previous: ZipOxideConfig = std::mem::take(&mut self.0);
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+impl ZipOxideConfig {
+    /// Returns the configuration active on the calling thread.
+    pub fn current() -> ZipOxideConfig {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Runs `scope` with `config` as the calling thread's configuration, restoring the previous one afterwards.
+    ///
+    /// # Behavior
+    /// - Scopes nest: an inner call overrides the outer one until it returns.
+    /// - Only the calling thread is affected. zipoxide reads the configuration before handing work to
+    ///   Rayon workers, so parallel operations started inside `scope` still honour it.
+    pub fn with_thread_local<T>(config: ZipOxideConfig, scope: impl FnOnce() -> T) -> T {
+        let previous: ZipOxideConfig = CURRENT.with(|current| current.replace(config));
+        let _restore: RestoreOnDrop = RestoreOnDrop(previous);
+        scope()
+    }
+}
@@ -0,0 +1,95 @@
+use encoding_rs::Encoding;
+use std::path::{Component, Path, PathBuf};
+
+/// Device names Windows reserves in every directory, with or without an extension.
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1",
+    "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Characters that may not appear in a Windows file name.
+const WINDOWS_INVALID_CHARS: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+
+/// Rewrites a single path component so it can be created on Windows.
+///
+/// - Reserved device names (`CON`, `aux.txt`, `LPT1.log`, ...) are prefixed with `_`.
+/// - Invalid characters and control characters are replaced with `_`.
+/// - Trailing dots and spaces, which Windows silently drops, are replaced with `_`.
+pub(crate) fn sanitize_windows_component(component: &str) -> String {
+    let mut sanitized: String = component
+        .chars()
+        .map(|c| if WINDOWS_INVALID_CHARS.contains(&c) || c.is_control() { '_' } else { c })
+        .collect();
+
+    let trimmed_len: usize = sanitized.trim_end_matches(['.', ' ']).len();
+    if trimmed_len < sanitized.len() {
+        let trailing: usize = sanitized.len() - trimmed_len;
+        sanitized.truncate(trimmed_len);
+        sanitized.extend(std::iter::repeat_n('_', trailing));
+    }
+
+    let stem: &str = sanitized.split('.').next().unwrap_or_default().trim_end();
+    if WINDOWS_RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// Returns `true` for entry names that try to leave the extraction directory or depend on the host's
+/// path syntax: absolute paths, `..` components, drive letters and backslashes.
+pub(crate) fn is_unsafe_entry_name(name: &str) -> bool {
+    let bytes: &[u8] = name.as_bytes();
+    let has_drive_letter: bool = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    name.starts_with('/')
+        || name.contains('\\')
+        || has_drive_letter
+        || name.split('/').any(|component| component == "..")
+}
+
+/// Converts an entry name into the relative path it is extracted to.
+///
+/// Root, prefix, `.` and `..` components are dropped so the result always stays below the extraction
+/// directory. Returns the relative path and whether a component was rewritten or dropped on the way.
+pub(crate) fn entry_output_path(name: &str, sanitize_windows: bool) -> (PathBuf, bool) {
+    let mut relative_path: PathBuf = PathBuf::new();
+    // A leading `/` only produces an empty first component, which is skipped like any other.
+    let mut renamed: bool = name.starts_with('/');
+    for component in name.split('/').filter(|component| !component.is_empty()) {
+        let sanitized: String = match sanitize_windows {
+            true => sanitize_windows_component(component),
+            false => component.to_string(),
+        };
+        renamed |= sanitized != component;
+        for part in Path::new(&sanitized).components() {
+            match part {
+                Component::Normal(part) => relative_path.push(part),
+                Component::CurDir => {}
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => renamed = true,
+            }
+        }
+    }
+    (relative_path, renamed)
+}
+
+/// Returns `true` for names that leave nothing to extract to once [`entry_output_path`] has dropped
+/// empty, `.`, `..` and root components: `""`, `"."`, `"./"`, `"/"`, `"../.."` and the like.
+///
+/// Directory entries with such names are harmless (they denote the extraction directory), but a file
+/// entry would be written over the extraction directory itself.
+pub(crate) fn is_degenerate_entry_name(name: &str) -> bool {
+    entry_output_path(name, false).0.as_os_str().is_empty()
+}
+
+/// Returns an entry's name, re-decoding its raw bytes with `name_encoding` if it was stored without the
+/// UTF-8 flag.
+///
+/// The `zip` crate decodes such names as CP437, as the ZIP specification requires, but does not expose
+/// the flag itself: a name that differs from its raw bytes read as UTF-8 can only have been decoded that way.
+pub(crate) fn decode_entry_name(name: &str, name_raw: &[u8], name_encoding: Option<&'static Encoding>) -> String {
+    match name_encoding {
+        Some(encoding) if std::str::from_utf8(name_raw) != Ok(name) => {
+            encoding.decode_without_bom_handling(name_raw).0.into_owned()
+        }
+        _ => name.to_string(),
+    }
+}
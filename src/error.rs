@@ -0,0 +1,140 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::sync::PoisonError;
+use zip::result::ZipError;
+
+/// Errors produced by zipoxide operations.
+///
+/// Most failures come straight from the filesystem ([`ZipoxideError::Io`]) or from the
+/// [`zip`](https://docs.rs/zip/latest/zip/) crate ([`ZipoxideError::Zip`]). Conditions that callers
+/// commonly need to react to are surfaced as dedicated variants instead of being folded into a
+/// generic message.
+#[derive(Debug)]
+pub enum ZipoxideError {
+    /// An underlying I/O operation failed.
+    Io(io::Error),
+    /// The `zip` crate reported an error while reading or writing the archive.
+    Zip(ZipError),
+    /// An entry is AES-encrypted and cannot be decrypted by this build of the `zip` crate.
+    AesUnsupported,
+    /// No entry with the given name exists in the archive.
+    EntryNotFound(String),
+    /// A path expected to be a regular file is something else (e.g. a directory) or does not exist.
+    NotAFile(PathBuf),
+    /// A path expected to be a directory is something else (e.g. a regular file) or does not exist.
+    NotADirectory(PathBuf),
+    /// The requested behaviour needs a Cargo feature this build was compiled without.
+    FeatureDisabled(&'static str),
+    /// An entry name is absolute, contains `..`, a drive letter or a backslash, and strict name
+    /// checking was requested.
+    UnsafeEntryName(String),
+    /// The archive holds more entries than the configured `max_entries` limit allows.
+    TooManyEntries(usize),
+    /// An entry's declared uncompressed size exceeds its compressed size by more than the configured
+    /// `max_compression_ratio`.
+    CompressionRatioExceeded(String),
+    /// A worker thread panicked while holding shared state, or that state was still shared when the
+    /// results were collected.
+    ConcurrencyError(String),
+    /// The directory to extract into already exists as something other than a directory (e.g. a
+    /// regular file).
+    ExtractTargetNotADirectory(PathBuf),
+    /// An input path cannot be turned into an entry name: it does not start with the configured
+    /// `strip_prefix`, has no file name, or is not valid UTF-8.
+    InvalidPath(PathBuf),
+    /// A file entry's name is empty or consists only of `.`, `/` and dropped components, so it would be
+    /// extracted onto the extraction directory itself.
+    DegenerateEntryName(String),
+    /// An input path is neither a regular file nor a directory once symlinks are followed: a broken
+    /// symlink, a named pipe, a socket or a device file.
+    UnsupportedEntryType(PathBuf),
+    /// The files to archive add up to more uncompressed bytes than the configured
+    /// `max_total_uncompressed_bytes` limit allows.
+    SizeLimitExceeded { actual: u64, limit: u64 },
+    /// A path given as input to an archive writer does not exist.
+    InputPathNotFound(PathBuf),
+    /// An entry read as text is not valid UTF-8; holds the entry's raw bytes.
+    InvalidUtf8(Vec<u8>),
+    /// File entries from two different archives extracted together would be written to this path.
+    EntryCollision(PathBuf),
+}
+
+impl fmt::Display for ZipoxideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZipoxideError::Io(e) => write!(f, "I/O error: {e}"),
+            ZipoxideError::Zip(e) => write!(f, "ZIP error: {e}"),
+            ZipoxideError::AesUnsupported => f.write_str("AES-encrypted entries are not supported by this build"),
+            ZipoxideError::EntryNotFound(name) => write!(f, "entry not found in archive: {name}"),
+            ZipoxideError::NotAFile(path) => write!(f, "not a regular file: {}", path.display()),
+            ZipoxideError::NotADirectory(path) => write!(f, "not a directory: {}", path.display()),
+            ZipoxideError::FeatureDisabled(feature) => write!(f, "this build lacks the `{feature}` feature"),
+            ZipoxideError::UnsafeEntryName(name) => write!(f, "unsafe entry name in archive: {name}"),
+            ZipoxideError::TooManyEntries(count) => write!(f, "archive has too many entries: {count}"),
+            ZipoxideError::CompressionRatioExceeded(name) => {
+                write!(f, "entry exceeds the maximum compression ratio: {name}")
+            }
+            ZipoxideError::ConcurrencyError(message) => write!(f, "concurrency error: {message}"),
+            ZipoxideError::ExtractTargetNotADirectory(path) => {
+                write!(f, "extraction target is not a directory: {}", path.display())
+            }
+            ZipoxideError::InvalidPath(path) => write!(f, "cannot derive an entry name from {}", path.display()),
+            ZipoxideError::DegenerateEntryName(name) => write!(f, "entry name does not name a file: {name:?}"),
+            ZipoxideError::UnsupportedEntryType(path) => {
+                write!(f, "not a regular file or directory: {}", path.display())
+            }
+            ZipoxideError::SizeLimitExceeded { actual, limit } => {
+                write!(f, "input is {actual} bytes uncompressed, over the limit of {limit} bytes")
+            }
+            ZipoxideError::InputPathNotFound(path) => write!(f, "input path not found: {}", path.display()),
+            ZipoxideError::InvalidUtf8(bytes) => write!(f, "entry is not valid UTF-8 ({} bytes)", bytes.len()),
+            ZipoxideError::EntryCollision(path) => {
+                write!(f, "entries from different archives extract to the same path: {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ZipoxideError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ZipoxideError::Io(e) => Some(e),
+            ZipoxideError::Zip(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ZipoxideError {
+    fn from(e: io::Error) -> Self {
+        ZipoxideError::Io(e)
+    }
+}
+
+#[cfg(feature = "notify")]
+impl From<notify::Error> for ZipoxideError {
+    fn from(e: notify::Error) -> Self {
+        match e.kind {
+            notify::ErrorKind::Io(e) => ZipoxideError::Io(e),
+            _ => ZipoxideError::Io(io::Error::other(e)),
+        }
+    }
+}
+
+impl<T> From<PoisonError<T>> for ZipoxideError {
+    fn from(e: PoisonError<T>) -> Self {
+        ZipoxideError::ConcurrencyError(e.to_string())
+    }
+}
+
+impl From<ZipError> for ZipoxideError {
+    fn from(e: ZipError) -> Self {
+        match e {
+            // The `zip` crate only reports AES problems through a free-form "unsupported" message.
+            ZipError::UnsupportedArchive(message) if message.contains("AES") => ZipoxideError::AesUnsupported,
+            ZipError::Io(e) => ZipoxideError::Io(e),
+            e => ZipoxideError::Zip(e),
+        }
+    }
+}
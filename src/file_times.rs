@@ -0,0 +1,98 @@
+use std::fs::{self, FileTimes};
+use std::io::Read;
+use std::time::{Duration, SystemTime};
+use time::OffsetDateTime;
+use zip::DateTime;
+use zip::extra_fields::ExtraField;
+use zip::read::ZipFile;
+
+/// Header ID of the NTFS extra field, which stores times in 100 ns ticks.
+pub(crate) const NTFS_EXTRA_FIELD_ID: u16 = 0x000a;
+
+/// Seconds between the Windows file time epoch (1601-01-01) and the Unix epoch.
+const WINDOWS_TO_UNIX_EPOCH_SECS: u64 = 11_644_473_600;
+
+/// Windows file time ticks per second.
+const TICKS_PER_SEC: u64 = 10_000_000;
+
+/// Converts a system time to Windows file time ticks, or 0 (meaning "not set") if it predates 1601.
+fn to_ticks(time: SystemTime) -> u64 {
+    let epoch: SystemTime = SystemTime::UNIX_EPOCH - Duration::from_secs(WINDOWS_TO_UNIX_EPOCH_SECS);
+    time.duration_since(epoch).map_or(0, |since| {
+        since.as_secs().saturating_mul(TICKS_PER_SEC).saturating_add(u64::from(since.subsec_nanos()) / 100)
+    })
+}
+
+/// Converts Windows file time ticks to a system time; 0 means the time was not recorded.
+fn from_ticks(ticks: u64) -> Option<SystemTime> {
+    let since: Duration = Duration::new(ticks / TICKS_PER_SEC, (ticks % TICKS_PER_SEC * 100) as u32);
+    let epoch: SystemTime = SystemTime::UNIX_EPOCH - Duration::from_secs(WINDOWS_TO_UNIX_EPOCH_SECS);
+    (ticks != 0).then(|| epoch.checked_add(since)).flatten()
+}
+
+/// Builds the NTFS extra field body for a file: modification, access and creation times at 100 ns
+/// resolution. Times the platform does not report fall back to the modification time.
+pub(crate) fn ntfs_extra_field(metadata: &fs::Metadata) -> Box<[u8]> {
+    let modified: u64 = metadata.modified().map_or(0, to_ticks);
+    let accessed: u64 = metadata.accessed().map_or(modified, to_ticks);
+    let created: u64 = metadata.created().map_or(modified, to_ticks);
+    let mut field: Vec<u8> = Vec::with_capacity(32);
+    field.extend_from_slice(&[0; 4]); // reserved
+    field.extend_from_slice(&1u16.to_le_bytes()); // attribute tag 1: file times
+    field.extend_from_slice(&24u16.to_le_bytes()); // attribute size
+    for ticks in [modified, accessed, created] {
+        field.extend_from_slice(&ticks.to_le_bytes());
+    }
+    field.into_boxed_slice()
+}
+
+/// Converts a file's modification time to the DOS timestamp of its entry, read as UTC. Times outside
+/// the DOS range (before 1980 or after 2107) fall back to 1980-01-01.
+pub(crate) fn dos_time(metadata: &fs::Metadata) -> DateTime {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| DateTime::try_from(OffsetDateTime::from(modified)).ok())
+        .unwrap_or_default()
+}
+
+/// Reads the modification and access times an entry recorded in an NTFS (100 ns resolution) or
+/// extended timestamp (1 s resolution) extra field, preferring NTFS.
+///
+/// Returns `None` for entries with neither field, whose only timestamp is the 2-second DOS one.
+pub(crate) fn recorded_file_times<'a>(extra_fields: impl Iterator<Item = &'a ExtraField>) -> Option<FileTimes> {
+    let (modified, accessed) = recorded_times(extra_fields)?;
+    Some(FileTimes::new().set_modified(modified).set_accessed(accessed))
+}
+
+/// Modification time of an entry: the one recorded in an NTFS or extended timestamp extra field, or else
+/// its DOS timestamp read as UTC.
+pub(crate) fn entry_modified_time<R: Read>(entry: &ZipFile<'_, R>) -> Option<SystemTime> {
+    match recorded_times(entry.extra_data_fields()) {
+        Some((modified, _)) => Some(modified),
+        None => {
+            let modified: OffsetDateTime = entry.last_modified().and_then(|dos| OffsetDateTime::try_from(dos).ok())?;
+            Some(SystemTime::from(modified))
+        }
+    }
+}
+
+/// `(modified, accessed)` from the first NTFS extra field, or else from the last extended timestamp field.
+fn recorded_times<'a>(extra_fields: impl Iterator<Item = &'a ExtraField>) -> Option<(SystemTime, SystemTime)> {
+    let mut extended: Option<(SystemTime, SystemTime)> = None;
+    for field in extra_fields {
+        match field {
+            ExtraField::Ntfs(ntfs) => {
+                let modified: SystemTime = from_ticks(ntfs.mtime())?;
+                return Some((modified, from_ticks(ntfs.atime()).unwrap_or(modified)));
+            }
+            ExtraField::ExtendedTimestamp(timestamp) => {
+                let seconds = |time: u32| SystemTime::UNIX_EPOCH + Duration::from_secs(u64::from(time));
+                if let Some(modified) = timestamp.mod_time().map(seconds) {
+                    extended = Some((modified, timestamp.ac_time().map_or(modified, seconds)));
+                }
+            }
+        }
+    }
+    extended
+}
@@ -0,0 +1,79 @@
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::error::ZipoxideError;
+
+/// Buffer size used on both sides of the stream.
+const GZIP_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Compresses `input` into a new gzip file at `output`.
+///
+/// # Arguments
+/// - `input`: Path to the file to compress.  
+/// - `output`: Path of the `.gz` file to create (truncated if it already exists).  
+///
+/// # Behavior
+/// - Streams the file through [`flate2`](https://docs.rs/flate2/latest/flate2/) at the default
+///   compression level, so memory use stays constant regardless of the input size.  
+///
+/// # Returns
+/// The number of uncompressed bytes read from `input`.
+///
+/// # Errors
+/// Returns [`ZipoxideError::Io`] if `input` cannot be read or `output` cannot be written.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::gzip::gzip_file;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     gzip_file("app.log", "app.log.gz")?;
+///     Ok(())
+/// }
+/// ```
+pub fn gzip_file(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<u64, ZipoxideError> {
+    let mut source: BufReader<File> = BufReader::with_capacity(GZIP_BUFFER_SIZE, File::open(input.as_ref())?);
+    let sink: BufWriter<File> = BufWriter::with_capacity(GZIP_BUFFER_SIZE, File::create(output.as_ref())?);
+    let mut encoder: GzEncoder<BufWriter<File>> = GzEncoder::new(sink, Compression::default());
+    let size: u64 = io::copy(&mut source, &mut encoder)?;
+    encoder.finish()?.flush()?;
+    Ok(size)
+}
+
+/// Decompresses the gzip file `input` into `output`.
+///
+/// # Arguments
+/// - `input`: Path to the `.gz` file.  
+/// - `output`: Path of the decompressed file to create (truncated if it already exists).  
+///
+/// # Behavior
+/// - Concatenated gzip members (as produced by `cat a.gz b.gz`) are decompressed one after another.  
+///
+/// # Returns
+/// The number of decompressed bytes written to `output`.
+///
+/// # Errors
+/// Returns [`ZipoxideError::Io`] if `input` cannot be read, is not valid gzip data, or `output`
+/// cannot be written.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::gzip::gunzip_file;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     gunzip_file("app.log.gz", "app.log")?;
+///     Ok(())
+/// }
+/// ```
+pub fn gunzip_file(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<u64, ZipoxideError> {
+    let source: BufReader<File> = BufReader::with_capacity(GZIP_BUFFER_SIZE, File::open(input.as_ref())?);
+    let mut decoder: MultiGzDecoder<BufReader<File>> = MultiGzDecoder::new(source);
+    let mut sink: BufWriter<File> = BufWriter::with_capacity(GZIP_BUFFER_SIZE, File::create(output.as_ref())?);
+    let size: u64 = io::copy(&mut decoder, &mut sink)?;
+    sink.flush()?;
+    Ok(size)
+}
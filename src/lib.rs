@@ -1,6 +1,49 @@
+mod config;
+mod entry_path;
+pub mod error;
+mod file_times;
+#[cfg(feature = "gzip")]
+pub mod gzip;
+#[cfg(feature = "manifest")]
+mod manifest;
+mod recovery;
+mod streaming;
+#[cfg(feature = "tar")]
+mod tar_archive;
+#[cfg(feature = "notify")]
+mod watch;
+pub mod zip_entry;
 pub mod zip_reader;
 pub mod zip_writer;
 
 // Re-export the public functions for external use
-pub use zip_reader::{extract_zip, read_zip_contents_into_buffer};
-pub use zip_writer::{create_zip_from_folder, create_zip_from_files};
+pub use config::ZipOxideConfig;
+pub use error::ZipoxideError;
+pub use recovery::recover_partial_zip;
+pub use streaming::{ArchiveFormat, extract_streaming};
+#[cfg(feature = "tar")]
+pub use tar_archive::{create_tar_from_folder, extract_tar, extract_zip_to_tar_gz, read_tar_contents_into_buffer};
+#[cfg(feature = "notify")]
+pub use watch::{WatchHandle, watch_and_zip};
+pub use zip_entry::{EntryKind, ZipEntry, ZipEntryHandle};
+pub use zip_reader::{
+    EntrySink, ExtractionOptions, ExtractionReport, OpenArchive, OverwritePolicy, ValidationReport, ZipReader,
+    encryption_summary, extract_zip, extract_zip_in_place, extract_zip_map, extract_zip_routed, extract_zip_since,
+    extract_zip_to, extract_zip_with_options, extract_zips, is_valid_zip, is_zip_file, list_zip_entries,
+    list_zip_entries_with_encoding, open_zip, peek_entry, read_zip_archive_comment, read_zip_contents_as_pathmap,
+    read_zip_contents_bounded, read_zip_contents_from_bytes, read_zip_contents_into_buffer, read_zip_contents_lenient,
+    read_zip_contents_with_options, read_zip_contents_ordered, read_zip_entries_chunked, read_zip_entry_as_string,
+    read_zip_entry_range, read_zip_entry_raw, read_zip_into_sink, read_zip_range, total_uncompressed_size,
+    validate_entries, zip_contains, zip_entry_count, zip_entry_names,
+};
+#[cfg(feature = "zstd")]
+pub use zip_writer::{ZSTD_DICTIONARY_NAME, create_zip_with_zstd_dict, create_zip_zstd_from_folder};
+pub use zip_writer::{
+    ArchiveStats, COMPRESSED_EXTENSIONS, CompressionBackend, CreationOptions, ErrorPolicy, create_incremental_zip,
+    create_split_zips_from_folder, create_zip_from_file, create_zip_from_files, create_zip_from_files_buffered,
+    create_zip_from_files_keep_paths, create_zip_from_files_with_options, create_zip_from_folder,
+    create_zip_from_folder_level, create_zip_from_folder_parallel, create_zip_from_folder_smart,
+    create_zip_from_folder_with_mode, create_zip_from_folder_with_options, create_zip_from_glob, create_zip_from_map,
+    create_zip_from_path_list, create_zip_from_stdin, create_zip_with_stored_entries, merge_split_zips, recompress_zip,
+    set_zip_archive_comment, split_zip, zip_file_size_estimate, zip_files_to_bytes,
+};
@@ -0,0 +1,47 @@
+use serde_json::{Value, json};
+use std::io::{self, Read, Write};
+
+/// Name of the manifest entry written at the root of the archive.
+pub(crate) const MANIFEST_NAME: &str = "MANIFEST.json";
+
+/// Collects per-file CRC32 and size information while entries are written,
+/// then serializes it as the `MANIFEST.json` entry.
+#[derive(Default)]
+pub(crate) struct Manifest {
+    files: Vec<Value>,
+}
+
+impl Manifest {
+    /// Copies `source` into `sink` while computing its CRC32, and records the result under `name`.
+    pub(crate) fn copy_and_record<R: Read, W: Write>(
+        &mut self,
+        name: &str,
+        source: &mut R,
+        sink: &mut W,
+    ) -> io::Result<u64> {
+        let mut hasher: crc32fast::Hasher = crc32fast::Hasher::new();
+        let mut buffer: [u8; 64 * 1024] = [0; 64 * 1024];
+        let mut size: u64 = 0;
+        loop {
+            let read: usize = source.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            sink.write_all(&buffer[..read])?;
+            size += read as u64;
+        }
+        self.record(name, hasher.finalize(), size);
+        Ok(size)
+    }
+
+    /// Records a file whose CRC32 and size are already known.
+    pub(crate) fn record(&mut self, name: &str, crc32: u32, size: u64) {
+        self.files.push(json!({ "name": name, "crc32": crc32, "size": size }));
+    }
+
+    /// Serializes the manifest as pretty-printed JSON.
+    pub(crate) fn to_json(&self) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec_pretty(&json!({ "files": self.files }))
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+use crate::error::ZipoxideError;
+use crate::zip_reader::{END_OF_CENTRAL_DIRECTORY_SIGNATURE, LOCAL_FILE_HEADER_SIGNATURE, read_zip_contents_from_bytes};
+
+/// Signature of a central directory file header (`PK\x01\x02`).
+const CENTRAL_DIRECTORY_SIGNATURE: [u8; 4] = *b"PK\x01\x02";
+
+/// Size of a local file header before the name and extra field.
+const LOCAL_HEADER_SIZE: usize = 30;
+
+/// General-purpose flag bit set when sizes and CRC32 follow the data in a data descriptor.
+const DATA_DESCRIPTOR_FLAG: u16 = 1 << 3;
+
+/// Size fields holding this value defer to a Zip64 extra field.
+const ZIP64_MARKER: u32 = u32::MAX;
+
+/// A complete entry found by scanning local file headers.
+struct LocalEntry<'a> {
+    /// Local header fields from "version needed" through the uncompressed size, copied as-is into the
+    /// central directory record.
+    fields: &'a [u8],
+    name: &'a [u8],
+    extra: &'a [u8],
+    offset: u32,
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+/// Parses the local file header at `offset`, returning the entry and the offset just past its data.
+///
+/// Returns `None` for headers that are truncated, whose data runs past the end of `bytes`, or whose
+/// sizes are not in the header (data descriptors, Zip64).
+fn parse_local_entry(bytes: &[u8], offset: usize) -> Option<(LocalEntry<'_>, usize)> {
+    let header: &[u8] = bytes.get(offset..offset + LOCAL_HEADER_SIZE)?;
+    let flags: u16 = read_u16(header, 6);
+    let compressed_size: u32 = read_u32(header, 18);
+    let uncompressed_size: u32 = read_u32(header, 22);
+    if flags & DATA_DESCRIPTOR_FLAG != 0 || compressed_size == ZIP64_MARKER || uncompressed_size == ZIP64_MARKER {
+        return None;
+    }
+    let name_start: usize = offset + LOCAL_HEADER_SIZE;
+    let extra_start: usize = name_start + read_u16(header, 26) as usize;
+    let data_start: usize = extra_start + read_u16(header, 28) as usize;
+    let data_end: usize = data_start + compressed_size as usize;
+    if data_end > bytes.len() {
+        return None;
+    }
+    let entry: LocalEntry<'_> = LocalEntry {
+        fields: &header[4..26],
+        name: &bytes[name_start..extra_start],
+        extra: &bytes[extra_start..data_start],
+        offset: u32::try_from(offset).ok()?,
+    };
+    Some((entry, data_end))
+}
+
+/// Finds every complete entry by scanning `bytes` for local file headers, skipping over the data of
+/// each entry found and over anything that does not parse.
+fn scan_local_entries(bytes: &[u8]) -> Vec<LocalEntry<'_>> {
+    let mut entries: Vec<LocalEntry<'_>> = Vec::new();
+    let mut position: usize = 0;
+    while let Some(found) = bytes[position..].windows(4).position(|window| window == LOCAL_FILE_HEADER_SIGNATURE) {
+        let offset: usize = position + found;
+        match parse_local_entry(bytes, offset) {
+            Some((entry, data_end)) => {
+                entries.push(entry);
+                position = data_end;
+            }
+            None => position = offset + 1,
+        }
+    }
+    entries
+}
+
+/// Builds a central directory and end-of-central-directory record describing `entries`, to be appended
+/// to an archive of `archive_size` bytes.
+fn central_directory(entries: &[LocalEntry<'_>], archive_size: usize) -> Vec<u8> {
+    let mut directory: Vec<u8> = Vec::new();
+    for entry in entries {
+        directory.extend_from_slice(&CENTRAL_DIRECTORY_SIGNATURE);
+        directory.extend_from_slice(&entry.fields[0..2]); // version made by: same as version needed
+        directory.extend_from_slice(entry.fields);
+        directory.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+        directory.extend_from_slice(&(entry.extra.len() as u16).to_le_bytes());
+        directory.extend_from_slice(&[0; 6]); // comment length, disk number, internal attributes
+        directory.extend_from_slice(&[0; 4]); // external attributes
+        directory.extend_from_slice(&entry.offset.to_le_bytes());
+        directory.extend_from_slice(entry.name);
+        directory.extend_from_slice(entry.extra);
+    }
+    let directory_size: [u8; 4] = (directory.len() as u32).to_le_bytes();
+    let count: [u8; 2] = (entries.len() as u16).to_le_bytes();
+    directory.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+    directory.extend_from_slice(&[0; 4]); // disk numbers
+    directory.extend_from_slice(&count);
+    directory.extend_from_slice(&count);
+    directory.extend_from_slice(&directory_size);
+    directory.extend_from_slice(&(archive_size as u32).to_le_bytes());
+    directory.extend_from_slice(&[0; 2]); // comment length
+    directory
+}
+
+/// Reads all files of a ZIP archive into memory, recovering what it can from archives whose central
+/// directory is missing or damaged, e.g. after an interrupted download or a full disk.
+///
+/// # Arguments
+/// - `zip_path`: Path to the possibly incomplete ZIP file.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Behavior
+/// - Intact archives are read exactly as by
+///   [`read_zip_contents_from_bytes`](crate::read_zip_contents_from_bytes).  
+/// - Otherwise the file is scanned from the start for `PK\x03\x04` local file headers. Every entry
+///   whose data is complete is recovered; a truncated last entry and any garbage between entries are
+///   skipped.  
+/// - Only entries that record their sizes in the local header can be found this way, so entries
+///   written with a data descriptor (by streaming writers) or as Zip64 are skipped. Recovery handles
+///   archives below 4 GiB with at most 65,535 entries.  
+/// - Directory entries are skipped, as for the other readers.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The file cannot be read.  
+/// - No complete entry can be found; the error is the one from parsing the archive normally.  
+/// - A recovered entry fails to decompress, decrypt, or match its CRC32.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::recover_partial_zip;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let recovered = recover_partial_zip("interrupted-download.zip", None)?;
+///     println!("Recovered {} files", recovered.len());
+///     Ok(())
+/// }
+/// ```
+pub fn recover_partial_zip(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+) -> Result<HashMap<String, Vec<u8>>, ZipoxideError> {
+    let mut bytes: Vec<u8> = fs::read(zip_path.as_ref())?;
+    let parse_error: ZipError = match ZipArchive::new(Cursor::new(&bytes[..])) {
+        Ok(_) => return read_zip_contents_from_bytes(&bytes, password),
+        Err(e) => e,
+    };
+
+    let entries: Vec<LocalEntry<'_>> = scan_local_entries(&bytes);
+    if entries.is_empty() || entries.len() > u16::MAX as usize || bytes.len() > u32::MAX as usize {
+        return Err(parse_error.into());
+    }
+    let directory: Vec<u8> = central_directory(&entries, bytes.len());
+    drop(entries);
+    bytes.extend_from_slice(&directory);
+    read_zip_contents_from_bytes(&bytes, password)
+}
@@ -0,0 +1,149 @@
+use std::fs::{self, FileTimes};
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+use zip::read::read_zipfile_from_stream;
+
+use crate::entry_path::{entry_output_path, is_degenerate_entry_name};
+use crate::error::ZipoxideError;
+use crate::file_times::recorded_file_times;
+use crate::zip_reader::{ExtractionOptions, ExtractionReport, check_extract_target, write_entry_file};
+
+/// Container format of an archive read by [`extract_streaming`].
+///
+/// # Variants
+/// - `Zip`: A ZIP archive, read front to back through its local file headers.  
+/// - `TarGz`: A gzip-compressed tar archive (needs the `tar` feature).  
+/// - `TarBz2`: A bzip2-compressed tar archive (needs the `tar` and `bzip2` features).  
+/// - `Auto`: Detect the format from the first bytes of the stream, see [`ArchiveFormat::detect`].  
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+    TarBz2,
+    #[default]
+    Auto,
+}
+
+impl ArchiveFormat {
+    /// Identifies the format from the leading bytes of an archive: `PK\x03\x04` for ZIP, `\x1f\x8b`
+    /// for gzip and `BZh` for bzip2.
+    ///
+    /// Returns `None` for anything else, including inputs too short to hold a signature. Never returns
+    /// [`ArchiveFormat::Auto`].
+    pub fn detect(header: &[u8]) -> Option<ArchiveFormat> {
+        if header.starts_with(b"PK\x03\x04") {
+            Some(ArchiveFormat::Zip)
+        } else if header.starts_with(b"\x1f\x8b") {
+            Some(ArchiveFormat::TarGz)
+        } else if header.starts_with(b"BZh") {
+            Some(ArchiveFormat::TarBz2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Extracts every entry of a ZIP stream, which has no central directory to seek to.
+fn extract_zip_stream<R: Read>(
+    mut reader: R,
+    extract_path: &Path,
+    options: &ExtractionOptions,
+) -> Result<ExtractionReport, ZipoxideError> {
+    let mut report: ExtractionReport = ExtractionReport { threads: 1, ..Default::default() };
+    while let Some(mut entry) = read_zipfile_from_stream(&mut reader)? {
+        let name: String = entry.name().to_string();
+        let (relative_path, renamed) = entry_output_path(&name, options.sanitize_windows);
+        let output_path: PathBuf = extract_path.join(relative_path);
+        if entry.is_dir() {
+            fs::create_dir_all(&output_path)?;
+        } else if is_degenerate_entry_name(&name) {
+            return Err(ZipoxideError::DegenerateEntryName(name));
+        } else {
+            if let Some(parent_dir) = output_path.parent()
+                && !parent_dir.exists()
+            {
+                fs::create_dir_all(parent_dir)?;
+            }
+            let times: Option<FileTimes> = recorded_file_times(entry.extra_data_fields());
+            write_entry_file(&output_path, &mut entry, None, times)?;
+        }
+        if renamed {
+            report.renamed.push((name, output_path.clone()));
+        }
+        report.extracted.push(output_path);
+    }
+    Ok(report)
+}
+
+/// Extracts a ZIP, `.tar.gz` or `.tar.bz2` archive from a non-seekable stream such as standard input
+/// or an HTTP response body.
+///
+/// # Arguments
+/// - `reader`: Stream positioned at the start of the archive.  
+/// - `extract_path`: Path to the directory where files will be extracted.  
+/// - `format`: Archive format; [`ArchiveFormat::Auto`] (the default) detects it from the first bytes.  
+///
+/// # Behavior
+/// - Entries are extracted one after another as they arrive, with the defaults of [`ExtractionOptions`].
+///   Entry names never escape `extract_path`, as in [`extract_zip_with_options`](crate::extract_zip_with_options).  
+/// - ZIP entries are read from their local headers, so entries written with a trailing data descriptor
+///   (sizes unknown up front) and encrypted entries cannot be extracted, and Unix modes are not restored.  
+/// - Tar archives are handled as by `extract_tar`: only regular files and directories are extracted.  
+///
+/// # Errors
+/// Returns an error if:
+/// - `extract_path` exists but is not a directory ([`ZipoxideError::ExtractTargetNotADirectory`]),
+///   checked before the stream is read.  
+/// - `format` is `Auto` and the stream does not start with a known signature ([`ZipoxideError::Io`]
+///   with [`io::ErrorKind::InvalidData`]).  
+/// - The format needs a feature this build lacks ([`ZipoxideError::FeatureDisabled`]).  
+/// - A file entry's name is empty or `.` ([`ZipoxideError::DegenerateEntryName`]).  
+/// - The stream is truncated or malformed, or a file cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{ArchiveFormat, extract_streaming};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // curl -L https://example.com/release.tar.gz | my-tool
+///     let report = extract_streaming(std::io::stdin().lock(), "output", ArchiveFormat::Auto)?;
+///     println!("Extracted {} paths", report.extracted.len());
+///     Ok(())
+/// }
+/// ```
+pub fn extract_streaming(
+    mut reader: impl Read,
+    extract_path: impl AsRef<Path>,
+    format: ArchiveFormat,
+) -> Result<ExtractionReport, ZipoxideError> {
+    let extract_path: &Path = extract_path.as_ref();
+    check_extract_target(extract_path)?;
+    // Peek at the signature, then put it back in front of the rest of the stream.
+    let mut header: Vec<u8> = Vec::with_capacity(4);
+    (&mut reader).take(4).read_to_end(&mut header)?;
+    let format: ArchiveFormat = match format {
+        ArchiveFormat::Auto => ArchiveFormat::detect(&header)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unrecognised archive signature"))?,
+        format => format,
+    };
+    let reader: io::Chain<Cursor<Vec<u8>>, _> = Cursor::new(header).chain(reader);
+    let options: ExtractionOptions = ExtractionOptions::default();
+
+    match format {
+        ArchiveFormat::Zip | ArchiveFormat::Auto => extract_zip_stream(reader, extract_path, &options),
+        #[cfg(feature = "tar")]
+        ArchiveFormat::TarGz => {
+            let decoder: flate2::read::MultiGzDecoder<_> = flate2::read::MultiGzDecoder::new(reader);
+            crate::tar_archive::unpack_tar(tar::Archive::new(decoder), extract_path, &options)
+        }
+        #[cfg(all(feature = "tar", feature = "bzip2"))]
+        ArchiveFormat::TarBz2 => {
+            let decoder: bzip2::read::MultiBzDecoder<_> = bzip2::read::MultiBzDecoder::new(reader);
+            crate::tar_archive::unpack_tar(tar::Archive::new(decoder), extract_path, &options)
+        }
+        #[cfg(not(feature = "tar"))]
+        ArchiveFormat::TarGz | ArchiveFormat::TarBz2 => Err(ZipoxideError::FeatureDisabled("tar")),
+        #[cfg(all(feature = "tar", not(feature = "bzip2")))]
+        ArchiveFormat::TarBz2 => Err(ZipoxideError::FeatureDisabled("bzip2")),
+    }
+}
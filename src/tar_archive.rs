@@ -0,0 +1,369 @@
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use tar::{Archive, Builder, Entry, EntryType, Header};
+use time::OffsetDateTime;
+use zip::ZipArchive;
+use zip::read::ZipFile;
+
+use crate::config::ZipOxideConfig;
+use crate::entry_path::{entry_output_path, is_degenerate_entry_name, is_unsafe_entry_name};
+use crate::error::ZipoxideError;
+use crate::zip_entry::EntryKind;
+use crate::zip_reader::{
+    ExtractionOptions, ExtractionReport, capacity_hint, check_extract_target, open_entry, restore_unix_mode,
+};
+use crate::zip_writer::{ArchiveStats, ErrorPolicy, walk_folder};
+
+/// Buffer size used for tar files on disk and for extracted files.
+const TAR_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Permissions given to files converted from ZIP entries that store no Unix mode.
+const DEFAULT_FILE_MODE: u32 = 0o644;
+
+/// Permissions given to directories converted from ZIP entries that store no Unix mode.
+const DEFAULT_DIRECTORY_MODE: u32 = 0o755;
+
+/// First two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Opens `tar_path` for reading, transparently decompressing it if it starts with the gzip magic bytes.
+fn open_tar(tar_path: &Path) -> Result<Archive<Box<dyn Read>>, ZipoxideError> {
+    let mut reader: BufReader<File> = BufReader::with_capacity(TAR_BUFFER_SIZE, File::open(tar_path)?);
+    let gzipped: bool = reader.fill_buf()?.starts_with(&GZIP_MAGIC);
+    let reader: Box<dyn Read> = match gzipped {
+        true => Box::new(MultiGzDecoder::new(reader)),
+        false => Box::new(reader),
+    };
+    Ok(Archive::new(reader))
+}
+
+/// Returns `true` for output paths ending in `.gz` or `.tgz`.
+fn wants_gzip(output_tar_path: &Path) -> bool {
+    output_tar_path.extension().is_some_and(|extension| extension == "gz" || extension == "tgz")
+}
+
+/// Returns the UTF-8 name of a tar entry, as the ZIP readers key their results.
+fn tar_entry_name<R: Read>(entry: &Entry<'_, R>) -> Result<String, ZipoxideError> {
+    let name: PathBuf = entry.path()?.into_owned();
+    match name.to_str() {
+        Some(name) => Ok(name.to_string()),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, "Non-UTF8 entry name").into()),
+    }
+}
+
+/// Appends every file and empty subdirectory of `folder_path` to `builder`.
+fn append_folder<W: Write>(builder: &mut Builder<W>, folder_path: &Path) -> Result<ArchiveStats, ZipoxideError> {
+    let mut stats: ArchiveStats = ArchiveStats::default();
+    for (path, kind) in walk_folder(folder_path, ErrorPolicy::AbortOnFirstError)?.0 {
+        let name: &Path = path.strip_prefix(folder_path).map_err(io::Error::other)?;
+        match kind {
+            EntryKind::Directory => builder.append_dir(name, &path)?,
+            EntryKind::File => {
+                stats.uncompressed_size += fs::metadata(&path)?.len();
+                builder.append_path_with_name(&path, name)?;
+            }
+        }
+        stats.entries += 1;
+    }
+    Ok(stats)
+}
+
+/// Extracts the regular files and directories of `archive` below `extract_path`, in archive order.
+///
+/// `strict_names` is checked entry by entry, so callers that must refuse an archive before writing
+/// anything check the names up front.
+pub(crate) fn unpack_tar<R: Read>(
+    mut archive: Archive<R>,
+    extract_path: &Path,
+    options: &ExtractionOptions,
+) -> Result<ExtractionReport, ZipoxideError> {
+    let mut report: ExtractionReport = ExtractionReport { threads: 1, ..Default::default() };
+    for entry in archive.entries()? {
+        let mut entry: Entry<'_, R> = entry?;
+        let entry_type: EntryType = entry.header().entry_type();
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            continue;
+        }
+        let name: String = tar_entry_name(&entry)?;
+        if options.strict_names && is_unsafe_entry_name(&name) {
+            return Err(ZipoxideError::UnsafeEntryName(name));
+        }
+        let (relative_path, renamed) = entry_output_path(&name, options.sanitize_windows);
+        let output_path: PathBuf = extract_path.join(relative_path);
+        if entry_type.is_dir() {
+            fs::create_dir_all(&output_path)?;
+        } else if is_degenerate_entry_name(&name) {
+            return Err(ZipoxideError::DegenerateEntryName(name));
+        } else {
+            if let Some(parent_dir) = output_path.parent()
+                && !parent_dir.exists()
+            {
+                fs::create_dir_all(parent_dir)?;
+            }
+            let mut file: BufWriter<File> = BufWriter::with_capacity(TAR_BUFFER_SIZE, File::create(&output_path)?);
+            io::copy(&mut entry, &mut file)?;
+            file.flush()?;
+            restore_unix_mode(file.get_ref(), entry.header().mode().ok())?;
+        }
+        if renamed {
+            report.renamed.push((name, output_path.clone()));
+        }
+        report.extracted.push(output_path);
+    }
+    Ok(report)
+}
+
+/// Creates a tar archive from the contents of a folder, gzip-compressing it when `output_tar_path`
+/// ends in `.gz` or `.tgz`.
+///
+/// # Arguments
+/// - `output_tar_path`: Path where the resulting `.tar`, `.tar.gz` or `.tgz` archive will be created.  
+/// - `folder_path`: Root folder whose contents (including subdirectories) will be archived.  
+///
+/// # Behavior
+/// - Mirrors [`create_zip_from_folder`](crate::create_zip_from_folder): entry names are relative to
+///   `folder_path` and empty subdirectories are stored as directory entries.  
+/// - File permissions and modification times are taken from the filesystem.  
+/// - Gzip output uses the default compression level.  
+///
+/// # Returns
+/// An [`ArchiveStats`] summary, shared with the ZIP writers.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output_tar_path` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - `folder_path` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - A file cannot be read or the archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_tar_from_folder;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let stats = create_tar_from_folder("backup.tar.gz", "my_folder")?;
+///     println!("{} entries, {} bytes", stats.entries, stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+pub fn create_tar_from_folder(
+    output_tar_path: impl AsRef<Path>,
+    folder_path: impl AsRef<Path>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output_tar_path: &Path = output_tar_path.as_ref();
+    let folder_path: &Path = folder_path.as_ref();
+    if output_tar_path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "output tar path already exists").into());
+    }
+    if !folder_path.is_dir() {
+        return Err(ZipoxideError::NotADirectory(folder_path.to_path_buf()));
+    }
+
+    let sink: BufWriter<File> = BufWriter::with_capacity(TAR_BUFFER_SIZE, File::create(output_tar_path)?);
+    let mut stats: ArchiveStats = match wants_gzip(output_tar_path) {
+        true => {
+            let mut builder: Builder<GzEncoder<BufWriter<File>>> =
+                Builder::new(GzEncoder::new(sink, Compression::default()));
+            let stats: ArchiveStats = append_folder(&mut builder, folder_path)?;
+            builder.into_inner()?.finish()?.flush()?;
+            stats
+        }
+        false => {
+            let mut builder: Builder<BufWriter<File>> = Builder::new(sink);
+            let stats: ArchiveStats = append_folder(&mut builder, folder_path)?;
+            builder.into_inner()?.flush()?;
+            stats
+        }
+    };
+    stats.archive_size = fs::metadata(output_tar_path)?.len();
+    Ok(stats)
+}
+
+/// Extracts a `.tar`, `.tar.gz` or `.tgz` archive, configured through the same [`ExtractionOptions`]
+/// as [`extract_zip_with_options`](crate::extract_zip_with_options).
+///
+/// # Arguments
+/// - `tar_path`: Path to the tar archive; gzip compression is detected from its first bytes.  
+/// - `extract_path`: Path to the directory where files will be extracted.  
+/// - `options`: Extraction settings, see below.  
+///
+/// # Behavior
+/// - Entries are extracted sequentially, in archive order, since tar has no central directory to
+///   parallelise over. [`ExtractionReport::threads`] is always `1`.  
+/// - Regular files and directories are extracted; symlinks, hard links, devices and other special
+///   entries are skipped. Unix permission bits are restored on Unix, as for ZIP archives.  
+/// - `sanitize_windows` and `strict_names` behave as for ZIP archives. With `strict_names` or a
+///   `max_entries` limit (from the options or [`ZipOxideConfig::current`]), the archive is read twice
+///   so that nothing is written before every entry has been checked.  
+/// - `password`, `mmap_threshold`, `threads`, `max_compression_ratio` and `resume` do not apply to tar
+///   archives and are ignored.  
+///
+/// # Errors
+/// Returns an error if:
+/// - `extract_path` exists but is not a directory ([`ZipoxideError::ExtractTargetNotADirectory`]).  
+/// - The archive cannot be opened or is not a valid tar (or gzip) stream.  
+/// - [`ZipoxideError::UnsafeEntryName`] when `strict_names` is set and an entry name is unsafe.  
+/// - [`ZipoxideError::TooManyEntries`] when the archive exceeds `max_entries`.  
+/// - [`ZipoxideError::DegenerateEntryName`] when a file entry's name is empty or `.`.  
+/// - A file or directory cannot be created or written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{ExtractionOptions, extract_tar};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let report = extract_tar("backup.tar.gz", "output", ExtractionOptions::default())?;
+///     println!("Extracted {} paths", report.extracted.len());
+///     Ok(())
+/// }
+/// ```
+pub fn extract_tar(
+    tar_path: impl AsRef<Path>,
+    extract_path: impl AsRef<Path>,
+    options: ExtractionOptions,
+) -> Result<ExtractionReport, ZipoxideError> {
+    let tar_path: &Path = tar_path.as_ref();
+    let extract_path: &Path = extract_path.as_ref();
+    check_extract_target(extract_path)?;
+    let max_entries: Option<usize> = options.max_entries.or(ZipOxideConfig::current().max_entries);
+
+    if options.strict_names || max_entries.is_some() {
+        let mut entry_count: usize = 0;
+        for entry in open_tar(tar_path)?.entries()? {
+            let name: String = tar_entry_name(&entry?)?;
+            if options.strict_names && is_unsafe_entry_name(&name) {
+                return Err(ZipoxideError::UnsafeEntryName(name));
+            }
+            entry_count += 1;
+        }
+        if let Some(max_entries) = max_entries
+            && entry_count > max_entries
+        {
+            return Err(ZipoxideError::TooManyEntries(entry_count));
+        }
+    }
+
+    unpack_tar(open_tar(tar_path)?, extract_path, &options)
+}
+
+/// Reads all files of a `.tar`, `.tar.gz` or `.tgz` archive into memory.
+///
+/// # Arguments
+/// - `tar_path`: Path to the tar archive; gzip compression is detected from its first bytes.  
+///
+/// # Behavior
+/// - Mirrors [`read_zip_contents_into_buffer`](crate::read_zip_contents_into_buffer), reading entries
+///   sequentially since tar has no central directory.  
+/// - Only regular files are returned; directories, links and special entries are skipped.  
+/// - If a name appears more than once (as after `tar --append`), the last occurrence wins, matching
+///   what extracting the archive would leave on disk.  
+///
+/// # Returns
+/// A `HashMap<String, Vec<u8>>` keyed by entry name.
+///
+/// # Errors
+/// Returns an error if the archive cannot be opened or read, or an entry name is not valid UTF-8.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_tar_contents_into_buffer;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     for (name, data) in read_tar_contents_into_buffer("backup.tar.gz")? {
+///         println!("File: {}, Size: {} bytes", name, data.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_tar_contents_into_buffer(tar_path: impl AsRef<Path>) -> Result<HashMap<String, Vec<u8>>, ZipoxideError> {
+    let mut contents: HashMap<String, Vec<u8>> = HashMap::new();
+    for entry in open_tar(tar_path.as_ref())?.entries()? {
+        let mut entry: Entry<'_, Box<dyn Read>> = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name: String = tar_entry_name(&entry)?;
+        let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.size()));
+        entry.read_to_end(&mut buffer)?;
+        contents.insert(name, buffer);
+    }
+    Ok(contents)
+}
+
+/// Builds the tar header for a ZIP entry: type, size, Unix mode and modification time.
+fn zip_entry_header<R: Read>(entry: &ZipFile<'_, R>) -> Header {
+    let mut header: Header = Header::new_gnu();
+    let (entry_type, default_mode) = match entry.is_dir() {
+        true => (EntryType::Directory, DEFAULT_DIRECTORY_MODE),
+        false => (EntryType::Regular, DEFAULT_FILE_MODE),
+    };
+    header.set_entry_type(entry_type);
+    header.set_size(if entry.is_dir() { 0 } else { entry.size() });
+    header.set_mode(entry.unix_mode().map_or(default_mode, |mode| mode & 0o7777));
+    let modified: Option<OffsetDateTime> =
+        entry.last_modified().and_then(|modified| OffsetDateTime::try_from(modified).ok());
+    header.set_mtime(modified.map_or(0, |modified| modified.unix_timestamp().max(0) as u64));
+    header
+}
+
+/// Converts a ZIP archive into a gzip-compressed tar archive, streaming every entry straight from one
+/// to the other.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to convert.  
+/// - `output_tar_gz`: Path where the `.tar.gz` archive will be created.  
+/// - `password`: Optional password used to decrypt encrypted entries.  
+///
+/// # Behavior
+/// - Entries are copied in central-directory order with their names, sizes, Unix permissions and
+///   modification times. Entries without a stored Unix mode get `0644` (files) or `0755` (directories).  
+/// - Each entry is decompressed while it is compressed into the tar stream, so no entry is held in
+///   memory in full and nothing is written besides `output_tar_gz`.  
+/// - Gzip output uses the default compression level.  
+///
+/// # Errors
+/// Returns an error if:
+/// - `output_tar_gz` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - The ZIP file cannot be opened or parsed, or an entry cannot be decompressed or decrypted.  
+/// - An entry name cannot be stored in a tar archive (e.g. it contains `..`).  
+/// - The output cannot be written. A partly written `output_tar_gz` is left in place.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::extract_zip_to_tar_gz;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     extract_zip_to_tar_gz("release.zip", "release.tar.gz", None)?;
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zip_to_tar_gz(
+    zip_path: impl AsRef<Path>,
+    output_tar_gz: impl AsRef<Path>,
+    password: Option<&str>,
+) -> Result<(), ZipoxideError> {
+    let output_tar_gz: &Path = output_tar_gz.as_ref();
+    if output_tar_gz.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "output tar path already exists").into());
+    }
+    let reader: BufReader<File> = BufReader::with_capacity(TAR_BUFFER_SIZE, File::open(zip_path.as_ref())?);
+    let mut zip_archive: ZipArchive<BufReader<File>> = ZipArchive::new(reader)?;
+
+    let sink: BufWriter<File> = BufWriter::with_capacity(TAR_BUFFER_SIZE, File::create(output_tar_gz)?);
+    let mut builder: Builder<GzEncoder<BufWriter<File>>> = Builder::new(GzEncoder::new(sink, Compression::default()));
+    for index in 0..zip_archive.len() {
+        let mut entry: ZipFile<'_, BufReader<File>> = open_entry(&mut zip_archive, index, password)?;
+        let mut header: Header = zip_entry_header(&entry);
+        let name: String = entry.name().to_string();
+        match entry.is_dir() {
+            true => builder.append_data(&mut header, name, io::empty())?,
+            false => builder.append_data(&mut header, name, &mut entry)?,
+        }
+    }
+    builder.into_inner()?.finish()?.flush()?;
+    Ok(())
+}
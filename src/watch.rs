@@ -0,0 +1,413 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader};
+use std::iter;
+use std::path::{self, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::SystemTime;
+use zip::read::ZipFile;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::error::ZipoxideError;
+use crate::zip_entry::EntryKind;
+use crate::zip_writer::{
+    ArchiveStats, CreationOptions, ErrorPolicy, PendingEntry, baseline_checksums, file_crc32, invalid_input_path,
+    walk_folder, write_entries,
+};
+
+/// Suffix of the archive being rebuilt, renamed over the output once complete.
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// Size and modification time of a file, compared to spot changes.
+type FileState = (u64, Option<SystemTime>);
+
+/// What the background thread receives: filesystem events from `notify`, or the request to stop.
+#[derive(Debug)]
+enum Message {
+    Event(notify::Result<Event>),
+    Stop,
+}
+
+/// Background watcher started by [`watch_and_zip`].
+///
+/// Dropping the handle stops the watcher too, discarding any error it hit; call [`WatchHandle::stop`]
+/// to see that error.
+#[derive(Debug)]
+pub struct WatchHandle {
+    messages: Sender<Message>,
+    thread: Option<JoinHandle<Result<(), ZipoxideError>>>,
+}
+
+impl WatchHandle {
+    /// Stops watching and waits for the background thread to finish.
+    ///
+    /// Changes made before the call are picked up by one last scan of the folder, so the archive is up
+    /// to date once this returns, even if their events had not been delivered yet.
+    ///
+    /// # Errors
+    /// Returns the error that stopped the watcher early, if any (an unreadable file, a failed write), or
+    /// [`ZipoxideError::ConcurrencyError`] if the thread panicked.
+    pub fn stop(mut self) -> Result<(), ZipoxideError> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<(), ZipoxideError> {
+        let Some(thread) = self.thread.take() else {
+            return Ok(());
+        };
+        // A thread that already returned has dropped its receiver; its result is collected below.
+        let _ = self.messages.send(Message::Stop);
+        thread.join().map_err(|_| ZipoxideError::ConcurrencyError("watcher thread panicked".to_string()))?
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+/// Returns the current state of `path`, or `None` if it no longer exists.
+fn file_state(path: &Path) -> io::Result<Option<FileState>> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(Some((metadata.len(), metadata.modified().ok()))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Records `files` in `known`, returning the ones that are new or changed, in sorted order.
+fn record_changes(known: &mut HashMap<PathBuf, FileState>, files: HashMap<PathBuf, FileState>) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = Vec::new();
+    for (path, state) in files {
+        if known.get(&path) != Some(&state) {
+            changed.push(path.clone());
+            known.insert(path, state);
+        }
+    }
+    changed.sort_unstable();
+    changed
+}
+
+/// Watched folder and the archive mirroring it.
+struct Watcher {
+    watch_dir: PathBuf,
+    /// `watch_dir` with symlinks resolved, for backends that report canonical paths.
+    canonical_dir: PathBuf,
+    output: PathBuf,
+    temp: PathBuf,
+    /// `output` and `temp` made absolute, so they can be left out wherever they are reported from.
+    excluded: [PathBuf; 2],
+    options: CreationOptions,
+}
+
+impl Watcher {
+    /// Maps a path reported by `notify` below `watch_dir`, or `None` for the archive itself.
+    fn local_path(&self, path: PathBuf) -> io::Result<Option<PathBuf>> {
+        let path: PathBuf = match path.strip_prefix(&self.canonical_dir) {
+            Ok(relative) if !path.starts_with(&self.watch_dir) => self.watch_dir.join(relative),
+            _ => path,
+        };
+        match self.excluded.contains(&path::absolute(&path)?) {
+            true => Ok(None),
+            false => Ok(Some(path)),
+        }
+    }
+
+    /// Fails for a path that was skipped although it still exists, unless `on_error` is
+    /// [`ErrorPolicy::SkipAndCollect`]. Paths deleted in the meantime are simply gone.
+    fn check_skipped(&self, path: &Path) -> Result<(), ZipoxideError> {
+        match fs::symlink_metadata(path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            _ if self.options.on_error == ErrorPolicy::SkipAndCollect => Ok(()),
+            _ => Err(io::Error::other(format!("{} could not be read", path.display())).into()),
+        }
+    }
+
+    /// Lists the files below `root` with their current state, leaving out the archive itself and
+    /// anything deleted while the folder is being walked.
+    fn scan(&self, root: &Path) -> Result<HashMap<PathBuf, FileState>, ZipoxideError> {
+        let (found, skipped) = match walk_folder(root, ErrorPolicy::SkipAndCollect) {
+            Ok(listing) => listing,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && root != self.watch_dir => return Ok(HashMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+        for directory in &skipped {
+            self.check_skipped(directory)?;
+        }
+        let mut files: HashMap<PathBuf, FileState> = HashMap::new();
+        for (path, kind) in found {
+            if kind == EntryKind::Directory || self.excluded.contains(&path::absolute(&path)?) {
+                continue;
+            }
+            if let Some(state) = file_state(&path)? {
+                files.insert(path, state);
+            }
+        }
+        Ok(files)
+    }
+
+    /// Rescans the whole folder, returning the files that appeared or changed since `known` was recorded.
+    fn rescan(&self, known: &mut HashMap<PathBuf, FileState>) -> Result<Vec<PathBuf>, ZipoxideError> {
+        let current: HashMap<PathBuf, FileState> = self.scan(&self.watch_dir)?;
+        known.retain(|path, _| current.contains_key(path));
+        Ok(record_changes(known, current))
+    }
+
+    /// Checks the paths named by filesystem events, returning the files that appeared or changed.
+    ///
+    /// A directory is walked, since files may land in a new directory before it is watched. A path
+    /// that no longer exists is a removal and is forgotten, with everything below it.
+    fn refresh(
+        &self,
+        paths: BTreeSet<PathBuf>,
+        known: &mut HashMap<PathBuf, FileState>,
+    ) -> Result<Vec<PathBuf>, ZipoxideError> {
+        let mut current: HashMap<PathBuf, FileState> = HashMap::new();
+        for path in paths {
+            let Some(path) = self.local_path(path)? else {
+                continue;
+            };
+            if path.is_dir() {
+                current.extend(self.scan(&path)?);
+                continue;
+            }
+            match file_state(&path)? {
+                Some(state) => {
+                    current.insert(path, state);
+                }
+                None => known.retain(|known_path, _| !known_path.starts_with(&path)),
+            }
+        }
+        Ok(record_changes(known, current))
+    }
+
+    /// Builds the pending entry for a file below the watched folder.
+    fn entry(&self, path: PathBuf) -> Result<PendingEntry, ZipoxideError> {
+        let root: &Path = self.options.strip_prefix.as_deref().unwrap_or(&self.watch_dir);
+        let name: String = match path.strip_prefix(root).ok().and_then(Path::to_str) {
+            Some(name) => name.to_string(),
+            None => return Err(invalid_input_path(&path)),
+        };
+        Ok(PendingEntry { source_path: path, name, kind: EntryKind::File })
+    }
+
+    /// Writes `paths` to the archive: appended after the existing entries when they are all new, or
+    /// replacing the entries of the same name through a rewrite otherwise.
+    ///
+    /// Files that can no longer be read are left out and forgotten, so a later change retries them.
+    fn update(&self, paths: Vec<PathBuf>, known: &mut HashMap<PathBuf, FileState>) -> Result<(), ZipoxideError> {
+        let entries: Vec<PendingEntry> = paths.into_iter().map(|path| self.entry(path)).collect::<Result<_, _>>()?;
+        // A file deleted after its event must not fail the write; skipped files are checked below.
+        let options: CreationOptions =
+            CreationOptions { on_error: ErrorPolicy::SkipAndCollect, ..self.options.clone() };
+        let stats: ArchiveStats = match self.output.exists() {
+            true => {
+                let archive: ZipArchive<BufReader<File>> = ZipArchive::new(BufReader::new(File::open(&self.output)?))?;
+                let archived: HashSet<&str> = archive.file_names().collect();
+                match entries.iter().any(|entry| archived.contains(entry.name.as_str())) {
+                    true => self.rewrite(entries, &options)?,
+                    false => self.append(entries, &options)?,
+                }
+            }
+            false => self.rewrite(entries, &options)?,
+        };
+        for path in stats.skipped_files {
+            known.remove(&path);
+            self.check_skipped(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Appends `entries` to the existing archive in place, without touching the entries already in it.
+    fn append(&self, entries: Vec<PendingEntry>, options: &CreationOptions) -> Result<ArchiveStats, ZipoxideError> {
+        let zip_file: File = OpenOptions::new().read(true).write(true).open(&self.output)?;
+        let zip_writer: ZipWriter<File> = ZipWriter::new_append(zip_file)?;
+        let (zip_file, stats) = write_entries(zip_writer, entries, SimpleFileOptions::default(), options)?;
+        if options.sync {
+            zip_file.sync_all()?;
+        }
+        Ok(stats)
+    }
+
+    /// Rewrites the archive with `entries` added, or replacing the entries of the same name.
+    ///
+    /// Unchanged entries are raw-copied into a temporary archive, the new files are compressed after
+    /// them, and the result is renamed over the output, so readers never see a half-written archive.
+    fn rewrite(&self, entries: Vec<PendingEntry>, options: &CreationOptions) -> Result<ArchiveStats, ZipoxideError> {
+        let replaced: HashSet<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        let mut zip_writer: ZipWriter<File> = ZipWriter::new(File::create(&self.temp)?);
+        if self.output.exists() {
+            let reader: BufReader<File> = BufReader::new(File::open(&self.output)?);
+            let mut previous: ZipArchive<BufReader<File>> = ZipArchive::new(reader)?;
+            for index in 0..previous.len() {
+                let entry: ZipFile<'_, BufReader<File>> = previous.by_index_raw(index)?;
+                if !replaced.contains(entry.name()) {
+                    zip_writer.raw_copy_file(entry)?;
+                }
+            }
+        }
+        let (zip_file, stats) = write_entries(zip_writer, entries, SimpleFileOptions::default(), options)?;
+        if options.sync {
+            zip_file.sync_all()?;
+        }
+        fs::rename(&self.temp, &self.output)?;
+        Ok(stats)
+    }
+
+    /// Updates the archive from the events sent by `notify`, until [`WatchHandle`] asks to stop.
+    ///
+    /// Events that arrive together are handled as one batch, so a burst of writes costs one update.
+    /// `_fs_watcher` is kept alive for as long as events are wanted.
+    fn run(
+        self,
+        mut known: HashMap<PathBuf, FileState>,
+        messages: Receiver<Message>,
+        _fs_watcher: RecommendedWatcher,
+    ) -> Result<(), ZipoxideError> {
+        loop {
+            let first: Message = messages.recv().unwrap_or(Message::Stop);
+            let mut paths: BTreeSet<PathBuf> = BTreeSet::new();
+            let (mut rescan, mut stop) = (false, false);
+            for message in iter::once(first).chain(messages.try_iter()) {
+                match message {
+                    Message::Event(Ok(event)) if event.need_rescan() => rescan = true,
+                    // Opening and reading files, including our own reads, changes nothing.
+                    Message::Event(Ok(Event { kind: EventKind::Access(_), .. })) => {}
+                    Message::Event(Ok(event)) => paths.extend(event.paths),
+                    // The backend lost track of the folder; only a full scan can catch up.
+                    Message::Event(Err(_)) => rescan = true,
+                    Message::Stop => stop = true,
+                }
+            }
+            // The last scan also covers changes whose events are still on their way.
+            let changed: Vec<PathBuf> = match rescan || stop {
+                true => self.rescan(&mut known)?,
+                false => self.refresh(paths, &mut known)?,
+            };
+            if !changed.is_empty() {
+                self.update(changed, &mut known)?;
+            }
+            if stop {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Keeps a ZIP archive in sync with a folder, adding new and changed files from a background thread.
+///
+/// Requires the `notify` feature.
+///
+/// # Arguments
+/// - `watch_dir`: Folder to watch, including its subdirectories.  
+/// - `output_zip`: Archive to keep up to date. An existing archive is updated, otherwise it is created.  
+/// - `options`: Archive-level settings applied to the files written, as for
+///   [`create_zip_from_folder_with_options`](crate::create_zip_from_folder_with_options). Entry names are
+///   relative to `watch_dir` unless `strip_prefix` is set.  
+///
+/// # Behavior
+/// - Before returning, files missing from `output_zip` or whose CRC32 differs from the archived entry are
+///   written, so the archive starts out matching the folder.  
+/// - A background thread then receives filesystem events for `watch_dir` through the `notify` crate
+///   (inotify, FSEvents, ReadDirectoryChangesW, ...) and checks only the paths they name. New files are
+///   appended to `output_zip` in place, leaving the existing entries untouched. Changed files replace
+///   their entries, which needs a rewrite: unchanged entries are copied over without recompression into
+///   a file with a `.tmp` suffix, renamed over `output_zip` once complete.  
+/// - Files deleted before they could be written, and directories deleted while being walked, are
+///   treated as removed. Deleted files keep their entries, and empty directories are not recorded.  
+/// - With `on_error` left at [`ErrorPolicy::AbortOnFirstError`], a file or directory that exists but
+///   cannot be read stops the watcher; with [`ErrorPolicy::SkipAndCollect`] it is left out until it
+///   changes again.  
+/// - `output_zip` and its temporary file are ignored when they lie inside `watch_dir`.  
+/// - `embed_manifest` is not supported, since each update writes only part of the archive.  
+///
+/// # Returns
+/// A [`WatchHandle`]; [`WatchHandle::stop`] ends the watcher after a final scan.
+///
+/// # Errors
+/// Returns an error if:
+/// - `watch_dir` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - `options.embed_manifest` is set ([`ZipoxideError::Io`] with [`std::io::ErrorKind::InvalidInput`]).  
+/// - The platform's event backend cannot watch `watch_dir` (e.g. the inotify watch limit is reached).  
+/// - The existing archive cannot be read, or the initial update fails.  
+///
+/// Errors hit by the background thread stop it and are returned by [`WatchHandle::stop`].
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{CreationOptions, watch_and_zip};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let watcher = watch_and_zip("logs", "logs-archive.zip", CreationOptions::default())?;
+///     std::thread::sleep(std::time::Duration::from_secs(60));
+///     watcher.stop()?;
+///     Ok(())
+/// }
+/// ```
+pub fn watch_and_zip(
+    watch_dir: impl AsRef<Path>,
+    output_zip: impl AsRef<Path>,
+    options: CreationOptions,
+) -> Result<WatchHandle, ZipoxideError> {
+    let watch_dir: &Path = watch_dir.as_ref();
+    let output: &Path = output_zip.as_ref();
+    if !watch_dir.is_dir() {
+        return Err(ZipoxideError::NotADirectory(watch_dir.to_path_buf()));
+    }
+    if options.embed_manifest {
+        let message: &str = "embed_manifest is not supported when watching a folder";
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+    }
+    let mut temp: OsString = output.as_os_str().to_owned();
+    temp.push(TEMP_SUFFIX);
+    let temp: PathBuf = PathBuf::from(temp);
+    let watcher: Watcher = Watcher {
+        watch_dir: watch_dir.to_path_buf(),
+        canonical_dir: fs::canonicalize(watch_dir)?,
+        output: output.to_path_buf(),
+        excluded: [path::absolute(output)?, path::absolute(&temp)?],
+        temp,
+        options,
+    };
+
+    // Subscribe before the initial scan, so nothing changed during it goes unnoticed.
+    let (sender, messages) = mpsc::channel::<Message>();
+    let event_sender: Sender<Message> = sender.clone();
+    let mut fs_watcher: RecommendedWatcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        // The receiver is gone once the watcher thread has returned; later events are moot.
+        let _ = event_sender.send(Message::Event(event));
+    })?;
+    fs_watcher.watch(watch_dir, RecursiveMode::Recursive)?;
+
+    let mut known: HashMap<PathBuf, FileState> = watcher.scan(watch_dir)?;
+    let archived: HashMap<String, u32> = match output.exists() {
+        true => baseline_checksums(output)?,
+        false => HashMap::new(),
+    };
+    let mut changed: Vec<PathBuf> = Vec::new();
+    for path in known.keys() {
+        let name: String = watcher.entry(path.clone())?.name;
+        let crc32: u32 = match file_crc32(path) {
+            Ok(crc32) => crc32,
+            // Deleted since the scan; its removal event is already queued.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        if archived.get(&name) != Some(&crc32) {
+            changed.push(path.clone());
+        }
+    }
+    if !changed.is_empty() || !output.exists() {
+        changed.sort_unstable();
+        watcher.update(changed, &mut known)?;
+    }
+
+    let thread: JoinHandle<Result<(), ZipoxideError>> =
+        thread::spawn(move || watcher.run(known, messages, fs_watcher));
+    Ok(WatchHandle { messages: sender, thread: Some(thread) })
+}
@@ -0,0 +1,68 @@
+use std::io::Read;
+use zip::read::ZipFile;
+use zip::{CompressionMethod, DateTime};
+
+/// Whether an archive entry holds file data or only marks a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    File,
+    Directory,
+}
+
+/// Central-directory metadata of a single archive entry, as returned by
+/// [`list_zip_entries`](crate::list_zip_entries).
+///
+/// # Fields
+/// - `name`: Entry name as stored in the archive (directories end with `/`).
+/// - `kind`: [`EntryKind::File`] or [`EntryKind::Directory`], taken from the entry metadata
+///   rather than guessed from the name.
+/// - `size`: Declared uncompressed size in bytes.
+/// - `compressed_size`: Size of the stored (compressed) data in bytes.
+/// - `compression_method`: Method used to store the entry data.
+/// - `encrypted`: Whether the entry data is encrypted.
+/// - `crc32`: Declared CRC32 of the uncompressed data.
+/// - `last_modified`: DOS modification timestamp, if one is stored.
+#[derive(Debug, Clone)]
+pub struct ZipEntry {
+    pub name: String,
+    pub kind: EntryKind,
+    pub size: u64,
+    pub compressed_size: u64,
+    pub compression_method: CompressionMethod,
+    pub encrypted: bool,
+    pub crc32: u32,
+    pub last_modified: Option<DateTime>,
+}
+
+impl ZipEntry {
+    /// Returns `true` if this entry is a directory marker rather than a file.
+    pub fn is_directory(&self) -> bool {
+        self.kind == EntryKind::Directory
+    }
+
+    /// Builds the metadata snapshot of an opened entry.
+    pub(crate) fn from_zip_file<R: Read>(entry: &ZipFile<'_, R>) -> Self {
+        ZipEntry {
+            name: entry.name().to_string(),
+            kind: if entry.is_dir() { EntryKind::Directory } else { EntryKind::File },
+            size: entry.size(),
+            compressed_size: entry.compressed_size(),
+            compression_method: entry.compression(),
+            encrypted: entry.encrypted(),
+            crc32: entry.crc32(),
+            last_modified: entry.last_modified(),
+        }
+    }
+}
+
+/// An entry of an archive opened with [`ZipReader`](crate::ZipReader): its position in the central
+/// directory together with its metadata.
+///
+/// # Fields
+/// - `index`: Position of the entry in the central directory, stable for the lifetime of the reader.
+/// - `entry`: The entry's [`ZipEntry`] metadata.
+#[derive(Debug, Clone)]
+pub struct ZipEntryHandle {
+    pub index: usize,
+    pub entry: ZipEntry,
+}
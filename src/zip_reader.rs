@@ -1,13 +1,334 @@
+use filetime::FileTime;
+#[cfg(all(feature = "mmap", not(target_arch = "wasm32")))]
 use memmap2::Mmap;
 use rayon::prelude::*;
-use std::collections::HashMap;
-use std::fs::{self, File};
-use std::io::{self, Cursor};
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File, FileTimes};
+use std::io::{self, BufReader, BufWriter, Cursor, Read, Seek, Write};
 use std::path::Path;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use zip::ZipArchive;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+use zip::{CompressionMethod, ZipArchive};
 use zip::read::ZipFile;
+use zip::result::ZipError;
+
+use crate::entry_path::{decode_entry_name, entry_output_path, is_degenerate_entry_name, is_unsafe_entry_name};
+use crate::config::ZipOxideConfig;
+use crate::error::ZipoxideError;
+use crate::file_times::{entry_modified_time, recorded_file_times};
+use crate::zip_entry::{ZipEntry, ZipEntryHandle};
+
+/// Buffer size used when writing extracted files, so small `io::copy` chunks don't each become a syscall.
+const EXTRACT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Signature at the start of every local file header (`PK\x03\x04`).
+pub(crate) const LOCAL_FILE_HEADER_SIGNATURE: [u8; 4] = *b"PK\x03\x04";
+
+/// Signature of the end-of-central-directory record (`PK\x05\x06`), which is where an empty archive starts.
+pub(crate) const END_OF_CENTRAL_DIRECTORY_SIGNATURE: [u8; 4] = *b"PK\x05\x06";
+
+/// Size of the end-of-central-directory record without its comment.
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+
+/// Signature of the Zip64 end-of-central-directory locator (`PK\x06\x07`), stored right before the
+/// end-of-central-directory record of Zip64 archives.
+const ZIP64_LOCATOR_SIGNATURE: [u8; 4] = *b"PK\x06\x07";
+
+/// Size of the Zip64 end-of-central-directory locator.
+const ZIP64_LOCATOR_SIZE: usize = 20;
+
+/// Signature of the Zip64 end-of-central-directory record (`PK\x06\x06`).
+const ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE: [u8; 4] = *b"PK\x06\x06";
+
+/// In-memory view of a whole archive: a memory map when the `mmap` feature is enabled, an owned
+/// buffer otherwise (and always on `wasm32`, which has no `mmap`).
+#[cfg(all(feature = "mmap", not(target_arch = "wasm32")))]
+type ArchiveBytes = Mmap;
+#[cfg(not(all(feature = "mmap", not(target_arch = "wasm32"))))]
+type ArchiveBytes = Vec<u8>;
+
+/// Makes the archive at `zip_path` available for random access as an [`ArchiveBytes`].
+#[cfg(all(feature = "mmap", not(target_arch = "wasm32")))]
+fn load_archive(zip_path: &Path) -> io::Result<ArchiveBytes> {
+    let file: File = File::open(zip_path)?;
+    unsafe { Mmap::map(&file) }
+}
+
+/// Makes the archive at `zip_path` available for random access as an [`ArchiveBytes`].
+#[cfg(not(all(feature = "mmap", not(target_arch = "wasm32"))))]
+fn load_archive(zip_path: &Path) -> io::Result<ArchiveBytes> {
+    fs::read(zip_path)
+}
+
+/// Counting semaphore limiting how many entries are buffered in memory at the same time.
+struct BufferPermits {
+    available: Mutex<usize>,
+    released: Condvar,
+}
+
+/// A held [`BufferPermits`] slot, returned to the pool when dropped.
+struct BufferPermit<'a> {
+    permits: &'a BufferPermits,
+}
+
+impl BufferPermits {
+    fn new(count: usize) -> Self {
+        BufferPermits { available: Mutex::new(count.max(1)), released: Condvar::new() }
+    }
+
+    /// Blocks the calling worker until a slot is free.
+    fn acquire(&self) -> Result<BufferPermit<'_>, ZipoxideError> {
+        let mut available = self.available.lock()?;
+        while *available == 0 {
+            available = self.released.wait(available)?;
+        }
+        *available -= 1;
+        Ok(BufferPermit { permits: self })
+    }
+}
+
+impl Drop for BufferPermit<'_> {
+    fn drop(&mut self) {
+        // A poisoned pool fails every later `acquire` anyway, so the slot need not be returned.
+        if let Ok(mut available) = self.permits.available.lock() {
+            *available += 1;
+        }
+        self.permits.released.notify_one();
+    }
+}
+
+/// Reader the `zip` crate can parse an archive from, boxed so mapped and file-backed archives share one code path.
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Backing storage workers read an archive from.
+enum ArchiveSource {
+    /// The whole archive, memory-mapped (or read into memory without the `mmap` feature).
+    Mapped(ArchiveBytes),
+    /// An archive above the mmap threshold; every reader opens its own buffered file handle.
+    Seekable(PathBuf),
+}
+
+impl ArchiveSource {
+    /// Memory-maps `zip_path`, unless it is larger than `mmap_threshold` bytes.
+    fn open(zip_path: &Path, mmap_threshold: u64) -> Result<Self, ZipoxideError> {
+        if fs::metadata(zip_path)?.len() > mmap_threshold {
+            return Ok(ArchiveSource::Seekable(zip_path.to_path_buf()));
+        }
+        Ok(ArchiveSource::Mapped(load_archive(zip_path)?)) // memory-map the whole zip
+    }
+
+    /// Opens an independent view of the archive, parsing its central directory.
+    fn archive(&self) -> Result<ZipArchive<Box<dyn ReadSeek + '_>>, ZipoxideError> {
+        let reader: Box<dyn ReadSeek + '_> = match self {
+            ArchiveSource::Mapped(mmap) => Box::new(Cursor::new(&mmap[..])),
+            ArchiveSource::Seekable(path) => {
+                Box::new(BufReader::with_capacity(EXTRACT_BUFFER_SIZE, File::open(path)?))
+            }
+        };
+        Ok(ZipArchive::new(reader)?)
+    }
+}
+
+/// Runs `work` on a dedicated Rayon pool of `threads` workers, or on the global pool when `threads` is `None`.
+/// Parallel iterators inside `work` use the chosen pool.
+fn with_thread_pool<T, F>(threads: Option<usize>, work: F) -> Result<T, ZipoxideError>
+where
+    T: Send,
+    F: FnOnce() -> Result<T, ZipoxideError> + Send,
+{
+    let Some(threads) = threads else {
+        return work();
+    };
+    let pool: rayon::ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    pool.install(work)
+}
+
+/// Applies the Unix permission bits stored for an entry to its extracted file. Set-id and sticky
+/// bits are never restored.
+#[cfg(unix)]
+pub(crate) fn restore_unix_mode(file: &File, mode: Option<u32>) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    match mode {
+        Some(mode) => file.set_permissions(fs::Permissions::from_mode(mode & 0o777)),
+        None => Ok(()),
+    }
+}
+
+/// Unix permission bits have no equivalent on this platform.
+#[cfg(not(unix))]
+pub(crate) fn restore_unix_mode(_file: &File, _mode: Option<u32>) -> io::Result<()> {
+    Ok(())
+}
+
+/// Copies `contents` into a new file at `path` and applies the Unix `mode` and the recorded `times`.
+fn copy_to_file(path: &Path, contents: &mut impl Read, mode: Option<u32>, times: Option<FileTimes>) -> io::Result<()> {
+    let mut file: BufWriter<File> = BufWriter::with_capacity(EXTRACT_BUFFER_SIZE, File::create(path)?);
+    io::copy(contents, &mut file)?;
+    file.flush()?;
+    restore_unix_mode(file.get_ref(), mode)?;
+    match times {
+        Some(times) => file.get_ref().set_times(times),
+        None => Ok(()),
+    }
+}
+
+/// Largest buffer preallocated from an entry's declared size. Bigger entries grow their buffer as they
+/// are read instead.
+const MAX_CAPACITY_HINT: u64 = 64 * 1024 * 1024;
+
+/// Turns an entry's declared uncompressed size into an initial buffer capacity.
+///
+/// Declared sizes are untrusted: streamed entries may record zero, and corrupt or hostile archives any
+/// value up to 16 EiB. Entries are always read to EOF, so the size only saves reallocations, and is
+/// capped at [`MAX_CAPACITY_HINT`] so a bogus value cannot trigger a huge allocation.
+pub(crate) fn capacity_hint(declared_size: u64) -> usize {
+    declared_size.min(MAX_CAPACITY_HINT) as usize
+}
+
+/// Suffix of the temporary file an entry is written to before being renamed to its final name.
+const PART_SUFFIX: &str = ".part";
+
+/// Writes `contents` to `output_path` through a sibling `.part` file that is renamed into place only once
+/// the copy succeeded, applying the Unix `mode` and the modification and access `times` on the way.
+///
+/// A failed copy (disk full, corrupt data, wrong password) therefore never leaves a truncated file under
+/// the final name; the `.part` file is removed and the error returned.
+pub(crate) fn write_entry_file(
+    output_path: &Path,
+    contents: &mut impl Read,
+    mode: Option<u32>,
+    times: Option<FileTimes>,
+) -> io::Result<()> {
+    let mut part_path: OsString = output_path.as_os_str().to_owned();
+    part_path.push(PART_SUFFIX);
+    let part_path: PathBuf = PathBuf::from(part_path);
+    let written: io::Result<()> =
+        copy_to_file(&part_path, contents, mode, times).and_then(|()| fs::rename(&part_path, output_path));
+    if written.is_err() {
+        let _ = fs::remove_file(&part_path);
+    }
+    written
+}
+
+/// Refuses to extract into `extract_path` if it exists as anything but a directory. A missing
+/// `extract_path` is fine; the ZIP extractors create it with [`create_extract_target`] once the
+/// archive has been read, and report any problem reaching it then.
+pub(crate) fn check_extract_target(extract_path: &Path) -> Result<(), ZipoxideError> {
+    match fs::metadata(extract_path) {
+        Ok(metadata) if !metadata.is_dir() => {
+            Err(ZipoxideError::ExtractTargetNotADirectory(extract_path.to_path_buf()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Creates `extract_path` and its missing parents, so a failure names the extraction directory instead
+/// of surfacing as a bare "No such file or directory" from the first file written into it.
+pub(crate) fn create_extract_target(extract_path: &Path) -> Result<(), ZipoxideError> {
+    fs::create_dir_all(extract_path).map_err(|e| {
+        let message: String = format!("could not create extraction directory {}: {e}", extract_path.display());
+        ZipoxideError::Io(io::Error::new(e.kind(), message))
+    })
+}
+
+/// Refuses archives with more than `max_entries` entries, or with an entry whose declared size exceeds
+/// its compressed size by more than `max_compression_ratio`. Only central-directory metadata is read.
+fn check_limits<R: Read + Seek>(
+    zip_archive: &mut ZipArchive<R>,
+    max_entries: Option<usize>,
+    max_compression_ratio: Option<f64>,
+) -> Result<(), ZipoxideError> {
+    if let Some(max_entries) = max_entries
+        && zip_archive.len() > max_entries
+    {
+        return Err(ZipoxideError::TooManyEntries(zip_archive.len()));
+    }
+    if let Some(max_compression_ratio) = max_compression_ratio {
+        for index in 0..zip_archive.len() {
+            let entry: ZipFile<'_, R> = zip_archive.by_index_raw(index)?;
+            if entry.size() as f64 > entry.compressed_size().max(1) as f64 * max_compression_ratio {
+                return Err(ZipoxideError::CompressionRatioExceeded(entry.name().to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Opens the entry at `index`, decrypting it with `password` only if its metadata marks it as encrypted.
+///
+/// Archives mixing encrypted and plaintext entries can thus be read with a single password, without
+/// relying on how the `zip` crate treats a password given for a plaintext entry. AES failures reported by
+/// the `zip` crate are mapped to [`ZipoxideError::AesUnsupported`].
+pub(crate) fn open_entry<'a, R: Read + Seek>(
+    zip_archive: &'a mut ZipArchive<R>,
+    index: usize,
+    password: Option<&str>,
+) -> Result<ZipFile<'a, R>, ZipoxideError> {
+    let password: Option<&str> = match password {
+        Some(v) if zip_archive.by_index_raw(index)?.encrypted() => Some(v),
+        _ => None,
+    };
+    let entry: ZipFile<'a, R> = match password {
+        Some(v) => zip_archive.by_index_decrypt(index, v.as_bytes())?,
+        None => zip_archive.by_index(index)?,
+    };
+    Ok(entry)
+}
+
+/// Reads the entry at `index` of the archive held in `bytes` into memory.
+///
+/// Returns `Ok(None)` for directory entries.
+fn read_entry_bytes(bytes: &[u8], index: usize, password: Option<&str>) -> Result<Option<Vec<u8>>, ZipoxideError> {
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(bytes))?;
+    let mut entry: ZipFile<'_, Cursor<&[u8]>> = open_entry(&mut zip_archive, index, password)?;
+    if entry.is_dir() {
+        return Ok(None);
+    }
+    let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.size()));
+    io::copy(&mut entry, &mut buffer)?;
+    Ok(Some(buffer))
+}
+
+/// Opens the entry called `entry_name` like [`open_entry`].
+///
+/// A missing entry is reported as [`ZipoxideError::EntryNotFound`].
+fn open_entry_by_name<'a, R: Read + Seek>(
+    zip_archive: &'a mut ZipArchive<R>,
+    entry_name: &str,
+    password: Option<&str>,
+) -> Result<ZipFile<'a, R>, ZipoxideError> {
+    match zip_archive.index_for_name(entry_name) {
+        Some(index) => open_entry(zip_archive, index, password),
+        None => Err(ZipoxideError::EntryNotFound(entry_name.to_string())),
+    }
+}
+
+/// Streams every file entry of the archive at `zip_path`, in central-directory order, to `visit`.
+///
+/// Directory entries are skipped. Decompression happens lazily as `visit` reads from the entry.
+fn for_each_entry<F>(zip_path: &Path, password: Option<&str>, mut visit: F) -> Result<(), ZipoxideError>
+where
+    F: FnMut(&mut ZipFile<'_, Cursor<&[u8]>>) -> Result<(), ZipoxideError>,
+{
+    let mmap: ArchiveBytes = load_archive(zip_path)?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    for index in 0..zip_archive.len() {
+        let mut entry: ZipFile<'_, Cursor<&[u8]>> = open_entry(&mut zip_archive, index, password)?;
+        if entry.is_dir() {
+            continue;
+        }
+        visit(&mut entry)?;
+    }
+    Ok(())
+}
 
 /// Extracts the contents of a ZIP archive into a target directory using memory-mapped I/O and parallelism.
 ///
@@ -20,26 +341,40 @@ use zip::read::ZipFile;
 /// # Behavior
 /// - Uses [`memmap2`](https://docs.rs/memmap2/latest/memmap2/) to memory-map the entire ZIP file for efficient random access.  
 /// - Uses [`rayon`](https://docs.rs/rayon/latest/rayon/) to extract files in parallel.  
-/// - Attempts decryption with [`by_index_decrypt`](https://docs.rs/zip/latest/zip/read/struct.ZipArchive.html#method.by_index_decrypt) if `password` is provided.  
-/// - Recreates directory structure as found in the ZIP archive.  
+/// - If `password` is provided, encrypted entries are decrypted with [`by_index_decrypt`](https://docs.rs/zip/latest/zip/read/struct.ZipArchive.html#method.by_index_decrypt); plaintext entries of the same archive are read as-is.  
+/// - Recreates directory structure as found in the ZIP archive, including empty directory entries.  
+/// - On Unix, restores the permission bits stored with each file entry (without set-id or sticky bits).  
+/// - Each file is written to `<name>.part` and renamed once complete, so an entry that fails mid-copy
+///   (disk full, corrupt data) never leaves a truncated file under its final name.  
 /// - Preserves relative paths; directory traversal protection (e.g., stripping `../`) should be added externally if required.  
 ///
 /// # Performance
 /// - Each parallel task re-initializes its own `ZipArchive` view over the shared memory-mapped file.  
 /// - This avoids contention but increases overhead for archives with many entries.  
+/// - Output files are written through a 64 KiB `BufWriter` to reduce syscall overhead for small chunks.  
 ///
 /// # Errors
 /// Returns an error if:
 /// - The ZIP file cannot be opened or memory-mapped.  
-/// - The archive is corrupted or unreadable.  
+/// - The archive is corrupted or unreadable, including when the end-of-central-directory record
+///   claims more entries than the central directory holds.  
+/// - `extract_path` exists but is not a directory ([`ZipoxideError::ExtractTargetNotADirectory`]),
+///   checked before the archive is opened.  
 /// - A file cannot be decrypted with the provided password.  
+/// - An entry is AES-encrypted and AES decryption is unavailable ([`ZipoxideError::AesUnsupported`]).  
+/// - A file entry's name is empty or `.`, naming `extract_path` itself ([`ZipoxideError::DegenerateEntryName`]).  
+/// - `extract_path` cannot be created once the archive has been read; the [`ZipoxideError::Io`] message
+///   names the directory ("could not create extraction directory").  
 /// - Directories or files cannot be created under `extract_path`.  
 /// - File write operations fail.  
 ///
+/// The first failing entry aborts extraction and its error is returned; files extracted before it
+/// are left in place.
+///
 /// # Security Notes
-/// - Only legacy ZipCrypto is supported for decryption. This scheme is weak and may
-///   incorrectly accept invalid passwords due to ZIP spec limitations.  
-/// - AES-encrypted ZIPs may not be supported. Test with your archives before relying on this in production.  
+/// - Legacy ZipCrypto is weak and may incorrectly accept invalid passwords due to ZIP spec limitations.  
+/// - AES-128/192/256 (WinZip AE-1/AE-2) entries are decrypted through the `zip` crate's `aes-crypto` feature.
+///   If AES decryption is unavailable, [`ZipoxideError::AesUnsupported`] is returned.  
 ///
 /// # Panics
 /// - Panics if a file entry in the ZIP archive does not have a valid parent directory path.  
@@ -58,7 +393,6 @@ use zip::read::ZipFile;
 ///     Ok(())
 /// }
 /// ```
-
 #[allow(unused)]
 pub fn extract_zip(
     zip_path: String,
@@ -67,37 +401,694 @@ pub fn extract_zip(
 ) -> Result<(), Box<dyn std::error::Error>> {
     let zip_path: &Path = Path::new(&zip_path);
     let extract_path: &Path = Path::new(&extract_path);
-    let zip_file: File = File::open(zip_path)?;
-    let mmap: Mmap = unsafe { Mmap::map(&zip_file)? }; // memory-map the whole zip
+    check_extract_target(extract_path)?;
+    let mmap: ArchiveBytes = load_archive(zip_path)?; // memory-map the whole zip
     let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(std::io::Cursor::new(&mmap[..]))?;
     let indexes: Vec<usize> = (0..zip_archive.len()).collect();
+    create_extract_target(extract_path)?;
 
     indexes.par_iter().try_for_each(
-        |&index| -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        |&index| -> Result<(), ZipoxideError> {
             let mut zip_archive: ZipArchive<Cursor<&[u8]>> =
                 ZipArchive::new(std::io::Cursor::new(&mmap[..]))?;
-            let mut entry: ZipFile<'_, Cursor<&[u8]>> = match &password {
-                Some(v) => zip_archive.by_index_decrypt(index, v.as_bytes())?,
-                None => zip_archive.by_index(index)?,
-            };
+            let mut entry: ZipFile<'_, Cursor<&[u8]>> = open_entry(&mut zip_archive, index, password.as_deref())?;
             let file_name: &str = entry.name();
             let output_path: PathBuf = extract_path.join(Path::new(file_name));
-            if let Some(parent_dir) = output_path.parent() {
-                if !parent_dir.exists() {
-                    fs::create_dir_all(parent_dir)?;
-                }
+            if entry.is_dir() {
+                fs::create_dir_all(&output_path)?;
+                return Ok(());
+            }
+            if is_degenerate_entry_name(file_name) {
+                return Err(ZipoxideError::DegenerateEntryName(file_name.to_string()));
             }
-            let mut file: File = File::create(output_path)?;
-            io::copy(&mut entry, &mut file)?;
+            if let Some(parent_dir) = output_path.parent()
+                && !parent_dir.exists()
+            {
+                fs::create_dir_all(parent_dir)?;
+            }
+            let mode: Option<u32> = entry.unix_mode();
+            let times: Option<FileTimes> = recorded_file_times(entry.extra_data_fields());
+            write_entry_file(&output_path, &mut entry, mode, times)?;
             Ok(())
         },
-    );
+    )?;
     Ok(())
 }
 
+/// Options controlling [`extract_zip_with_options`] and [`read_zip_contents_with_options`].
+///
+/// # Fields
+/// - `password`: Optional password used to decrypt encrypted entries.  
+/// - `sanitize_windows`: Rewrites path components that cannot be created on Windows (reserved
+///   device names such as `CON` or `aux.txt`, trailing dots and spaces, and `<>:"|?*`) instead of
+///   failing or writing to a device. Defaults to `true` on Windows and `false` elsewhere.  
+/// - `mmap_threshold`: Archives larger than this many bytes are read through buffered, seekable file
+///   handles instead of being memory-mapped. Defaults to `usize::MAX`, so only archives that do not fit
+///   the address space (over 4 GiB on 32-bit targets) take the slower path.  
+/// - `strict_names`: When `true`, extraction is refused with [`ZipoxideError::UnsafeEntryName`] if any
+///   entry name is absolute, contains a `..` component, a drive letter or a backslash, instead of
+///   extracting it below `extract_path` with the offending parts dropped. Defaults to `false`.  
+/// - `threads`: Number of worker threads. `Some(n)` runs the extraction on a dedicated Rayon pool of
+///   `n` threads (useful for benchmarks and reproducible runs); `None` uses the global Rayon pool, which
+///   honours the `RAYON_NUM_THREADS` environment variable.  
+/// - `max_entries`, `max_compression_ratio`: Limits checked against the central directory before
+///   anything is written, see [`ZipOxideConfig`]. `None` falls back to [`ZipOxideConfig::current`].  
+/// - `resume`: When `true`, files that already exist at their target path with the entry's size and
+///   CRC32 are skipped instead of rewritten, so re-running an interrupted extraction only writes what
+///   is missing or different. Skipped files are listed in [`ExtractionReport::skipped`].  
+/// - `name_encoding`: Encoding of entry names stored without the UTF-8 flag, for archives written by
+///   legacy tools in the system code page (e.g. `encoding_rs::SHIFT_JIS`, `encoding_rs::WINDOWS_1251`).
+///   `None` decodes them as CP437, as the ZIP specification requires. Flagged names are always UTF-8.  
+/// - `preserve_dir_timestamps`: When `true`, once every entry is written, each directory created below
+///   the extraction directory gets as modification time the latest timestamp of the entries inside it,
+///   its own directory entry included, since writing files into it moved its time to the present.
+///   Entry times come from NTFS or extended timestamp extra fields, or else the DOS timestamp read as
+///   UTC. Defaults to `false`.  
+#[derive(Debug, Clone)]
+pub struct ExtractionOptions {
+    pub password: Option<String>,
+    pub sanitize_windows: bool,
+    pub mmap_threshold: u64,
+    pub strict_names: bool,
+    pub threads: Option<usize>,
+    pub max_entries: Option<usize>,
+    pub max_compression_ratio: Option<f64>,
+    pub resume: bool,
+    pub name_encoding: Option<&'static encoding_rs::Encoding>,
+    pub preserve_dir_timestamps: bool,
+}
+
+impl Default for ExtractionOptions {
+    fn default() -> Self {
+        ExtractionOptions {
+            password: None,
+            sanitize_windows: cfg!(windows),
+            mmap_threshold: usize::MAX as u64,
+            strict_names: false,
+            threads: None,
+            max_entries: None,
+            max_compression_ratio: None,
+            resume: false,
+            name_encoding: None,
+            preserve_dir_timestamps: false,
+        }
+    }
+}
+
+/// Summary of what [`extract_zip_with_options`] (or `extract_tar`, with the `tar` feature) wrote to disk.
+///
+/// # Fields
+/// - `extracted`: Paths of the extracted files and directories, in central-directory order.  
+/// - `renamed`: `(entry_name, output_path)` for every entry whose path was rewritten by
+///   `sanitize_windows` or had root, drive or `..` components dropped, in central-directory order.  
+/// - `threads`: Number of worker threads the extraction ran on.  
+/// - `skipped`: Paths of files left untouched by `resume` because they already matched, or left out by
+///   a selective extractor such as [`extract_zip_map`], in central-directory order. These are not listed
+///   in `extracted`.  
+#[derive(Debug, Clone, Default)]
+pub struct ExtractionReport {
+    pub extracted: Vec<PathBuf>,
+    pub renamed: Vec<(String, PathBuf)>,
+    pub threads: usize,
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Outcome of extracting a single entry, folded into an [`ExtractionReport`].
+struct ExtractedEntry {
+    name: String,
+    output_path: PathBuf,
+    renamed: bool,
+    skipped: bool,
+}
+
+/// Returns `true` if `path` is an existing file of `size` bytes whose contents hash to `crc32`.
+fn file_matches(path: &Path, size: u64, crc32: u32) -> Result<bool, ZipoxideError> {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() && metadata.len() == size => {}
+        Ok(_) => return Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    }
+    let mut file: File = File::open(path)?;
+    let mut hasher: crc32fast::Hasher = crc32fast::Hasher::new();
+    let mut buffer: Vec<u8> = vec![0; EXTRACT_BUFFER_SIZE];
+    loop {
+        let read: usize = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize() == crc32)
+}
+
+/// Extracts a ZIP archive like [`extract_zip`], configured through [`ExtractionOptions`].
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be extracted.  
+/// - `extract_path`: Path to the directory where files will be extracted.  
+/// - `options`: Extraction settings, see [`ExtractionOptions`].  
+///
+/// # Behavior
+/// - Entries are extracted in parallel over the memory-mapped archive, as in [`extract_zip`]. The
+///   largest entries are scheduled first; the report still lists paths in central-directory order.  
+/// - With `sanitize_windows`, reserved names get a `_` prefix (`CON` → `_CON`, `aux.txt` → `_aux.txt`)
+///   and trailing dots, trailing spaces and invalid characters are replaced with `_`. Every rewritten
+///   entry is listed in [`ExtractionReport::renamed`].  
+/// - Entry names never escape `extract_path`: root, drive and `..` components are dropped (and the entry
+///   is listed in [`ExtractionReport::renamed`]). With `strict_names`, every name is checked before
+///   anything is written and the first unsafe one aborts extraction.  
+/// - Archives larger than `mmap_threshold` are read through per-worker buffered file handles instead
+///   of a memory map.  
+///
+/// # Errors
+/// Same as [`extract_zip`], plus:
+/// - [`ZipoxideError::UnsafeEntryName`] when `strict_names` is set and an entry name is unsafe.  
+/// - [`ZipoxideError::TooManyEntries`] or [`ZipoxideError::CompressionRatioExceeded`] when a limit is exceeded.  
+///
+/// Degenerate file entry names ([`ZipoxideError::DegenerateEntryName`]) and unsafe names are refused
+/// before anything is written.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{ExtractionOptions, extract_zip_with_options};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let options = ExtractionOptions { sanitize_windows: true, ..Default::default() };
+///     let report = extract_zip_with_options("archive.zip", "output", options)?;
+///     for (name, path) in report.renamed {
+///         println!("{name} was extracted as {}", path.display());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zip_with_options(
+    zip_path: impl AsRef<Path>,
+    extract_path: impl AsRef<Path>,
+    options: ExtractionOptions,
+) -> Result<ExtractionReport, ZipoxideError> {
+    extract_routed(zip_path.as_ref(), extract_path.as_ref(), &options, |_, name| Some(name.to_string()))
+}
+
+/// What [`extract_zip_in_place`] does when the directory named after the archive already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverwritePolicy {
+    /// Extract into the existing directory, replacing files of the same name and keeping the others.
+    Overwrite,
+    /// Leave the existing directory alone and extract into the first free `name (2)`, `name (3)`, ...
+    /// next to it, as desktop archive tools do.
+    Rename,
+    /// Fail with [`ZipoxideError::Io`] of kind [`io::ErrorKind::AlreadyExists`].
+    #[default]
+    Error,
+}
+
+/// Extracts a ZIP archive into a directory next to it named after the archive, so `downloads/archive.zip`
+/// is extracted into `downloads/archive/`.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be extracted.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+/// - `policy`: What to do when the directory already exists, see [`OverwritePolicy`].  
+///
+/// # Behavior
+/// - The directory name is the archive's file name without its last extension, so `backup.tar.zip`
+///   becomes `backup.tar/`.  
+/// - The directory is created first; entries are then extracted as by [`extract_zip_with_options`]
+///   with default options.  
+///
+/// # Returns
+/// The directory the archive was extracted into.
+///
+/// # Errors
+/// Returns an error if:
+/// - `zip_path` has no file name ([`ZipoxideError::InvalidPath`]).  
+/// - The directory exists and `policy` is [`OverwritePolicy::Error`] ([`ZipoxideError::Io`] with
+///   [`io::ErrorKind::AlreadyExists`]), or it exists as a file and `policy` is
+///   [`OverwritePolicy::Overwrite`] ([`ZipoxideError::ExtractTargetNotADirectory`]).  
+/// - The directory cannot be created, or extraction fails as for [`extract_zip_with_options`].  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{OverwritePolicy, extract_zip_in_place};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let folder = extract_zip_in_place("downloads/photos.zip", None, OverwritePolicy::Rename)?;
+///     println!("Extracted into {}", folder.display());
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zip_in_place(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+    policy: OverwritePolicy,
+) -> Result<PathBuf, ZipoxideError> {
+    let zip_path: &Path = zip_path.as_ref();
+    let stem: &OsStr = zip_path.file_stem().ok_or_else(|| ZipoxideError::InvalidPath(zip_path.to_path_buf()))?;
+    let extract_path: PathBuf = zip_path.with_file_name(stem);
+    let extract_path: PathBuf = match policy {
+        OverwritePolicy::Overwrite => {
+            check_extract_target(&extract_path)?;
+            create_extract_target(&extract_path)?;
+            extract_path
+        }
+        OverwritePolicy::Error => {
+            fs::create_dir(&extract_path)?;
+            extract_path
+        }
+        OverwritePolicy::Rename => {
+            let mut candidate: PathBuf = extract_path.clone();
+            let mut suffix: usize = 2;
+            // Creating the directory is the existence check, so two callers never pick the same name.
+            loop {
+                match fs::create_dir(&candidate) {
+                    Ok(()) => break candidate,
+                    Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                        let mut name: OsString = stem.to_owned();
+                        name.push(format!(" ({suffix})"));
+                        candidate = zip_path.with_file_name(name);
+                        suffix += 1;
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+    };
+    let options: ExtractionOptions = ExtractionOptions { password: password.map(str::to_string), ..Default::default() };
+    extract_zip_with_options(zip_path, &extract_path, options)?;
+    Ok(extract_path)
+}
+
+/// Extracts only the entries of a ZIP archive modified after `since`, for incremental restores.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be extracted.  
+/// - `extract_path`: Path to the directory where files will be extracted.  
+/// - `since`: Checkpoint; only entries with a modification time strictly after it are written.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Behavior
+/// - Entries are selected by the DOS timestamp stored in the central directory. DOS timestamps carry
+///   no time zone and have a two-second resolution; they are compared as UTC.  
+/// - Entries without a valid timestamp are treated as older than any checkpoint.  
+/// - Otherwise behaves like [`extract_zip_with_options`] with default options; entries that are not
+///   newer are listed in [`ExtractionReport::skipped`].  
+///
+/// # Errors
+/// Same as [`extract_zip_with_options`].
+///
+/// # Example
+/// ```rust,no_run
+/// use time::OffsetDateTime;
+/// use zipoxide::extract_zip_since;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let checkpoint = OffsetDateTime::from_unix_timestamp(1_717_200_000)?; // 2024-06-01 00:00 UTC
+///     let report = extract_zip_since("backup.zip", "restore", checkpoint, None)?;
+///     println!("Restored {} newer entries", report.extracted.len());
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zip_since(
+    zip_path: impl AsRef<Path>,
+    extract_path: impl AsRef<Path>,
+    since: OffsetDateTime,
+    password: Option<&str>,
+) -> Result<ExtractionReport, ZipoxideError> {
+    let options: ExtractionOptions = ExtractionOptions { password: password.map(str::to_string), ..Default::default() };
+    extract_routed(zip_path.as_ref(), extract_path.as_ref(), &options, |entry, name| {
+        let modified: Option<OffsetDateTime> =
+            entry.last_modified().and_then(|modified| OffsetDateTime::try_from(modified).ok());
+        modified.is_some_and(|modified| modified > since).then(|| name.to_string())
+    })
+}
+
+/// Extracts a ZIP archive, choosing the name of every entry on disk with a mapping function.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be extracted.  
+/// - `extract_path`: Path to the directory where files will be extracted.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+/// - `remap`: Called with each entry name; returns the name to extract the entry under, or `None` to
+///   skip it.  
+///
+/// # Behavior
+/// - Remapped names go through the same guard as archive names: root, drive and `..` components are
+///   dropped, so a mapping can never write outside `extract_path`.  
+/// - Entries are extracted in parallel with the defaults of [`ExtractionOptions`]. Skipped entries are
+///   listed in [`ExtractionReport::skipped`] under their original name's path.  
+/// - Directory entries are passed to `remap` with their trailing `/`. When two entries map to the same
+///   name, the one written last wins.  
+///
+/// # Errors
+/// Same as [`extract_zip_with_options`].
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::extract_zip_map;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Drop the top-level folder GitHub adds to source archives.
+///     extract_zip_map("repo-main.zip", "repo", None, |name| {
+///         name.split_once('/').map(|(_, rest)| rest.to_string())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zip_map(
+    zip_path: impl AsRef<Path>,
+    extract_path: impl AsRef<Path>,
+    password: Option<&str>,
+    remap: impl Fn(&str) -> Option<String> + Sync,
+) -> Result<ExtractionReport, ZipoxideError> {
+    let options: ExtractionOptions = ExtractionOptions { password: password.map(str::to_string), ..Default::default() };
+    extract_routed(zip_path.as_ref(), extract_path.as_ref(), &options, |_, name| remap(name))
+}
+
+/// Extracts a ZIP archive, choosing the directory every entry is extracted into with a routing function.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be extracted.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+/// - `route`: Called with each entry name; returns the directory to extract the entry into, or `None`
+///   to skip it.  
+///
+/// # Behavior
+/// - An entry keeps its archive path below the directory it is routed to: routing `docs/a.txt` to
+///   `texts` writes `texts/docs/a.txt`. Use [`extract_zip_map`] to rename entries instead.  
+/// - The traversal guard applies below each routed directory: root, drive and `..` components of the
+///   entry name are dropped, so an entry can never escape the directory it was routed to.  
+/// - Routed directories are created as needed. Entries are extracted in parallel with the defaults of
+///   [`ExtractionOptions`]; skipped entries are listed in [`ExtractionReport::skipped`] by their name
+///   as a relative path.  
+/// - Directory entries are passed to `route` with their trailing `/`.  
+///
+/// # Errors
+/// Returns an error if:
+/// - A routed directory exists but is not a directory ([`ZipoxideError::ExtractTargetNotADirectory`]),
+///   checked before anything is written.  
+/// - Otherwise, the same as [`extract_zip_with_options`].  
+///
+/// # Example
+/// ```rust,no_run
+/// use std::path::PathBuf;
+/// use zipoxide::extract_zip_routed;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     extract_zip_routed("dataset.zip", None, |name| match name.rsplit_once('.') {
+///         Some((_, "csv")) => Some(PathBuf::from("tables")),
+///         Some((_, "png" | "jpg")) => Some(PathBuf::from("images")),
+///         _ => None,
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zip_routed(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+    route: impl Fn(&str) -> Option<PathBuf> + Sync,
+) -> Result<ExtractionReport, ZipoxideError> {
+    let options: ExtractionOptions = ExtractionOptions { password: password.map(str::to_string), ..Default::default() };
+    extract_to_roots(zip_path.as_ref(), &options, |_, name| match route(name) {
+        Some(root) => (root, Some(name.to_string())),
+        None => (PathBuf::new(), None),
+    })
+}
+
+/// Extracts the entries of the archive at `zip_path` below `extract_path`, under the names `route` picks
+/// for them: the core of [`extract_zip_with_options`].
+///
+/// `route` sees each entry's central-directory metadata and decoded name before anything is written,
+/// and returns the name to extract it under. Entries it maps to `None` are listed in
+/// [`ExtractionReport::skipped`].
+fn extract_routed<R>(
+    zip_path: &Path,
+    extract_path: &Path,
+    options: &ExtractionOptions,
+    route: R,
+) -> Result<ExtractionReport, ZipoxideError>
+where
+    R: Fn(&ZipFile<'_, Box<dyn ReadSeek + '_>>, &str) -> Option<String>,
+{
+    check_extract_target(extract_path)?;
+    extract_to_roots(zip_path, options, |entry, name| (extract_path.to_path_buf(), route(entry, name)))
+}
+
+/// Extracts the entries of the archive at `zip_path`, each below the directory `route` picks for it.
+///
+/// `route` returns the directory an entry goes to and the name to extract it under, which is kept
+/// inside that directory. Entries named `None` are listed in [`ExtractionReport::skipped`], under the
+/// directory returned with them.
+fn extract_to_roots<R>(
+    zip_path: &Path,
+    options: &ExtractionOptions,
+    route: R,
+) -> Result<ExtractionReport, ZipoxideError>
+where
+    R: Fn(&ZipFile<'_, Box<dyn ReadSeek + '_>>, &str) -> (PathBuf, Option<String>),
+{
+    let source: ArchiveSource = ArchiveSource::open(zip_path, options.mmap_threshold)?;
+    let mut zip_archive: ZipArchive<Box<dyn ReadSeek + '_>> = source.archive()?;
+
+    let config: ZipOxideConfig = ZipOxideConfig::current();
+    check_limits(
+        &mut source.archive()?,
+        options.max_entries.or(config.max_entries),
+        options.max_compression_ratio.or(config.max_compression_ratio),
+    )?;
+
+    // (index, size, entry name, output path, renamed)
+    let mut schedule: Vec<(usize, u64, String, PathBuf, bool)> = Vec::with_capacity(zip_archive.len());
+    let mut unselected: Vec<(usize, ExtractedEntry)> = Vec::new();
+    let mut roots: HashSet<PathBuf> = HashSet::new();
+    let mut dir_times: HashMap<PathBuf, SystemTime> = HashMap::new();
+    for index in 0..zip_archive.len() {
+        let entry: ZipFile<'_, Box<dyn ReadSeek + '_>> = zip_archive.by_index_raw(index)?;
+        let name: String = decode_entry_name(entry.name(), entry.name_raw(), options.name_encoding);
+        if options.strict_names && is_unsafe_entry_name(&name) {
+            return Err(ZipoxideError::UnsafeEntryName(name));
+        }
+        match route(&entry, &name) {
+            (_, Some(target)) if !entry.is_dir() && is_degenerate_entry_name(&target) => {
+                return Err(ZipoxideError::DegenerateEntryName(name));
+            }
+            (root, Some(target)) => {
+                let (relative_path, renamed) = entry_output_path(&target, options.sanitize_windows);
+                let output_path: PathBuf = root.join(relative_path);
+                if options.preserve_dir_timestamps
+                    && let Some(modified) = entry_modified_time(&entry)
+                {
+                    let own_dir: Option<&Path> = entry.is_dir().then_some(output_path.as_path());
+                    for dir in own_dir.into_iter().chain(output_path.ancestors().skip(1)) {
+                        if dir == root {
+                            break;
+                        }
+                        let latest: &mut SystemTime = dir_times.entry(dir.to_path_buf()).or_insert(modified);
+                        *latest = (*latest).max(modified);
+                    }
+                }
+                if !roots.contains(&root) {
+                    check_extract_target(&root)?;
+                    roots.insert(root);
+                }
+                schedule.push((index, entry.size(), name, output_path, renamed));
+            }
+            (root, None) => {
+                let output_path: PathBuf = root.join(entry_output_path(&name, options.sanitize_windows).0);
+                unselected.push((index, ExtractedEntry { name, output_path, renamed: false, skipped: true }));
+            }
+        }
+    }
+    // Schedule the largest entries first so the pool does not finish on one long-running entry.
+    schedule.sort_by_key(|&(_, size, _, _, _)| std::cmp::Reverse(size));
+    for root in &roots {
+        create_extract_target(root)?;
+    }
+
+    let (outputs, threads) = with_thread_pool(options.threads, || {
+        let mut outputs: Vec<(usize, ExtractedEntry)> = schedule
+            .par_iter()
+            .map(|(index, _, name, output_path, renamed)| -> Result<(usize, ExtractedEntry), ZipoxideError> {
+                let index: usize = *index;
+                let mut zip_archive: ZipArchive<Box<dyn ReadSeek + '_>> = source.archive()?;
+                let mut entry: ZipFile<'_, Box<dyn ReadSeek + '_>> =
+                    open_entry(&mut zip_archive, index, options.password.as_deref())?;
+                let (name, output_path): (String, PathBuf) = (name.clone(), output_path.clone());
+                let mut extracted: ExtractedEntry =
+                    ExtractedEntry { name, output_path, renamed: *renamed, skipped: false };
+                if entry.is_dir() {
+                    fs::create_dir_all(&extracted.output_path)?;
+                    return Ok((index, extracted));
+                }
+                if options.resume && file_matches(&extracted.output_path, entry.size(), entry.crc32())? {
+                    extracted.skipped = true;
+                    return Ok((index, extracted));
+                }
+                if let Some(parent_dir) = extracted.output_path.parent()
+                    && !parent_dir.exists()
+                {
+                    fs::create_dir_all(parent_dir)?;
+                }
+                let mode: Option<u32> = entry.unix_mode();
+                let times: Option<FileTimes> = recorded_file_times(entry.extra_data_fields());
+                write_entry_file(&extracted.output_path, &mut entry, mode, times)?;
+                Ok((index, extracted))
+            })
+            .collect::<Result<_, _>>()?;
+        outputs.extend(unselected);
+        outputs.sort_unstable_by_key(|&(index, _)| index);
+        Ok((outputs, rayon::current_num_threads()))
+    })?;
+    // Only after every entry is written, since adding a file to a directory updates its time.
+    for (dir, modified) in dir_times {
+        filetime::set_file_mtime(&dir, FileTime::from_system_time(modified))?;
+    }
+
+    let mut report: ExtractionReport = ExtractionReport { threads, ..Default::default() };
+    for (_, extracted) in outputs {
+        if extracted.renamed {
+            report.renamed.push((extracted.name, extracted.output_path.clone()));
+        }
+        match extracted.skipped {
+            true => report.skipped.push(extracted.output_path),
+            false => report.extracted.push(extracted.output_path),
+        }
+    }
+    Ok(report)
+}
+
+/// Extracts several ZIP archives into one shared directory, processing the archives in parallel.
+///
+/// # Arguments
+/// - `inputs`: Archives to extract, each with the optional password used to decrypt its entries.  
+/// - `extract_path`: Directory that receives the contents of every archive.  
+///
+/// # Behavior
+/// - All central directories are read first. If file entries of two different archives map to the same
+///   output path, nothing is written and [`ZipoxideError::EntryCollision`] is returned. Duplicate names
+///   within one archive are not a collision; the later entry wins, as with [`extract_zip`].  
+/// - Archives are then extracted in parallel, each one sequentially, so a batch of many archives keeps
+///   one worker per archive instead of multiplying threads.  
+/// - Entry names are mapped to paths below `extract_path` as in [`extract_zip_with_options`]: root,
+///   drive and `..` components are dropped. Empty directory entries are created.  
+/// - Unix permissions and recorded timestamps are restored on the extracted files.  
+///
+/// # Errors
+/// Returns an error if:
+/// - `extract_path` exists but is not a directory ([`ZipoxideError::ExtractTargetNotADirectory`]).  
+/// - An archive cannot be opened, memory-mapped or parsed.  
+/// - A file entry name is degenerate ([`ZipoxideError::DegenerateEntryName`]).  
+/// - Two archives contain a file at the same output path ([`ZipoxideError::EntryCollision`]).  
+/// - An entry cannot be decrypted or decompressed, or a file cannot be written. Archives already being
+///   extracted by other workers may be left partially extracted.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::extract_zips;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let inputs = vec![
+///         ("part-a.zip".to_string(), None),
+///         ("part-b.zip".to_string(), Some("secret".to_string())),
+///     ];
+///     extract_zips(inputs, "dataset")?;
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zips(
+    inputs: Vec<(String, Option<String>)>,
+    extract_path: impl AsRef<Path>,
+) -> Result<(), ZipoxideError> {
+    let extract_path: &Path = extract_path.as_ref();
+    check_extract_target(extract_path)?;
+    let mut owners: HashMap<PathBuf, usize> = HashMap::new();
+    let mut directories: Vec<PathBuf> = Vec::new();
+    for (archive, (zip_path, _)) in inputs.iter().enumerate() {
+        for entry in list_zip_entries(zip_path)? {
+            let (relative_path, _) = entry_output_path(&entry.name, false);
+            if entry.is_directory() {
+                directories.push(relative_path);
+                continue;
+            }
+            if relative_path.as_os_str().is_empty() {
+                return Err(ZipoxideError::DegenerateEntryName(entry.name));
+            }
+            if let Some(owner) = owners.insert(relative_path.clone(), archive)
+                && owner != archive
+            {
+                return Err(ZipoxideError::EntryCollision(extract_path.join(relative_path)));
+            }
+        }
+    }
+    create_extract_target(extract_path)?;
+    for directory in directories {
+        fs::create_dir_all(extract_path.join(directory))?;
+    }
+
+    inputs.par_iter().try_for_each(|(zip_path, password)| {
+        for_each_entry(Path::new(zip_path), password.as_deref(), |entry| {
+            let output_path: PathBuf = extract_path.join(entry_output_path(entry.name(), false).0);
+            if let Some(parent_dir) = output_path.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            let mode: Option<u32> = entry.unix_mode();
+            let times: Option<FileTimes> = recorded_file_times(entry.extra_data_fields());
+            write_entry_file(&output_path, entry, mode, times)?;
+            Ok(())
+        })
+    })
+}
+
+/// Extracts a ZIP archive to a callback instead of the filesystem, handing each file entry's name
+/// and a streaming reader to `sink`.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be extracted.  
+/// - `password`: Optional password used to decrypt encrypted entries.  
+/// - `sink`: Called once per file entry with its name and a reader over its decompressed contents.  
+///
+/// # Behavior
+/// - Entries are visited one at a time in central-directory order; directory entries are skipped.  
+/// - Data is decompressed while `sink` reads it, so nothing is buffered or written to disk by zipoxide.
+///   Useful for uploading entries elsewhere (object storage, another process).  
+/// - Whatever `sink` leaves unread is skipped; the entry's CRC32 is only verified if it is read to the end.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The ZIP file cannot be opened, memory-mapped or parsed.  
+/// - An entry cannot be decrypted with the provided password.  
+/// - `sink` returns an error, which stops extraction and is returned as [`ZipoxideError::Io`].  
+///
+/// # Example
+/// ```rust,no_run
+/// use std::io;
+/// use zipoxide::extract_zip_to;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     extract_zip_to("archive.zip", None, |name, reader| {
+///         let size = io::copy(reader, &mut io::sink())?;
+///         println!("{name}: {size} bytes");
+///         Ok(())
+///     })?;
+///     Ok(())
+/// }
+/// ```
+pub fn extract_zip_to<F>(zip_path: impl AsRef<Path>, password: Option<&str>, mut sink: F) -> Result<(), ZipoxideError>
+where
+    F: FnMut(&str, &mut dyn Read) -> io::Result<()>,
+{
+    for_each_entry(zip_path.as_ref(), password, |entry| {
+        let name: String = entry.name().to_string();
+        sink(&name, entry)?;
+        Ok(())
+    })
+}
+
 /// Reads all files inside a ZIP archive into memory buffers in parallel,
 /// returning a `HashMap` where keys are file names and values are file contents.
 ///
+/// Equivalent to [`read_zip_contents_with_options`] with default options and `password`.
+///
 /// # Arguments
 /// - `zip_path`: Path to the ZIP file to be read.  
 /// - `password`: Optional password used to decrypt encrypted files. If `None`, only
@@ -106,13 +1097,16 @@ pub fn extract_zip(
 /// # Behavior
 /// - Uses [`memmap2`](https://docs.rs/memmap2/latest/memmap2/) to memory-map the entire ZIP file for efficient random access.  
 /// - Uses [`rayon`](https://docs.rs/rayon/latest/rayon/) to read files in parallel.  
-/// - If `password` is provided, attempts to decrypt each entry with [`by_index_decrypt`](https://docs.rs/zip/latest/zip/read/struct.ZipArchive.html#method.by_index_decrypt).  
-/// - Stores each file's full contents into a `Vec<u8>` in memory.  
+/// - If `password` is provided, encrypted entries are decrypted with [`by_index_decrypt`](https://docs.rs/zip/latest/zip/read/struct.ZipArchive.html#method.by_index_decrypt); plaintext entries of the same archive are read as-is.  
+/// - Stores each file's full contents into a `Vec<u8>` in memory; directory entries are skipped.  
+/// - Each entry is read until EOF. Its declared size only presizes the buffer (up to 64 MiB), so entries
+///   from streamed archives with a zero or wrong size in their headers are still read in full.  
 /// - File names are taken directly from the ZIP archive’s metadata (UTF-8 required).  
 ///
 /// # Concurrency Model
 /// - Each worker thread creates its own `ZipArchive` view over the shared memory-mapped file.  
-/// - Results are collected in a thread-safe `Arc<Mutex<HashMap<...>>>` before being unwrapped.  
+/// - Results are collected in a thread-safe `Arc<Mutex<HashMap<...>>>`; lock failures are returned as
+///   errors instead of panicking.  
 /// - Suitable for archives with many medium-sized files; overhead may dominate if there are only a few entries.  
 ///
 /// # Returns
@@ -123,16 +1117,17 @@ pub fn extract_zip(
 /// # Errors
 /// Returns an error if:
 /// - The ZIP file cannot be opened or memory-mapped.  
-/// - The archive is corrupted or unreadable.  
+/// - The archive is corrupted or unreadable, including when the end-of-central-directory record
+///   claims more entries than the central directory holds.  
 /// - A file cannot be extracted or decrypted (wrong password).  
-///
-/// # Panics
-/// - Panics if the `Arc<Mutex<_>>` cannot be unwrapped (only occurs if still shared, which should not happen here).  
+/// - An entry is AES-encrypted and AES decryption is unavailable ([`ZipoxideError::AesUnsupported`]).  
+/// - The shared result map is poisoned or still shared when results are collected
+///   ([`ZipoxideError::ConcurrencyError`]). A failing worker stops the others and its error is returned.  
 ///
 /// # Security Notes
-/// - Only legacy ZipCrypto is supported for decryption. This scheme is weak and may
-///   incorrectly accept invalid passwords due to ZIP spec limitations.  
-/// - AES-encrypted ZIP files may not be supported. Test with your target archives.  
+/// - Legacy ZipCrypto is weak and may incorrectly accept invalid passwords due to ZIP spec limitations.  
+/// - AES-128/192/256 (WinZip AE-1/AE-2) entries are decrypted through the `zip` crate's `aes-crypto` feature.
+///   If AES decryption is unavailable, [`ZipoxideError::AesUnsupported`] is returned.  
 ///
 /// # Example
 /// ```rust,no_run
@@ -156,33 +1151,1444 @@ pub fn read_zip_contents_into_buffer(
     zip_path: String,
     password: Option<String>,
 ) -> Result<HashMap<String, Vec<u8>>, Box<dyn std::error::Error + Send + Sync>> {
-    let zip_path: &Path = Path::new(&zip_path);
-    let file: File = File::open(zip_path)?;
-    let mmap: Mmap = unsafe { Mmap::map(&file)? }; // memory-map the whole zip
+    let options: ExtractionOptions = ExtractionOptions { password, ..Default::default() };
+    Ok(read_zip_contents_with_options(zip_path, options)?)
+}
+
+/// Reads all files inside a ZIP archive into memory like [`read_zip_contents_into_buffer`], configured
+/// through [`ExtractionOptions`].
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `options`: Reading settings, see [`ExtractionOptions`].  
+///
+/// # Behavior
+/// - Entries are read in parallel over the memory-mapped archive, on a dedicated pool when `threads`
+///   is set. Archives larger than `mmap_threshold` are read through per-worker buffered file handles.  
+/// - `max_entries` and `max_compression_ratio` are checked against the central directory before any
+///   entry is decompressed.  
+/// - Keys are the entry names, decoded with `name_encoding` when stored without the UTF-8 flag. With
+///   `strict_names`, an unsafe name aborts reading before anything is decompressed.  
+/// - `sanitize_windows` and `resume` only affect extraction to disk and are ignored.  
+/// - Directory entries are skipped.  
+///
+/// # Errors
+/// Same as [`read_zip_contents_into_buffer`], plus:
+/// - [`ZipoxideError::UnsafeEntryName`] when `strict_names` is set and an entry name is unsafe.  
+/// - [`ZipoxideError::TooManyEntries`] or [`ZipoxideError::CompressionRatioExceeded`] when a limit is exceeded.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{ExtractionOptions, read_zip_contents_with_options};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let options = ExtractionOptions { threads: Some(2), max_entries: Some(1_000), ..Default::default() };
+///     for (name, data) in read_zip_contents_with_options("archive.zip", options)? {
+///         println!("File: {}, Size: {} bytes", name, data.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_contents_with_options(
+    zip_path: impl AsRef<Path>,
+    options: ExtractionOptions,
+) -> Result<HashMap<String, Vec<u8>>, ZipoxideError> {
+    let source: ArchiveSource = ArchiveSource::open(zip_path.as_ref(), options.mmap_threshold)?;
+    let mut zip_archive: ZipArchive<Box<dyn ReadSeek + '_>> = source.archive()?;
+
+    let config: ZipOxideConfig = ZipOxideConfig::current();
+    check_limits(
+        &mut zip_archive,
+        options.max_entries.or(config.max_entries),
+        options.max_compression_ratio.or(config.max_compression_ratio),
+    )?;
+
+    // (index, entry name)
+    let mut schedule: Vec<(usize, String)> = Vec::with_capacity(zip_archive.len());
+    for index in 0..zip_archive.len() {
+        let entry: ZipFile<'_, Box<dyn ReadSeek + '_>> = zip_archive.by_index_raw(index)?;
+        let name: String = decode_entry_name(entry.name(), entry.name_raw(), options.name_encoding);
+        if options.strict_names && is_unsafe_entry_name(&name) {
+            return Err(ZipoxideError::UnsafeEntryName(name));
+        }
+        if !entry.is_dir() {
+            schedule.push((index, name));
+        }
+    }
 
-    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(std::io::Cursor::new(&mmap[..]))?;
     let shared_results: Arc<Mutex<HashMap<String, Vec<u8>>>> = Arc::new(Mutex::new(HashMap::new()));
+    with_thread_pool(options.threads, || {
+        schedule.into_par_iter().try_for_each(|(index, name)| -> Result<(), ZipoxideError> {
+            let mut zip_archive: ZipArchive<Box<dyn ReadSeek + '_>> = source.archive()?;
+            let mut entry: ZipFile<'_, Box<dyn ReadSeek + '_>> =
+                open_entry(&mut zip_archive, index, options.password.as_deref())?;
+            let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.size()));
+            io::copy(&mut entry, &mut buffer)?;
+            shared_results.lock()?.insert(name, buffer);
+            Ok(())
+        })
+    })?;
 
+    let shared_results: Mutex<HashMap<String, Vec<u8>>> = Arc::try_unwrap(shared_results)
+        .map_err(|_| ZipoxideError::ConcurrencyError("results still shared after all workers finished".to_string()))?;
+    Ok(shared_results.into_inner()?)
+}
+
+/// Reads all files of a ZIP archive held in memory, e.g. received over the network or built by
+/// [`zip_files_to_bytes`](crate::zip_files_to_bytes).
+///
+/// # Arguments
+/// - `bytes`: The complete archive.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Behavior
+/// - Same as [`read_zip_contents_into_buffer`], reading entries in parallel straight from `bytes`
+///   instead of a memory-mapped file.  
+/// - Directory entries are skipped.  
+///
+/// # Errors
+/// Returns an error if `bytes` is not a valid ZIP archive, or an entry cannot be decompressed or
+/// decrypted ([`ZipoxideError::AesUnsupported`] when AES decryption is unavailable).
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_contents_from_bytes;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let bytes: Vec<u8> = std::fs::read("archive.zip")?;
+///     for (name, data) in read_zip_contents_from_bytes(&bytes, None)? {
+///         println!("File: {}, Size: {} bytes", name, data.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_contents_from_bytes(
+    bytes: &[u8],
+    password: Option<&str>,
+) -> Result<HashMap<String, Vec<u8>>, ZipoxideError> {
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(bytes))?;
     let indexes: Vec<usize> = (0..zip_archive.len()).collect();
 
-    indexes.par_iter().try_for_each(
-        |&index| -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-            let mut zip_archive: ZipArchive<Cursor<&[u8]>> =
-                ZipArchive::new(std::io::Cursor::new(&mmap[..]))?;
-            let mut entry: ZipFile<'_, Cursor<&[u8]>> = match &password {
-                Some(v) => zip_archive.by_index_decrypt(index, v.as_bytes())?,
-                None => zip_archive.by_index(index)?,
+    let files: Vec<Option<(String, Vec<u8>)>> = indexes
+        .par_iter()
+        .map(|&index| -> Result<Option<(String, Vec<u8>)>, ZipoxideError> {
+            let name: String = zip_archive.name_for_index(index).unwrap_or_default().to_string();
+            Ok(read_entry_bytes(bytes, index, password)?.map(|buffer| (name, buffer)))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(files.into_iter().flatten().collect())
+}
+
+/// Reads all files of a ZIP archive into memory, in the order they appear in the central directory.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Behavior
+/// - Unlike [`read_zip_contents_into_buffer`], entries are read sequentially on the calling thread so
+///   their order can be kept; prefer the parallel readers when order does not matter.  
+/// - Directory entries are skipped. Duplicate names are all returned, in archive order.  
+///
+/// # Returns
+/// A `Vec<(String, Vec<u8>)>` of entry names and contents, in central-directory order.
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened or parsed, or an entry cannot be decompressed or
+/// decrypted ([`ZipoxideError::AesUnsupported`] when AES decryption is unavailable).
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_contents_ordered;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     for (name, data) in read_zip_contents_ordered("archive.zip", None)? {
+///         println!("File: {}, Size: {} bytes", name, data.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_contents_ordered(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+) -> Result<Vec<(String, Vec<u8>)>, ZipoxideError> {
+    let mut contents: Vec<(String, Vec<u8>)> = Vec::new();
+    for_each_entry(zip_path.as_ref(), password, |entry| {
+        let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.size()));
+        io::copy(entry, &mut buffer)?;
+        contents.push((entry.name().to_string(), buffer));
+        Ok(())
+    })?;
+    Ok(contents)
+}
+
+/// Reads all files of a ZIP archive into memory like [`read_zip_contents_into_buffer`], while capping
+/// how many entries are being decompressed into buffers at the same time.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+/// - `max_in_flight`: Maximum number of entries buffered concurrently (values below 1 are treated as 1).  
+///
+/// # Behavior
+/// - Rayon still drives the work, but each worker must hold one of `max_in_flight` permits while it
+///   decompresses an entry, so at most that many partially-filled buffers (and decompressor states)
+///   exist at once. With `max_in_flight = 1`, entries are buffered strictly one after another.  
+/// - Finished buffers are moved into the returned map, so the map itself still grows to the total
+///   uncompressed size; the cap bounds the transient peak on top of it, which matters for archives
+///   holding a few very large entries.  
+/// - Directory entries are skipped.  
+///
+/// # Errors
+/// Same as [`read_zip_contents_into_buffer`].
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_contents_bounded;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Never decompress more than two entries at a time.
+///     let contents = read_zip_contents_bounded("large.zip", None, 2)?;
+///     println!("Read {} files", contents.len());
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_contents_bounded(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+    max_in_flight: usize,
+) -> Result<HashMap<String, Vec<u8>>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    let indexes: Vec<usize> = (0..zip_archive.len()).collect();
+
+    let permits: BufferPermits = BufferPermits::new(max_in_flight);
+    let shared_results: Mutex<HashMap<String, Vec<u8>>> = Mutex::new(HashMap::new());
+
+    indexes.par_iter().try_for_each(|&index| -> Result<(), ZipoxideError> {
+        let _permit: BufferPermit<'_> = permits.acquire()?;
+        if let Some(buffer) = read_entry_bytes(&mmap[..], index, password)? {
+            let name: String = zip_archive.name_for_index(index).unwrap_or_default().to_string();
+            shared_results.lock()?.insert(name, buffer);
+        }
+        Ok(())
+    })?;
+
+    Ok(shared_results.into_inner()?)
+}
+
+/// Destination for the entries read by [`read_zip_into_sink`].
+///
+/// `accept` is called from Rayon worker threads, concurrently and in no particular order, so
+/// implementations synchronise internally (atomics, a `Mutex`, a channel sender).
+///
+/// # Example
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use zipoxide::EntrySink;
+///
+/// struct ByteCounter(AtomicUsize);
+///
+/// impl EntrySink for ByteCounter {
+///     fn accept(&self, _name: &str, data: Vec<u8>) {
+///         self.0.fetch_add(data.len(), Ordering::Relaxed);
+///     }
+/// }
+/// ```
+pub trait EntrySink: Sync {
+    /// Receives the name and decompressed contents of one file entry.
+    fn accept(&self, name: &str, data: Vec<u8>);
+}
+
+/// Collects entries into a map, as [`read_zip_contents_into_buffer`] does.
+impl EntrySink for Mutex<HashMap<String, Vec<u8>>> {
+    fn accept(&self, name: &str, data: Vec<u8>) {
+        self.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(name.to_string(), data);
+    }
+}
+
+/// Reads all files of a ZIP archive in parallel, handing each one to `sink` instead of collecting them
+/// into a `HashMap`.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+/// - `sink`: Receives every file entry as soon as it is decompressed, see [`EntrySink`].  
+///
+/// # Behavior
+/// - Entries are read on Rayon worker threads from a memory-mapped archive, as by
+///   [`read_zip_contents_into_buffer`], and passed to `sink` from the thread that read them.  
+/// - Directory entries are skipped.  
+/// - Each buffer is moved into `sink`, so memory use depends on what the sink keeps: a counter or a
+///   database writer lets buffers go as soon as they are handled.  
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed, or an entry cannot be
+/// decompressed or decrypted. Entries already handed to `sink` stay there.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::sync::Mutex;
+/// use std::sync::mpsc::{self, Sender};
+/// use zipoxide::{EntrySink, read_zip_into_sink};
+///
+/// struct Forward(Mutex<Sender<(String, usize)>>);
+///
+/// impl EntrySink for Forward {
+///     fn accept(&self, name: &str, data: Vec<u8>) {
+///         let _ = self.0.lock().unwrap().send((name.to_string(), data.len()));
+///     }
+/// }
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (sender, receiver) = mpsc::channel();
+///     read_zip_into_sink("archive.zip", None, &Forward(Mutex::new(sender)))?;
+///     for (name, size) in receiver.try_iter() {
+///         println!("{name}: {size} bytes");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_into_sink<S: EntrySink + ?Sized>(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+    sink: &S,
+) -> Result<(), ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    let indexes: Vec<usize> = (0..zip_archive.len()).collect();
+
+    indexes.par_iter().try_for_each(|&index| -> Result<(), ZipoxideError> {
+        if let Some(buffer) = read_entry_bytes(&mmap[..], index, password)? {
+            sink.accept(zip_archive.name_for_index(index).unwrap_or_default(), buffer);
+        }
+        Ok(())
+    })
+}
+
+/// Iterator behind [`read_zip_entries_chunked`], owning the archive between batches.
+struct EntryChunks {
+    bytes: Option<ArchiveBytes>,
+    files: Vec<(usize, String)>,
+    next: usize,
+    chunk_size: usize,
+    password: Option<String>,
+    error: Option<ZipoxideError>,
+}
+
+impl EntryChunks {
+    /// Maps the archive and lists its file entries, keeping directories out of the batches.
+    fn open(zip_path: &Path) -> Result<(ArchiveBytes, Vec<(usize, String)>), ZipoxideError> {
+        let mmap: ArchiveBytes = load_archive(zip_path)?; // memory-map the whole zip
+        let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+        let mut files: Vec<(usize, String)> = Vec::with_capacity(zip_archive.len());
+        for index in 0..zip_archive.len() {
+            let entry: ZipFile<'_, Cursor<&[u8]>> = zip_archive.by_index_raw(index)?;
+            if !entry.is_dir() {
+                files.push((index, entry.name().to_string()));
+            }
+        }
+        drop(zip_archive);
+        Ok((mmap, files))
+    }
+}
+
+impl Iterator for EntryChunks {
+    type Item = Result<HashMap<String, Vec<u8>>, ZipoxideError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+        let bytes: &ArchiveBytes = self.bytes.as_ref()?;
+        if self.next >= self.files.len() {
+            return None;
+        }
+        let end: usize = (self.next + self.chunk_size).min(self.files.len());
+        let batch: &[(usize, String)] = &self.files[self.next..end];
+        self.next = end;
+        let password: Option<&str> = self.password.as_deref();
+        let chunk: Result<HashMap<String, Vec<u8>>, ZipoxideError> = batch
+            .par_iter()
+            .map(|(index, name)| {
+                let buffer: Vec<u8> = read_entry_bytes(&bytes[..], *index, password)?.unwrap_or_default();
+                Ok((name.clone(), buffer))
+            })
+            .collect();
+        if chunk.is_err() {
+            self.next = self.files.len();
+        }
+        Some(chunk)
+    }
+}
+
+/// Reads the files of a ZIP archive in batches of at most `chunk_size` entries, so only one batch is
+/// held in memory at a time.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `chunk_size`: Maximum number of files per batch (values below 1 are treated as 1).  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Behavior
+/// - The archive is opened and its central directory read when this function is called; entries are
+///   only decompressed as batches are requested, each batch in parallel.  
+/// - Every batch is a fresh `HashMap`, so dropping it before asking for the next one bounds memory use
+///   to the largest batch rather than the whole archive.  
+/// - Batches follow central-directory order; directory entries are skipped and do not count towards
+///   `chunk_size`.  
+/// - After an error is yielded, the iterator ends.  
+///
+/// # Errors
+/// Yields an error if the ZIP file cannot be opened or parsed (as the only item), or an entry of the
+/// current batch cannot be decompressed or decrypted.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_entries_chunked;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     for batch in read_zip_entries_chunked("large.zip", 16, None) {
+///         for (name, data) in batch? {
+///             println!("File: {}, Size: {} bytes", name, data.len());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_entries_chunked(
+    zip_path: impl AsRef<Path>,
+    chunk_size: usize,
+    password: Option<&str>,
+) -> impl Iterator<Item = Result<HashMap<String, Vec<u8>>, ZipoxideError>> {
+    let (bytes, files, error) = match EntryChunks::open(zip_path.as_ref()) {
+        Ok((bytes, files)) => (Some(bytes), files, None),
+        Err(error) => (None, Vec::new(), Some(error)),
+    };
+    let password: Option<String> = password.map(str::to_string);
+    EntryChunks { bytes, files, next: 0, chunk_size: chunk_size.max(1), password, error }
+}
+
+/// Reads the files at central-directory indices `start..end` of a ZIP archive into memory, so a large
+/// archive can be split into shards processed by different workers or machines.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `start`: Index of the first entry of the range.  
+/// - `end`: Index one past the last entry of the range.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Behavior
+/// - Indices count every entry, directories included, so shards cut from [`zip_entry_count`] never
+///   overlap or leave gaps; directory entries in the range are skipped rather than returned.  
+/// - `end` is clamped to the number of entries, and an empty range (`start >= end`) returns an empty map.  
+/// - Entries outside the range are neither decompressed nor decrypted; those inside are read in parallel.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The ZIP file cannot be opened, memory-mapped or parsed.  
+/// - An entry in the range cannot be decompressed or decrypted.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{read_zip_range, zip_entry_count};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Shard 2 of 4.
+///     let count = zip_entry_count("huge.zip")? as usize;
+///     let shard = read_zip_range("huge.zip", count * 2 / 4, count * 3 / 4, None)?;
+///     println!("Shard holds {} files", shard.len());
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_range(
+    zip_path: impl AsRef<Path>,
+    start: usize,
+    end: usize,
+    password: Option<&str>,
+) -> Result<HashMap<String, Vec<u8>>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    let indexes: Vec<usize> = (start..end.min(zip_archive.len())).collect();
+
+    let files: Vec<Option<(String, Vec<u8>)>> = indexes
+        .par_iter()
+        .map(|&index| -> Result<Option<(String, Vec<u8>)>, ZipoxideError> {
+            let name: String = zip_archive.name_for_index(index).unwrap_or_default().to_string();
+            Ok(read_entry_bytes(&mmap, index, password)?.map(|buffer| (name, buffer)))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(files.into_iter().flatten().collect())
+}
+
+/// Reads all files of a ZIP archive into memory like [`read_zip_contents_into_buffer`], keyed by
+/// platform-native relative paths instead of stored entry names.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Behavior
+/// - Each `/`-separated entry name becomes a [`PathBuf`] built component by component, so keys use the
+///   host's separator and support [`Path::extension`], [`Path::parent`] and friends.  
+/// - Root, drive and `..` components are dropped, matching the paths [`extract_zip_with_options`] writes to.  
+/// - Entries are read in parallel over the memory-mapped archive; directory entries are skipped.  
+///
+/// # Errors
+/// Same as [`read_zip_contents_into_buffer`].
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_contents_as_pathmap;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let contents = read_zip_contents_as_pathmap("archive.zip", None)?;
+///     let sources = contents.keys().filter(|path| path.extension().is_some_and(|ext| ext == "rs"));
+///     println!("{} Rust files", sources.count());
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_contents_as_pathmap(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+) -> Result<HashMap<PathBuf, Vec<u8>>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    let indexes: Vec<usize> = (0..zip_archive.len()).collect();
+
+    let results: Vec<Option<(PathBuf, Vec<u8>)>> = indexes
+        .par_iter()
+        .map(|&index| -> Result<Option<(PathBuf, Vec<u8>)>, ZipoxideError> {
+            let Some(buffer) = read_entry_bytes(&mmap[..], index, password)? else {
+                return Ok(None);
             };
+            let name: &str = zip_archive.name_for_index(index).unwrap_or_default();
+            Ok(Some((entry_output_path(name, false).0, buffer)))
+        })
+        .collect::<Result<_, _>>()?;
 
-            let file_name: String = entry.name().to_string();
-            let mut buffer = Vec::with_capacity(entry.size() as usize);
-            io::copy(&mut entry, &mut buffer)?;
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Reads all files of a ZIP archive into memory like [`read_zip_contents_into_buffer`], but keeps
+/// going when individual entries fail instead of discarding everything on the first error.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to be read.  
+/// - `password`: Optional password used to decrypt encrypted files.  
+///
+/// # Returns
+/// A tuple of:
+/// - A `HashMap<String, Vec<u8>>` of every entry that was read successfully.  
+/// - A `Vec<(String, String)>` of `(entry_name, error_message)` for every entry that failed
+///   (corrupt data, CRC mismatch, wrong password, ...), in central-directory order.  
+///
+/// # Behavior
+/// - Entries are read in parallel over the memory-mapped archive, as in [`read_zip_contents_into_buffer`].  
+/// - Directory entries are skipped.  
+///
+/// # Errors
+/// Only fails as a whole if the ZIP file cannot be opened, memory-mapped or its central directory parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_contents_lenient;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (contents, failures) = read_zip_contents_lenient("damaged.zip", None)?;
+///     println!("Recovered {} files", contents.len());
+///     for (name, error) in failures {
+///         eprintln!("Could not read {name}: {error}");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn read_zip_contents_lenient(
+    zip_path: impl AsRef<Path>,
+    password: Option<&str>,
+) -> Result<(HashMap<String, Vec<u8>>, Vec<(String, String)>), ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    let indexes: Vec<usize> = (0..zip_archive.len()).collect();
 
-            shared_results.lock().unwrap().insert(file_name, buffer);
+    let results: Vec<(String, Result<Option<Vec<u8>>, ZipoxideError>)> = indexes
+        .par_iter()
+        .map(|&index| {
+            let name: String = zip_archive.name_for_index(index).unwrap_or_default().to_string();
+            let result: Result<Option<Vec<u8>>, ZipoxideError> = read_entry_bytes(&mmap[..], index, password);
+            (name, result)
+        })
+        .collect();
 
-            Ok(())
-        },
-    )?;
+    let mut contents: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut failures: Vec<(String, String)> = Vec::new();
+    for (name, result) in results {
+        match result {
+            Ok(Some(buffer)) => {
+                contents.insert(name, buffer);
+            }
+            Ok(None) => {}
+            Err(e) => failures.push((name, e.to_string())),
+        }
+    }
+    Ok((contents, failures))
+}
+
+/// Reads at most the first `n` decompressed bytes of a single entry, without inflating the rest.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `entry_name`: Name of the entry as stored in the archive (e.g. `images/logo.png`).  
+/// - `n`: Maximum number of bytes to return.  
+/// - `password`: Optional password used to decrypt the entry.  
+///
+/// # Behavior
+/// - Decompression stops after `n` bytes via [`Read::take`], which makes this suitable for
+///   file-type sniffing (magic bytes) on large entries.  
+/// - Returns fewer than `n` bytes if the entry is shorter.  
+/// - The entry's CRC32 is only verified when the whole entry is read, so a truncated peek is not checksummed.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The ZIP file cannot be opened, memory-mapped or parsed.  
+/// - `entry_name` does not exist in the archive ([`ZipoxideError::EntryNotFound`]).  
+/// - The entry cannot be decrypted with the provided password.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::peek_entry;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let header = peek_entry("archive.zip", "images/logo.png", 8, None)?;
+///     if header.starts_with(b"\x89PNG") {
+///         println!("logo.png is a PNG image");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn peek_entry(
+    zip_path: impl AsRef<Path>,
+    entry_name: &str,
+    n: usize,
+    password: Option<&str>,
+) -> Result<Vec<u8>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let entry: ZipFile<'_, Cursor<&[u8]>> = open_entry_by_name(&mut zip_archive, entry_name, password)?;
+    let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.size()).min(n));
+    entry.take(n as u64).read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Reads a single entry and decodes it as UTF-8 text, e.g. a README, a config file or a manifest.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `entry_name`: Name of the entry as stored in the archive (e.g. `config/settings.toml`).  
+/// - `password`: Optional password used to decrypt the entry.  
+///
+/// # Behavior
+/// - The whole entry is decompressed into memory and its CRC32 verified before decoding.  
+/// - The text is returned as stored: a leading byte order mark is kept and line endings are not
+///   normalised.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The ZIP file cannot be opened, memory-mapped or parsed.  
+/// - `entry_name` does not exist in the archive ([`ZipoxideError::EntryNotFound`]).  
+/// - The entry cannot be decrypted or decompressed.  
+/// - The entry is not valid UTF-8 ([`ZipoxideError::InvalidUtf8`], holding the entry's bytes).  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_entry_as_string;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let readme = read_zip_entry_as_string("release.zip", "README.md", None)?;
+///     println!("{readme}");
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_entry_as_string(
+    zip_path: impl AsRef<Path>,
+    entry_name: &str,
+    password: Option<&str>,
+) -> Result<String, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let mut entry: ZipFile<'_, Cursor<&[u8]>> = open_entry_by_name(&mut zip_archive, entry_name, password)?;
+    let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.size()));
+    entry.read_to_end(&mut buffer)?;
+    String::from_utf8(buffer).map_err(|e| ZipoxideError::InvalidUtf8(e.into_bytes()))
+}
 
-    Ok(Arc::try_unwrap(shared_results).unwrap().into_inner()?)
+/// Reads the compressed data of a single entry as stored in the archive, without decompressing it.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `entry_name`: Name of the entry as stored in the archive (e.g. `data/records.bin`).  
+///
+/// # Behavior
+/// - Returns the entry's compression method together with exactly its `compressed_size` bytes, for
+///   callers that decompress entries themselves (a custom or WASM decoder, forwarding the data as-is).  
+/// - Deflated data is a raw Deflate stream (no zlib or gzip header); `Stored` data is the file itself.  
+/// - Encrypted entries are returned still encrypted, including their encryption header.  
+/// - The data is not checked against the entry's CRC32, which describes the decompressed contents.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The ZIP file cannot be opened, memory-mapped or parsed.  
+/// - `entry_name` does not exist in the archive ([`ZipoxideError::EntryNotFound`]).  
+///
+/// # Example
+/// ```rust,no_run
+/// use zip::CompressionMethod;
+/// use zipoxide::read_zip_entry_raw;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (method, data) = read_zip_entry_raw("archive.zip", "data/records.bin")?;
+///     if method == CompressionMethod::Stored {
+///         println!("{} bytes, stored as-is", data.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_entry_raw(
+    zip_path: impl AsRef<Path>,
+    entry_name: &str,
+) -> Result<(CompressionMethod, Vec<u8>), ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let Some(index) = zip_archive.index_for_name(entry_name) else {
+        return Err(ZipoxideError::EntryNotFound(entry_name.to_string()));
+    };
+    let mut entry: ZipFile<'_, Cursor<&[u8]>> = zip_archive.by_index_raw(index)?;
+    let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.compressed_size()));
+    entry.read_to_end(&mut buffer)?;
+    Ok((entry.compression(), buffer))
+}
+
+/// Reads `length` decompressed bytes starting at `offset` from a single entry, without keeping the
+/// rest of the entry in memory.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `entry_name`: Name of the entry as stored in the archive.  
+/// - `offset`: Position in the decompressed entry where the range starts.  
+/// - `length`: Maximum number of bytes to return.  
+/// - `password`: Optional password used to decrypt the entry.  
+///
+/// # Behavior
+/// - Compressed data cannot be seeked, so the first `offset` bytes are still decompressed but
+///   discarded as they are produced; decompression stops once the range has been read.  
+/// - Returns fewer than `length` bytes if the entry ends inside the range, and an empty buffer if
+///   `offset` is past its end.  
+/// - As with [`peek_entry`], the CRC32 is only verified when the range reaches the end of the entry.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The ZIP file cannot be opened, memory-mapped or parsed.  
+/// - `entry_name` does not exist in the archive ([`ZipoxideError::EntryNotFound`]).  
+/// - The entry cannot be decrypted or decompressed.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_entry_range;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Bytes 1 MiB..1 MiB + 4 KiB of a large log file.
+///     let chunk = read_zip_entry_range("logs.zip", "app.log", 1 << 20, 4096, None)?;
+///     println!("Read {} bytes", chunk.len());
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_entry_range(
+    zip_path: impl AsRef<Path>,
+    entry_name: &str,
+    offset: u64,
+    length: u64,
+    password: Option<&str>,
+) -> Result<Vec<u8>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let mut entry: ZipFile<'_, Cursor<&[u8]>> = open_entry_by_name(&mut zip_archive, entry_name, password)?;
+    io::copy(&mut (&mut entry).take(offset), &mut io::sink())?;
+    let available: u64 = entry.size().saturating_sub(offset);
+    let mut buffer: Vec<u8> = Vec::with_capacity(length.min(available) as usize);
+    entry.take(length).read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Lists the metadata of every entry in a ZIP archive, in central-directory order, without decompressing anything.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+///
+/// # Behavior
+/// - Reads only the central directory through the memory-mapped archive.  
+/// - Directory markers are included; use [`ZipEntry::is_directory`] to tell them apart from files.  
+/// - Encrypted entries are listed without needing a password.  
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::list_zip_entries;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     for entry in list_zip_entries("archive.zip")? {
+///         if !entry.is_directory() {
+///             println!("{} ({} bytes)", entry.name, entry.size);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn list_zip_entries(zip_path: impl AsRef<Path>) -> Result<Vec<ZipEntry>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let mut entries: Vec<ZipEntry> = Vec::with_capacity(zip_archive.len());
+    for index in 0..zip_archive.len() {
+        let entry: ZipFile<'_, Cursor<&[u8]>> = zip_archive.by_index_raw(index)?;
+        entries.push(ZipEntry::from_zip_file(&entry));
+    }
+    Ok(entries)
+}
+
+/// Lists the names of every entry in a ZIP archive, directories included, in central-directory order.
+///
+/// A lighter alternative to [`list_zip_entries`] when only the names are needed.
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::zip_entry_names;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     for name in zip_entry_names("archive.zip")? {
+///         println!("{name}");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn zip_entry_names(zip_path: impl AsRef<Path>) -> Result<Vec<String>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    let names: Vec<String> =
+        (0..zip_archive.len()).filter_map(|index| zip_archive.name_for_index(index)).map(str::to_string).collect();
+    Ok(names)
+}
+
+/// Reads the number of entries in a ZIP archive from its end-of-central-directory record, without
+/// parsing the central directory.
+///
+/// # Behavior
+/// - Only the last 64 KiB of the file (the record and its comment) are read, plus the Zip64 record for
+///   archives with 65,535 entries or more, so the cost does not grow with the number of entries. Useful
+///   to size a progress bar or pre-allocate before reading.  
+/// - The count is the one the archive declares, directories included; entries are not validated.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The file cannot be opened or read.  
+/// - No end-of-central-directory record is found ([`ZipoxideError::Zip`]), or a Zip64 count does not
+///   fit in a `u32`.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::zip_entry_count;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let total = zip_entry_count("large.zip")?;
+///     println!("Extracting {total} entries");
+///     Ok(())
+/// }
+/// ```
+pub fn zip_entry_count(zip_path: impl AsRef<Path>) -> Result<u32, ZipoxideError> {
+    let mut zip_file: File = File::open(zip_path.as_ref())?;
+    let tail_size: u64 = zip_file.metadata()?.len().min((END_OF_CENTRAL_DIRECTORY_SIZE + u16::MAX as usize) as u64);
+    let mut tail: Vec<u8> = vec![0; tail_size as usize];
+    zip_file.seek(io::SeekFrom::End(-(tail_size as i64)))?;
+    zip_file.read_exact(&mut tail)?;
+
+    // The comment may contain the signature too, so take the last record whose comment fits in the file.
+    let read_u16 = |at: usize| -> usize { u16::from_le_bytes([tail[at], tail[at + 1]]) as usize };
+    let record: usize = tail
+        .len()
+        .checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE)
+        .and_then(|last| {
+            (0..=last).rev().find(|&at| {
+                tail[at..at + 4] == END_OF_CENTRAL_DIRECTORY_SIGNATURE
+                    && at + END_OF_CENTRAL_DIRECTORY_SIZE + read_u16(at + 20) <= tail.len()
+            })
+        })
+        .ok_or_else(|| ZipError::InvalidArchive("could not find the end of central directory record".into()))?;
+    let count: usize = read_u16(record + 10);
+    let locator: Option<usize> = record
+        .checked_sub(ZIP64_LOCATOR_SIZE)
+        .filter(|&locator| tail[locator..locator + 4] == ZIP64_LOCATOR_SIGNATURE);
+    let locator: usize = match locator {
+        Some(locator) if count == u16::MAX as usize => locator,
+        _ => return Ok(count as u32),
+    };
+
+    // Zip64: the real count is in the Zip64 record, whose offset the locator gives.
+    let mut offset: [u8; 8] = [0; 8];
+    offset.copy_from_slice(&tail[locator + 8..locator + 16]);
+    let mut zip64_record: [u8; 40] = [0; 40];
+    zip_file.seek(io::SeekFrom::Start(u64::from_le_bytes(offset)))?;
+    zip_file.read_exact(&mut zip64_record)?;
+    if zip64_record[..4] != ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE {
+        return Err(ZipError::InvalidArchive("invalid Zip64 end of central directory record".into()).into());
+    }
+    let mut count: [u8; 8] = [0; 8];
+    count.copy_from_slice(&zip64_record[32..40]);
+    u32::try_from(u64::from_le_bytes(count))
+        .map_err(|_| ZipError::InvalidArchive("entry count does not fit in 32 bits".into()).into())
+}
+
+/// Reads the archive-level comment of a ZIP file, stored after the central directory.
+///
+/// # Behavior
+/// - The comment is decoded as UTF-8, replacing invalid sequences with `U+FFFD`.  
+/// - Returns `None` if the archive has no comment.  
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::read_zip_archive_comment;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     if let Some(comment) = read_zip_archive_comment("archive.zip")? {
+///         println!("{comment}");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn read_zip_archive_comment(zip_path: impl AsRef<Path>) -> Result<Option<String>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    let comment: &[u8] = zip_archive.comment();
+    Ok((!comment.is_empty()).then(|| String::from_utf8_lossy(comment).into_owned()))
+}
+
+/// Lists the entries of a ZIP archive like [`list_zip_entries`], decoding names stored without the
+/// UTF-8 flag with `name_encoding`.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `name_encoding`: Encoding used by the tool that wrote the archive, e.g. `encoding_rs::SHIFT_JIS`
+///   for archives created by Windows Explorer on a Japanese system. `None` behaves like
+///   [`list_zip_entries`] and decodes unflagged names as CP437.  
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::list_zip_entries_with_encoding;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     for entry in list_zip_entries_with_encoding("legacy.zip", Some(encoding_rs::SHIFT_JIS))? {
+///         println!("{}", entry.name);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn list_zip_entries_with_encoding(
+    zip_path: impl AsRef<Path>,
+    name_encoding: Option<&'static encoding_rs::Encoding>,
+) -> Result<Vec<ZipEntry>, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let mut entries: Vec<ZipEntry> = Vec::with_capacity(zip_archive.len());
+    for index in 0..zip_archive.len() {
+        let entry: ZipFile<'_, Cursor<&[u8]>> = zip_archive.by_index_raw(index)?;
+        let mut listed: ZipEntry = ZipEntry::from_zip_file(&entry);
+        listed.name = decode_entry_name(entry.name(), entry.name_raw(), name_encoding);
+        entries.push(listed);
+    }
+    Ok(entries)
+}
+
+/// Checks whether an entry called `entry_name` exists in a ZIP archive, without decompressing or listing anything.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `entry_name`: Exact entry name as stored in the archive (directories end with `/`).  
+///
+/// # Behavior
+/// - Looks the name up in the central-directory index built when the archive is opened, the same way
+///   [`ZipArchive::by_name`] does, instead of collecting every name first.  
+/// - Encrypted entries are found without needing a password.  
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::zip_contains;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     if zip_contains("release.zip", "bin/tool")? {
+///         println!("release.zip ships the tool binary");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn zip_contains(zip_path: impl AsRef<Path>, entry_name: &str) -> Result<bool, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+    Ok(zip_archive.index_for_name(entry_name).is_some())
+}
+
+/// Sums the declared uncompressed sizes of all entries in a ZIP archive, i.e. roughly how much disk
+/// space extracting it will take.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+///
+/// # Behavior
+/// - Only central-directory metadata is read; nothing is decompressed, and encrypted entries are
+///   counted without a password.  
+/// - Sizes are as declared by the archive, so a corrupt or malicious archive can under- or overstate
+///   them. The sum saturates at `u64::MAX`.  
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::total_uncompressed_size;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let needed = total_uncompressed_size("archive.zip")?;
+///     println!("Extraction needs {needed} bytes");
+///     Ok(())
+/// }
+/// ```
+pub fn total_uncompressed_size(zip_path: impl AsRef<Path>) -> Result<u64, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let mut total: u64 = 0;
+    for index in 0..zip_archive.len() {
+        total = total.saturating_add(zip_archive.by_index_raw(index)?.size());
+    }
+    Ok(total)
+}
+
+/// Counts the encrypted and plaintext file entries of a ZIP archive, e.g. to prompt for a password only
+/// when one is needed.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+///
+/// # Behavior
+/// - Only central-directory metadata is read; no password is needed and nothing is decrypted.  
+/// - ZipCrypto and AES entries both count as encrypted. Directory entries are not counted.  
+///
+/// # Returns
+/// `(encrypted, plaintext)` entry counts.
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::encryption_summary;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (encrypted, plaintext) = encryption_summary("archive.zip")?;
+///     if encrypted > 0 {
+///         println!("{encrypted} of {} files need a password", encrypted + plaintext);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn encryption_summary(zip_path: impl AsRef<Path>) -> Result<(usize, usize), ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let (mut encrypted, mut plaintext): (usize, usize) = (0, 0);
+    for index in 0..zip_archive.len() {
+        let entry: ZipFile<'_, Cursor<&[u8]>> = zip_archive.by_index_raw(index)?;
+        match (entry.is_dir(), entry.encrypted()) {
+            (true, _) => {}
+            (false, true) => encrypted += 1,
+            (false, false) => plaintext += 1,
+        }
+    }
+    Ok((encrypted, plaintext))
+}
+
+/// Outcome of [`validate_entries`].
+///
+/// # Fields
+/// - `missing`: Required entries the archive lacks, in the order they were required.  
+/// - `forbidden`: Forbidden entries the archive contains, in the order they were forbidden.  
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub missing: Vec<String>,
+    pub forbidden: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no required entry is missing and no forbidden entry is present.
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.forbidden.is_empty()
+    }
+}
+
+/// Checks that a ZIP archive contains every required entry and none of the forbidden ones, without
+/// extracting it, e.g. before deploying an uploaded bundle.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `required`: Entry names that must be present.  
+/// - `forbidden`: Entry names that must not be present.  
+/// - `password`: When set, every required entry found is also opened with it, so a wrong password is
+///   caught here rather than halfway through a later extraction.  
+///
+/// # Behavior
+/// - Names are compared exactly, as stored in the archive: directories end with `/`, and no path
+///   normalization or case folding is applied.  
+/// - Lookups go through the central-directory index, so nothing is decompressed.  
+///
+/// # Returns
+/// A [`ValidationReport`]; the archive passes if [`ValidationReport::is_valid`] returns `true`.
+///
+/// # Errors
+/// Returns an error if:
+/// - The ZIP file cannot be opened, memory-mapped or parsed.  
+/// - `password` is set and a required entry cannot be decrypted with it.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::validate_entries;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let report = validate_entries("release.zip", &["bin/app", "config.toml"], &[".env", "secrets.json"], None)?;
+///     if !report.is_valid() {
+///         println!("missing: {:?}, forbidden: {:?}", report.missing, report.forbidden);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn validate_entries(
+    zip_path: impl AsRef<Path>,
+    required: &[&str],
+    forbidden: &[&str],
+    password: Option<&str>,
+) -> Result<ValidationReport, ZipoxideError> {
+    let mmap: ArchiveBytes = load_archive(zip_path.as_ref())?; // memory-map the whole zip
+    let mut zip_archive: ZipArchive<Cursor<&[u8]>> = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let mut report: ValidationReport = ValidationReport::default();
+    for &name in required {
+        match zip_archive.index_for_name(name) {
+            Some(index) if password.is_some() => drop(open_entry(&mut zip_archive, index, password)?),
+            Some(_) => {}
+            None => report.missing.push(name.to_string()),
+        }
+    }
+    for &name in forbidden {
+        if zip_archive.index_for_name(name).is_some() {
+            report.forbidden.push(name.to_string());
+        }
+    }
+    Ok(report)
+}
+
+/// Checks whether a file looks like a ZIP archive by inspecting its first four bytes.
+///
+/// # Arguments
+/// - `path`: Path to the file to check.  
+///
+/// # Behavior
+/// - Returns `true` if the file starts with a local file header signature (`PK\x03\x04`) or,
+///   for empty archives, an end-of-central-directory signature (`PK\x05\x06`).  
+/// - Files shorter than four bytes are reported as not being ZIP files.  
+/// - Only the magic bytes are checked: the archive is not parsed, so a truncated or corrupt
+///   ZIP can still return `true`. Self-extracting archives (with an executable stub in front) return `false`.  
+///
+/// # Errors
+/// Returns an error if the file cannot be opened or read.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::is_zip_file;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     if is_zip_file("download.bin")? {
+///         println!("download.bin is a ZIP archive");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn is_zip_file(path: impl AsRef<Path>) -> Result<bool, io::Error> {
+    let mut file: File = File::open(path.as_ref())?;
+    let mut magic: [u8; 4] = [0; 4];
+    match file.read_exact(&mut magic) {
+        Ok(()) => Ok(magic == LOCAL_FILE_HEADER_SIGNATURE || magic == END_OF_CENTRAL_DIRECTORY_SIGNATURE),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Quickly checks whether a file is a ZIP archive by locating its end-of-central-directory record.
+///
+/// # Arguments
+/// - `zip_path`: Path to the file to check.  
+///
+/// # Behavior
+/// - Reads only the last 64 KiB of the file, as [`zip_entry_count`] does, so the cost does not grow
+///   with the archive's size or number of entries.  
+/// - Stricter than [`is_zip_file`]: a file that merely starts like a ZIP but was truncated before its
+///   central directory returns `false`, while self-extracting archives return `true`. The central
+///   directory itself is not parsed, so a damaged one can still return `true`.  
+/// - Never fails: files that cannot be opened or read are reported as not being ZIP archives.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::is_valid_zip;
+///
+/// fn main() {
+///     if !is_valid_zip("upload.bin") {
+///         eprintln!("upload.bin is not a ZIP archive");
+///     }
+/// }
+/// ```
+pub fn is_valid_zip(zip_path: impl AsRef<Path>) -> bool {
+    zip_entry_count(zip_path).is_ok()
+}
+
+/// A ZIP archive whose central directory has been read once, for inspecting and planning work on its
+/// entries before reading them.
+///
+/// The archive is memory-mapped (or read into memory without the `mmap` feature) by
+/// [`ZipReader::open`]; entries are then read from the mapping without reopening the file, either
+/// whole with [`ZipReader::read_entry`] or on demand through [`ZipReader::open_entry`]. Clones share
+/// the mapping, so cloning a reader per thread is cheap.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::ZipReader;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let reader = ZipReader::open("archive.zip")?;
+///     for handle in reader.entries_sorted_by_size(true).take(3) {
+///         println!("{} ({} bytes)", handle.entry.name, handle.entry.size);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ZipReader {
+    bytes: SharedBytes,
+    archive: ZipArchive<Cursor<SharedBytes>>,
+    entries: Vec<ZipEntryHandle>,
+}
+
+/// Archive bytes shared between a [`ZipReader`] and the `ZipArchive` it keeps for [`ZipReader::open_entry`].
+#[derive(Clone)]
+struct SharedBytes(Arc<ArchiveBytes>);
+
+impl AsRef<[u8]> for SharedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl ZipReader {
+    /// Opens the archive at `zip_path` and reads its central directory.
+    ///
+    /// # Errors
+    /// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+    pub fn open(zip_path: impl AsRef<Path>) -> Result<Self, ZipoxideError> {
+        let bytes: SharedBytes = SharedBytes(Arc::new(load_archive(zip_path.as_ref())?)); // memory-map the whole zip
+        let mut archive: ZipArchive<Cursor<SharedBytes>> = ZipArchive::new(Cursor::new(bytes.clone()))?;
+        let mut entries: Vec<ZipEntryHandle> = Vec::with_capacity(archive.len());
+        for index in 0..archive.len() {
+            let entry: ZipFile<'_, Cursor<SharedBytes>> = archive.by_index_raw(index)?;
+            entries.push(ZipEntryHandle { index, entry: ZipEntry::from_zip_file(&entry) });
+        }
+        Ok(ZipReader { bytes, archive, entries })
+    }
+
+    /// Number of entries in the archive, directories included.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the archive has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries in central-directory order.
+    pub fn entries(&self) -> impl Iterator<Item = ZipEntryHandle> + '_ {
+        self.entries.iter().cloned()
+    }
+
+    /// Looks up the entry called `name`, or `None` if the archive has none.
+    ///
+    /// Runs in constant time, through the name index the `zip` crate builds while reading the central directory.
+    pub fn entry_by_name(&self, name: &str) -> Option<ZipEntryHandle> {
+        self.archive.index_for_name(name).map(|index| self.entries[index].clone())
+    }
+
+    /// Iterates over the entries ordered by declared uncompressed size, largest first when `descending`.
+    ///
+    /// Scheduling the largest entries first keeps a parallel loop from ending on one long-running
+    /// task. Entries of equal size keep their central-directory order.
+    pub fn entries_sorted_by_size(&self, descending: bool) -> impl Iterator<Item = ZipEntryHandle> + '_ {
+        let mut sorted: Vec<&ZipEntryHandle> = self.entries.iter().collect();
+        match descending {
+            true => sorted.sort_by_key(|handle| std::cmp::Reverse(handle.entry.size)),
+            false => sorted.sort_by_key(|handle| handle.entry.size),
+        }
+        sorted.into_iter().cloned()
+    }
+
+    /// Reads the contents of the entry behind `handle`, decrypting it with `password` when one is provided.
+    ///
+    /// Directory entries read as empty. Safe to call from several threads at once.
+    ///
+    /// # Errors
+    /// Returns an error if the entry cannot be decompressed or decrypted, or `handle` does not belong
+    /// to this archive.
+    pub fn read_entry(&self, handle: &ZipEntryHandle, password: Option<&str>) -> Result<Vec<u8>, ZipoxideError> {
+        Ok(read_entry_bytes(self.bytes.as_ref(), handle.index, password)?.unwrap_or_default())
+    }
+
+    /// Opens the entry named `name` for reading, decompressing (and decrypting, when `password` is
+    /// provided and the entry is encrypted) only as the returned reader is consumed.
+    ///
+    /// Returns `Ok(None)` if the archive has no entry of that name. The reader borrows the `ZipReader`
+    /// mutably, so only one entry can be open at a time; clone the `ZipReader` or use
+    /// [`ZipReader::read_entry`] to read entries from several threads.
+    ///
+    /// # Errors
+    /// Returns an error if the entry's headers are corrupt or `password` is wrong.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::io::Read;
+    /// use zipoxide::ZipReader;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut reader = ZipReader::open("archive.zip")?;
+    ///     if let Some(mut entry) = reader.open_entry("config.toml", None)? {
+    ///         let mut config = String::new();
+    ///         entry.read_to_string(&mut config)?;
+    ///         println!("{config}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open_entry<'a>(
+        &'a mut self,
+        name: &str,
+        password: Option<&str>,
+    ) -> Result<Option<impl Read + use<'a>>, ZipoxideError> {
+        let Some(index) = self.archive.index_for_name(name) else {
+            return Ok(None);
+        };
+        Ok(Some(open_entry(&mut self.archive, index, password)?))
+    }
+}
+
+/// An archive opened with [`open_zip`], for listing its entries first and reading only the ones picked.
+///
+/// Wraps a [`ZipReader`] together with the password to decrypt entries with, so every read comes from
+/// the same memory mapping and the file is opened once.
+#[derive(Clone)]
+pub struct OpenArchive {
+    reader: ZipReader,
+    password: Option<String>,
+}
+
+impl OpenArchive {
+    /// Iterates over the entry names in central-directory order, directories included.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.reader.entries.iter().map(|handle| handle.entry.name.as_str())
+    }
+
+    /// Iterates over the metadata of every entry (size, compression, CRC32, ...) in central-directory order.
+    pub fn info(&self) -> impl Iterator<Item = &ZipEntry> + '_ {
+        self.reader.entries.iter().map(|handle| &handle.entry)
+    }
+
+    /// Reads the contents of the entry called `name`. Directory entries read as empty.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The archive has no entry called `name` ([`ZipoxideError::EntryNotFound`]).  
+    /// - The entry cannot be decompressed or decrypted.  
+    pub fn read(&self, name: &str) -> Result<Vec<u8>, ZipoxideError> {
+        let mut archive: ZipArchive<Cursor<SharedBytes>> = self.reader.archive.clone();
+        let mut entry: ZipFile<'_, Cursor<SharedBytes>> =
+            open_entry_by_name(&mut archive, name, self.password.as_deref())?;
+        let mut buffer: Vec<u8> = Vec::with_capacity(capacity_hint(entry.size()));
+        io::copy(&mut entry, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Reads the entries called `names` in parallel, keyed by name.
+    ///
+    /// # Errors
+    /// Fails as [`OpenArchive::read`] does for the first entry that cannot be read.
+    pub fn read_many(&self, names: &[&str]) -> Result<HashMap<String, Vec<u8>>, ZipoxideError> {
+        names.par_iter().map(|&name| Ok((name.to_string(), self.read(name)?))).collect()
+    }
+}
+
+/// Opens a ZIP archive once for a list-then-read workflow: inspect its entries, then read only some of them.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file.  
+/// - `password`: Optional password used to decrypt encrypted entries when they are read.  
+///
+/// # Behavior
+/// - The central directory is read up front; [`OpenArchive::names`] and [`OpenArchive::info`] then
+///   cost nothing.  
+/// - [`OpenArchive::read`] and [`OpenArchive::read_many`] decompress entries from the memory mapping
+///   made when opening, without reopening the file or parsing the central directory again.  
+/// - Use [`ZipReader`] directly to stream entries or to pass a different password per entry.  
+///
+/// # Returns
+/// An [`OpenArchive`] handle.
+///
+/// # Errors
+/// Returns an error if the ZIP file cannot be opened, memory-mapped or parsed.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::open_zip;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let archive = open_zip("archive.zip", None)?;
+///     let picked: Vec<&str> = archive.names().filter(|name| name.ends_with(".csv")).collect();
+///     for (name, data) in archive.read_many(&picked)? {
+///         println!("{name}: {} bytes", data.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn open_zip(zip_path: impl AsRef<Path>, password: Option<&str>) -> Result<OpenArchive, ZipoxideError> {
+    let reader: ZipReader = ZipReader::open(zip_path)?;
+    Ok(OpenArchive { reader, password: password.map(str::to_string) })
 }
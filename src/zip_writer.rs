@@ -1,76 +1,1691 @@
-use std::io;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::OsString;
+use std::io::{self, BufRead, BufReader, BufWriter, Cursor, Read, Seek, Write};
 use std::fs::{self, File, DirEntry};
-use std::path::{Path, PathBuf};
-use zip::write::FileOptions;
-use zip::ZipWriter;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use zip::read::ZipFile;
+use zip::write::{FileOptionExtension, FileOptions, FullFileOptions, SimpleFileOptions};
+use zip::{CompressionMethod, DateTime, ZipArchive, ZipWriter};
+
+#[cfg(feature = "manifest")]
+use crate::manifest::{MANIFEST_NAME, Manifest};
+use crate::config::ZipOxideConfig;
+use crate::error::ZipoxideError;
+use crate::file_times::{NTFS_EXTRA_FIELD_ID, dos_time, ntfs_extra_field};
+use crate::zip_entry::EntryKind;
+use crate::zip_reader::{END_OF_CENTRAL_DIRECTORY_SIGNATURE, open_entry};
+
+/// Archive-level settings for the writer functions, complementing the per-entry
+/// [`zip::write::FileOptions`].
+///
+/// # Fields
+/// - `strip_prefix`: When set, every entry name is built by stripping this prefix from the
+///   source file path instead of using the default root (the folder for
+///   [`create_zip_from_folder`], each input's file name for [`create_zip_from_files`]).  
+///   The prefix must be expressed the same way as the input paths (both relative or both absolute);
+///   a path outside it fails with [`ZipoxideError::InvalidPath`]. With `/home/user/project` as the
+///   prefix, `/home/user/project/src/main.rs` is stored as `src/main.rs`.
+/// - `embed_manifest`: When `true`, a `MANIFEST.json` entry listing every file's name, CRC32 and
+///   uncompressed size is written as the last entry of the archive. Requires the `manifest` feature;
+///   without it, writing fails with [`ZipoxideError::FeatureDisabled`] instead of silently omitting the manifest.
+/// - `backend`: When set, overrides the compression method of `zip_options` with the given
+///   [`CompressionBackend`] for every file entry.
+/// - `pre_allocate`: When `true`, all inputs are stat-ed up front and the output file is grown to the
+///   estimated archive size with [`File::set_len`] before writing, reducing fragmentation. Unused space
+///   is truncated once the archive is finished.
+/// - `parallel_compress`: When `true`, entries are compressed concurrently on Rayon worker threads
+///   and handed over a channel to a single writer, which appends them in their original order.
+///   Worth it for CPU-bound settings (high Deflate levels, Bzip2, Zstd); each in-flight entry is held
+///   in memory in compressed form until it is written. Extra fields from `ExtendedFileOptions` are not
+///   carried over in this mode, since entries are raw-copied from their per-entry archives.
+/// - `compression_level`: When set, overrides the compression level of `zip_options`. When `None`,
+///   the level of the calling thread's [`ZipOxideConfig`](crate::ZipOxideConfig) applies, if any.
+/// - `deduplicate`: When `true`, files with identical contents (same size and CRC32, confirmed byte by
+///   byte) are compressed and stored once. Every name is still listed in the central directory, with
+///   duplicates pointing at the first copy's data and appended after the other entries. zipoxide and
+///   most extractors honour the central directory; tools that trust local headers may not.
+/// - `sync`: When `true`, the finished archive is flushed to disk with [`File::sync_all`] before the
+///   writer returns, so it survives a crash or power loss right after. Costs a disk round trip.
+/// - `store_compressed_formats`: When `true`, files whose extension marks an already-compressed format
+///   (images, audio, video, archives; see [`COMPRESSED_EXTENSIONS`]) are stored instead of compressed,
+///   since compressing them again costs CPU time for little or no gain.
+/// - `buffer_size`: When set, source files are read in chunks of this many bytes and the archive is
+///   written through a buffer of the same size, trading memory for fewer system calls. When `None`,
+///   sources are read in 256 KiB chunks and the archive is written unbuffered. Must not be zero.
+/// - `strip_metadata`: When `true`, every entry is stamped 1980-01-01 00:00, the earliest DOS time,
+///   instead of the time set in `zip_options`, so archives built from the same inputs are byte-identical
+///   whenever they are built. No timestamp extra fields are written then, even with `preserve_timestamps`.
+/// - `max_total_uncompressed_bytes`: When set, the sizes of all input files are summed before the output
+///   file is created, and writing fails with [`ZipoxideError::SizeLimitExceeded`] if they exceed the
+///   limit, leaving nothing on disk. Not applied by `watch_and_zip` (`notify` feature), whose
+///   updates each cover only the changed files.
+/// - `on_error`: What to do when a source file cannot be read (permission denied, deleted since the
+///   folder was listed, failing partway) or a subdirectory cannot be listed, see [`ErrorPolicy`]. With
+///   [`ErrorPolicy::SkipAndCollect`] such paths are left out and listed in [`ArchiveStats::skipped_files`].
+///   Files that `deduplicate`, `pre_allocate` or `max_total_uncompressed_bytes` read or stat up front
+///   still fail the whole operation there.
+/// - `preserve_timestamps`: When `true`, every entry is stamped with its file's modification time and
+///   carries an NTFS extra field holding the modification, access and creation times in 100 ns ticks,
+///   which the extraction functions restore instead of the 2-second DOS timestamp. The compression
+///   method, level and Unix permissions of `zip_options` are kept, but its extra fields are replaced
+///   and encryption is refused. Entries are compressed sequentially even with `parallel_compress`,
+///   since the parallel writer's raw copies would drop the extra field.
+///
+/// # Example
+/// ```rust
+/// use std::path::PathBuf;
+/// use zipoxide::CreationOptions;
+///
+/// let creation_options = CreationOptions {
+///     strip_prefix: Some(PathBuf::from("/home/user/project")),
+///     ..Default::default()
+/// };
+/// assert!(creation_options.strip_prefix.is_some());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CreationOptions {
+    pub strip_prefix: Option<PathBuf>,
+    pub embed_manifest: bool,
+    pub backend: Option<CompressionBackend>,
+    pub pre_allocate: bool,
+    pub parallel_compress: bool,
+    pub compression_level: Option<i64>,
+    pub deduplicate: bool,
+    pub sync: bool,
+    pub store_compressed_formats: bool,
+    pub buffer_size: Option<usize>,
+    pub strip_metadata: bool,
+    pub max_total_uncompressed_bytes: Option<u64>,
+    pub on_error: ErrorPolicy,
+    pub preserve_timestamps: bool,
+}
+
+/// How the writers react to a source file or subdirectory that cannot be read, set through
+/// [`CreationOptions::on_error`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Fail the whole operation with the first error.
+    #[default]
+    AbortOnFirstError,
+    /// Leave the file or subdirectory out of the archive and list it in [`ArchiveStats::skipped_files`].
+    SkipAndCollect,
+}
+
+/// Extensions (compared case-insensitively) of already-compressed formats, stored uncompressed when
+/// [`CreationOptions::store_compressed_formats`] is set.
+pub const COMPRESSED_EXTENSIONS: [&str; 24] = [
+    "jpg", "jpeg", "png", "gif", "webp", "avif", "heic", "mp3", "aac", "ogg", "flac", "mp4", "m4a", "mov", "mkv",
+    "webm", "zip", "gz", "tgz", "bz2", "xz", "zst", "7z", "rar",
+];
+
+/// Returns `true` if the entry name ends in one of [`COMPRESSED_EXTENSIONS`].
+fn is_compressed_format(name: &str) -> bool {
+    Path::new(name).extension().and_then(|extension| extension.to_str()).is_some_and(|extension| {
+        COMPRESSED_EXTENSIONS.iter().any(|compressed| compressed.eq_ignore_ascii_case(extension))
+    })
+}
+
+/// Compression codec used for file entries, selectable at runtime through [`CreationOptions::backend`].
+///
+/// Codec availability follows the crate features: `deflate` (default, pure-Rust zlib-rs),
+/// `deflate-zlib` (system zlib through `flate2`), `bzip2` and `zstd`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionBackend {
+    Stored,
+    #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+    Deflate,
+    #[cfg(feature = "bzip2")]
+    Bzip2,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl CompressionBackend {
+    /// The [`zip::CompressionMethod`] written for entries using this backend.
+    pub fn compression_method(self) -> CompressionMethod {
+        match self {
+            CompressionBackend::Stored => CompressionMethod::Stored,
+            #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+            CompressionBackend::Deflate => CompressionMethod::Deflated,
+            #[cfg(feature = "bzip2")]
+            CompressionBackend::Bzip2 => CompressionMethod::Bzip2,
+            #[cfg(feature = "zstd")]
+            CompressionBackend::Zstd => CompressionMethod::Zstd,
+        }
+    }
+}
+
+/// Summary of an archive written by zipoxide.
+///
+/// # Fields
+/// - `entries`: Number of entries written, including directory entries and an embedded manifest.  
+/// - `uncompressed_size`: Total size of the file data before compression, in bytes.  
+/// - `archive_size`: Size of the finished archive on disk, in bytes.  
+/// - `skipped_files`: Source files and subdirectories left out because they could not be read, with
+///   [`ErrorPolicy::SkipAndCollect`]; always empty otherwise.  
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveStats {
+    pub entries: usize,
+    pub uncompressed_size: u64,
+    pub archive_size: u64,
+    pub skipped_files: Vec<PathBuf>,
+}
+
+/// Builds the archive entry name for `source_path`, honouring `CreationOptions::strip_prefix`
+/// and falling back to `default_name` otherwise.
+fn entry_name(
+    source_path: &Path,
+    default_name: &Path,
+    creation_options: &CreationOptions,
+) -> Result<String, ZipoxideError> {
+    let name: &Path = match &creation_options.strip_prefix {
+        Some(prefix) => source_path.strip_prefix(prefix).map_err(|_| invalid_input_path(source_path))?,
+        None => default_name,
+    };
+    Ok(name.to_str().ok_or_else(|| invalid_input_path(source_path))?.to_string())
+}
+
+/// Fails with [`io::ErrorKind::AlreadyExists`] if `output` exists, for the writers that report this
+/// as an error instead of panicking.
+fn ensure_output_absent(output: &Path) -> Result<(), ZipoxideError> {
+    if output.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "output zip path already exists").into());
+    }
+    Ok(())
+}
+
+/// Error for an input path that cannot be turned into an entry name.
+pub(crate) fn invalid_input_path(path: &Path) -> ZipoxideError {
+    ZipoxideError::InvalidPath(path.to_path_buf())
+}
+
+/// Read buffer size for source files, so large files are pulled from disk in fewer, larger reads.
+const SOURCE_BUFFER_SIZE: usize = 256 * 1024;
+
+/// Chunk size source files are read in, from [`CreationOptions::buffer_size`]. A zero size would read
+/// every file as empty, so it is refused.
+fn source_buffer_size(creation_options: &CreationOptions) -> Result<usize, ZipoxideError> {
+    match creation_options.buffer_size {
+        Some(0) => Err(io::Error::new(io::ErrorKind::InvalidInput, "buffer_size must not be zero").into()),
+        Some(buffer_size) => Ok(buffer_size),
+        None => Ok(SOURCE_BUFFER_SIZE),
+    }
+}
+
+/// A source file or empty directory queued for writing, together with its name inside the archive.
+pub(crate) struct PendingEntry {
+    pub(crate) source_path: PathBuf,
+    pub(crate) name: String,
+    pub(crate) kind: EntryKind,
+}
+
+/// A file whose contents are identical to an earlier entry's, stored as a second central-directory
+/// record pointing at that entry's data.
+struct SharedEntry {
+    original_name: String,
+    name: String,
+    size: u64,
+    crc32: u32,
+}
+
+/// Builds the per-entry options written with [`CreationOptions::preserve_timestamps`].
+///
+/// Timestamps go into an extra field, which only [`FullFileOptions`] can carry, so the caller's method,
+/// level and permissions are moved over to fresh options for every entry.
+struct TimestampedOptions {
+    method: CompressionMethod,
+    level: Option<i64>,
+    file_permissions: Option<u32>,
+    directory_permissions: Option<u32>,
+}
+
+impl TimestampedOptions {
+    /// Reads the settings `zip_options` writes. [`FileOptions`] has no getters for the method and the
+    /// permissions, so they are read back from a file and a directory entry written to a scratch archive.
+    fn new<K: FileOptionExtension + Clone>(zip_options: &FileOptions<'static, K>) -> Result<Self, ZipoxideError> {
+        let mut zip_writer: ZipWriter<Cursor<Vec<u8>>> = ZipWriter::new(Cursor::new(Vec::new()));
+        zip_writer.start_file("file", zip_options.clone())?;
+        zip_writer.add_directory("directory/", zip_options.clone())?;
+        let mut scratch: ZipArchive<Cursor<Vec<u8>>> = ZipArchive::new(zip_writer.finish()?)?;
+        let file: ZipFile<'_, Cursor<Vec<u8>>> = scratch.by_index_raw(0)?;
+        if file.encrypted() {
+            let message: &str = "preserve_timestamps cannot be combined with encryption";
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        }
+        let (method, file_permissions) = (file.compression(), file.unix_mode());
+        drop(file);
+        Ok(TimestampedOptions {
+            method,
+            level: if method == CompressionMethod::Stored { None } else { zip_options.get_compression_level() },
+            file_permissions,
+            directory_permissions: scratch.by_index_raw(1)?.unix_mode(),
+        })
+    }
+
+    /// Options for an entry whose source has `metadata`; `stored` writes a file uncompressed.
+    fn for_entry(
+        &self,
+        kind: EntryKind,
+        metadata: &fs::Metadata,
+        stored: bool,
+    ) -> Result<FullFileOptions<'static>, ZipoxideError> {
+        let mut zip_options: FullFileOptions<'static> =
+            FullFileOptions::default().last_modified_time(dos_time(metadata));
+        zip_options.add_extra_data(NTFS_EXTRA_FIELD_ID, ntfs_extra_field(metadata), false)?;
+        let (zip_options, permissions) = match kind {
+            EntryKind::Directory => (zip_options, self.directory_permissions),
+            EntryKind::File => {
+                let (method, level) = match stored {
+                    true => (CompressionMethod::Stored, None),
+                    false => (self.method, self.level),
+                };
+                let zip_options: FullFileOptions<'static> = zip_options
+                    .compression_method(method)
+                    .compression_level(level)
+                    .large_file(metadata.len() >= u32::MAX as u64);
+                (zip_options, self.file_permissions)
+            }
+        };
+        Ok(match permissions {
+            Some(permissions) => zip_options.unix_permissions(permissions),
+            None => zip_options,
+        })
+    }
+}
+
+/// Computes the CRC32 of a file's contents.
+pub(crate) fn file_crc32(path: &Path) -> io::Result<u32> {
+    let mut f: BufReader<File> = BufReader::with_capacity(SOURCE_BUFFER_SIZE, File::open(path)?);
+    let mut hasher: crc32fast::Hasher = crc32fast::Hasher::new();
+    loop {
+        let chunk: &[u8] = f.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(hasher.finalize());
+        }
+        hasher.update(chunk);
+        let consumed: usize = chunk.len();
+        f.consume(consumed);
+    }
+}
+
+/// Compares two files byte by byte, so a CRC32 collision never merges different contents.
+fn same_contents(a: &Path, b: &Path) -> io::Result<bool> {
+    let mut a: BufReader<File> = BufReader::with_capacity(SOURCE_BUFFER_SIZE, File::open(a)?);
+    let mut b: BufReader<File> = BufReader::with_capacity(SOURCE_BUFFER_SIZE, File::open(b)?);
+    loop {
+        let (a_chunk, b_chunk) = (a.fill_buf()?, b.fill_buf()?);
+        let len: usize = a_chunk.len().min(b_chunk.len());
+        if a_chunk[..len] != b_chunk[..len] {
+            return Ok(false);
+        }
+        if len == 0 {
+            return Ok(a_chunk.is_empty() && b_chunk.is_empty());
+        }
+        a.consume(len);
+        b.consume(len);
+    }
+}
+
+/// Splits `entries` into the ones whose data must be written and the files duplicating an earlier one.
+///
+/// Only files sharing their size with another file are hashed (in parallel); equal size and CRC32 are
+/// confirmed with a byte comparison before an entry is treated as a duplicate.
+fn split_duplicates(entries: Vec<PendingEntry>) -> io::Result<(Vec<PendingEntry>, Vec<SharedEntry>)> {
+    let sizes: Vec<Option<u64>> = entries
+        .iter()
+        .map(|entry| match entry.kind {
+            EntryKind::File => fs::metadata(&entry.source_path).map(|metadata| Some(metadata.len())),
+            EntryKind::Directory => Ok(None),
+        })
+        .collect::<io::Result<_>>()?;
+    let mut size_counts: HashMap<u64, usize> = HashMap::new();
+    for size in sizes.iter().flatten() {
+        *size_counts.entry(*size).or_default() += 1;
+    }
+    let crcs: Vec<Option<u32>> = entries
+        .par_iter()
+        .zip(sizes.par_iter())
+        .map(|(entry, size)| match size {
+            Some(size) if size_counts[size] > 1 => file_crc32(&entry.source_path).map(Some),
+            _ => Ok(None),
+        })
+        .collect::<io::Result<_>>()?;
+
+    let mut unique: Vec<PendingEntry> = Vec::with_capacity(entries.len());
+    let mut shared: Vec<SharedEntry> = Vec::new();
+    // Index into `unique` of every distinct content seen so far, by size and CRC32.
+    let mut seen: HashMap<(u64, u32), Vec<usize>> = HashMap::new();
+    for ((entry, size), crc32) in entries.into_iter().zip(sizes).zip(crcs) {
+        let (Some(size), Some(crc32)) = (size, crc32) else {
+            unique.push(entry);
+            continue;
+        };
+        let candidates: &mut Vec<usize> = seen.entry((size, crc32)).or_default();
+        let mut original: Option<&PendingEntry> = None;
+        for &candidate in candidates.iter() {
+            if same_contents(&unique[candidate].source_path, &entry.source_path)? {
+                original = Some(&unique[candidate]);
+                break;
+            }
+        }
+        match original {
+            Some(original) => {
+                let original_name: String = original.name.clone();
+                shared.push(SharedEntry { original_name, name: entry.name, size, crc32 });
+            }
+            None => {
+                candidates.push(unique.len());
+                unique.push(entry);
+            }
+        }
+    }
+    Ok((unique, shared))
+}
+
+/// Queues regular files under their file names, failing with [`ZipoxideError::NotAFile`] for anything else.
+fn file_name_entries(files: Vec<impl AsRef<Path>>) -> Result<Vec<PendingEntry>, ZipoxideError> {
+    let mut entries: Vec<PendingEntry> = Vec::with_capacity(files.len());
+    for file in files {
+        let path: &Path = file.as_ref();
+        let name: Option<&str> = path.file_name().and_then(|name| name.to_str());
+        match name {
+            Some(name) if path.is_file() => entries.push(PendingEntry {
+                source_path: path.to_path_buf(),
+                name: name.to_string(),
+                kind: EntryKind::File,
+            }),
+            _ => return Err(ZipoxideError::NotAFile(path.to_path_buf())),
+        }
+    }
+    Ok(entries)
+}
+
+/// Bytes written per entry besides its data and name: local file header (30) and central directory record (46).
+const ENTRY_OVERHEAD: u64 = 30 + 46;
+
+/// Size of the end-of-central-directory record without a comment.
+const END_OF_CENTRAL_DIRECTORY_SIZE: u64 = 22;
+
+/// Rough compressed-to-uncompressed ratio used for size estimates.
+/// Without an explicit backend the `FileOptions` default (Deflate) is assumed.
+fn estimated_compression_ratio(backend: Option<CompressionBackend>) -> f64 {
+    match backend {
+        Some(CompressionBackend::Stored) => 1.0,
+        _ => 0.7,
+    }
+}
+
+/// Estimates the size of the archive that writing `entries` will produce, by stat-ing every source file.
+fn estimate_archive_size(entries: &[PendingEntry], creation_options: &CreationOptions) -> io::Result<u64> {
+    let ratio: f64 = estimated_compression_ratio(creation_options.backend);
+    let mut total: u64 = END_OF_CENTRAL_DIRECTORY_SIZE;
+    for entry in entries {
+        let data_size: u64 = match entry.kind {
+            EntryKind::File => fs::metadata(&entry.source_path)?.len(),
+            EntryKind::Directory => 0,
+        };
+        total += (data_size as f64 * ratio).ceil() as u64 + ENTRY_OVERHEAD + 2 * entry.name.len() as u64;
+    }
+    Ok(total)
+}
+
+/// Fails with [`ZipoxideError::SizeLimitExceeded`] if the files among `entries` add up to more than
+/// `limit` bytes.
+fn check_total_size(entries: &[PendingEntry], limit: u64) -> Result<(), ZipoxideError> {
+    let mut actual: u64 = 0;
+    for entry in entries.iter().filter(|entry| entry.kind == EntryKind::File) {
+        actual = actual.saturating_add(fs::metadata(&entry.source_path)?.len());
+    }
+    if actual > limit {
+        return Err(ZipoxideError::SizeLimitExceeded { actual, limit });
+    }
+    Ok(())
+}
+
+/// Compresses a single entry into a standalone in-memory archive holding only that entry.
+fn compress_entry<K: FileOptionExtension>(
+    entry: &PendingEntry,
+    zip_options: FileOptions<'static, K>,
+    buffer_size: usize,
+) -> Result<Vec<u8>, ZipoxideError> {
+    let mut zip_writer: ZipWriter<Cursor<Vec<u8>>> = ZipWriter::new(Cursor::new(Vec::new()));
+    match entry.kind {
+        EntryKind::Directory => zip_writer.add_directory(entry.name.as_str(), zip_options)?,
+        EntryKind::File => {
+            zip_writer.start_file(entry.name.as_str(), zip_options)?;
+            let mut f: BufReader<File> = BufReader::with_capacity(buffer_size, File::open(&entry.source_path)?);
+            io::copy(&mut f, &mut zip_writer)?;
+        }
+    }
+    Ok(zip_writer.finish()?.into_inner())
+}
+
+/// Compresses `entries` on Rayon worker threads and passes each result, in the original order,
+/// to `write_compressed` on the calling thread.
+///
+/// Workers send `(index, compressed archive)` over a channel; results that arrive early are parked
+/// until every preceding entry has been written. After the first failure, remaining workers stop
+/// compressing and the error is returned. With `skip_unreadable`, an entry whose source fails with an
+/// I/O error is passed to `write_compressed` as `None` instead.
+fn compress_in_parallel<K, O, F>(
+    entries: &[PendingEntry],
+    options_for: O,
+    buffer_size: usize,
+    skip_unreadable: bool,
+    mut write_compressed: F,
+) -> Result<(), ZipoxideError>
+where
+    K: FileOptionExtension,
+    O: Fn(&PendingEntry) -> FileOptions<'static, K> + Send + Sync,
+    F: FnMut(&PendingEntry, Option<ZipArchive<Cursor<Vec<u8>>>>) -> Result<(), ZipoxideError>,
+{
+    let (sender, receiver) = mpsc::channel::<(usize, Result<Vec<u8>, ZipoxideError>)>();
+    let failed: AtomicBool = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let failed: &AtomicBool = &failed;
+        scope.spawn(move || {
+            entries.par_iter().enumerate().for_each_with(sender, |sender, (index, entry)| {
+                if failed.load(Ordering::Relaxed) {
+                    return;
+                }
+                // A closed channel means the writer already gave up; nothing left to do.
+                let _ = sender.send((index, compress_entry(entry, options_for(entry), buffer_size)));
+            });
+        });
+
+        let result = (|| -> Result<(), ZipoxideError> {
+            let mut parked: BTreeMap<usize, Option<Vec<u8>>> = BTreeMap::new();
+            let mut next_index: usize = 0;
+            for (index, compressed) in receiver.iter() {
+                // Compressing into memory cannot fail with an I/O error; those come from the source file.
+                let compressed: Option<Vec<u8>> = match compressed {
+                    Err(ZipoxideError::Io(_)) if skip_unreadable => None,
+                    compressed => Some(compressed?),
+                };
+                parked.insert(index, compressed);
+                while let Some(bytes) = parked.remove(&next_index) {
+                    let compressed: Option<ZipArchive<Cursor<Vec<u8>>>> =
+                        bytes.map(|bytes| ZipArchive::new(Cursor::new(bytes))).transpose()?;
+                    write_compressed(&entries[next_index], compressed)?;
+                    next_index += 1;
+                }
+            }
+            Ok(())
+        })();
+        if result.is_err() {
+            failed.store(true, Ordering::Relaxed);
+        }
+        // Dropping the receiver unblocks workers still trying to send.
+        drop(receiver);
+        result
+    })
+}
+
+/// Files and empty subdirectories found by [`walk_folder`], followed by the subdirectories it skipped.
+pub(crate) type FolderListing = (Vec<(PathBuf, EntryKind)>, Vec<PathBuf>);
+
+/// Recursively lists the files and empty subdirectories below `folder_path`.
+///
+/// The entries of every directory are visited in byte order of their names, so the listing (and the
+/// archives built from it) is the same on every platform and run, whatever order `read_dir` returns.
+/// Symlinks are followed, but a directory reached a second time (a symlink back to an ancestor, or a
+/// second link to the same directory) is skipped, so link cycles cannot make the walk loop forever.
+///
+/// With [`ErrorPolicy::SkipAndCollect`], a subdirectory that cannot be listed is left out and returned
+/// in the second list instead of failing the walk; `folder_path` itself must always be readable.
+pub(crate) fn walk_folder(
+    folder_path: &Path,
+    on_error: ErrorPolicy,
+) -> io::Result<FolderListing> {
+    let mut found: Vec<(PathBuf, EntryKind)> = Vec::new();
+    let mut skipped: Vec<PathBuf> = Vec::new();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut paths_to_visit: Vec<PathBuf> = vec![folder_path.to_path_buf()];
+
+    while let Some(path) = paths_to_visit.pop() {
+        if !path.is_dir() {
+            found.push((path, EntryKind::File));
+            continue;
+        }
+        let listing: io::Result<(PathBuf, Vec<PathBuf>)> = fs::canonicalize(&path).and_then(|canonical| {
+            let children: Vec<PathBuf> = fs::read_dir(&path)?
+                .map(|entry| entry.map(|entry: DirEntry| entry.path()))
+                .collect::<io::Result<_>>()?;
+            Ok((canonical, children))
+        });
+        let (canonical, mut children) = match listing {
+            Ok(listing) => listing,
+            Err(_) if on_error == ErrorPolicy::SkipAndCollect && path != folder_path => {
+                skipped.push(path);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if !visited.insert(canonical) {
+            continue;
+        }
+        if children.is_empty() && path != folder_path {
+            found.push((path, EntryKind::Directory));
+        }
+        // Pushed in reverse so the stack pops them, and walks their subtrees, in sorted order.
+        children.sort_unstable_by(|a, b| b.file_name().cmp(&a.file_name()));
+        paths_to_visit.extend(children);
+    }
+    Ok((found, skipped))
+}
+
+/// Lists the contents of `folder` as entries named relative to it.
+fn folder_entries(folder: &Path) -> Result<Vec<PendingEntry>, ZipoxideError> {
+    let mut entries: Vec<PendingEntry> = Vec::new();
+    let (found, _) = walk_folder(folder, ErrorPolicy::AbortOnFirstError)?;
+    for (path, kind) in found {
+        let name: String = match path.strip_prefix(folder).ok().and_then(Path::to_str) {
+            Some(name) => name.to_string(),
+            None => return Err(invalid_input_path(&path)),
+        };
+        entries.push(PendingEntry { source_path: path, name, kind });
+    }
+    Ok(entries)
+}
+
+/// Writes `entries` to a new archive at `output_zip_path`, in order.
+fn write_archive<K: FileOptionExtension + Clone + Send + Sync>(
+    output_zip_path: &Path,
+    entries: Vec<PendingEntry>,
+    zip_options: FileOptions<'static, K>,
+    creation_options: &CreationOptions,
+) -> Result<ArchiveStats, ZipoxideError> {
+    #[cfg(not(feature = "manifest"))]
+    if creation_options.embed_manifest {
+        return Err(ZipoxideError::FeatureDisabled("manifest"));
+    }
+    source_buffer_size(creation_options)?; // refuse a zero buffer size before creating the output
+    if let Some(limit) = creation_options.max_total_uncompressed_bytes {
+        check_total_size(&entries, limit)?;
+    }
+    let zip_file: File = File::create(output_zip_path)?;
+    if creation_options.pre_allocate {
+        zip_file.set_len(estimate_archive_size(&entries, creation_options)?)?;
+    }
+    let (zip_file, stats) = match creation_options.buffer_size {
+        Some(capacity) => {
+            let sink: BufWriter<File> = BufWriter::with_capacity(capacity, zip_file);
+            let (sink, stats) = write_archive_to(sink, entries, zip_options, creation_options)?;
+            (sink.into_inner().map_err(io::IntoInnerError::into_error)?, stats)
+        }
+        None => write_archive_to(zip_file, entries, zip_options, creation_options)?,
+    };
+    if creation_options.pre_allocate {
+        // Give back whatever part of the pre-allocated space the archive did not use.
+        zip_file.set_len(stats.archive_size)?;
+    }
+    if creation_options.sync {
+        zip_file.sync_all()?;
+    }
+    Ok(stats)
+}
+
+/// Writes `entries` as a ZIP archive to `sink`, in order, returning the sink once the archive is finished.
+///
+/// `creation_options.embed_manifest` is honoured only with the `manifest` feature; callers that accept it
+/// from users check the feature first.
+fn write_archive_to<W: Write + Seek, K: FileOptionExtension + Clone + Send + Sync>(
+    sink: W,
+    entries: Vec<PendingEntry>,
+    zip_options: FileOptions<'static, K>,
+    creation_options: &CreationOptions,
+) -> Result<(W, ArchiveStats), ZipoxideError> {
+    write_entries(ZipWriter::new(sink), entries, zip_options, creation_options)
+}
+
+/// Writes `entries` after whatever `zip_writer` already holds and finishes the archive, as
+/// [`write_archive_to`] does for a new one. The returned stats only count `entries`.
+pub(crate) fn write_entries<W: Write + Seek, K: FileOptionExtension + Clone + Send + Sync>(
+    mut zip_writer: ZipWriter<W>,
+    entries: Vec<PendingEntry>,
+    zip_options: FileOptions<'static, K>,
+    creation_options: &CreationOptions,
+) -> Result<(W, ArchiveStats), ZipoxideError> {
+    let zip_options: FileOptions<'static, K> = match creation_options.backend {
+        Some(backend) => zip_options.compression_method(backend.compression_method()),
+        None => zip_options,
+    };
+    let compression_level: Option<i64> =
+        creation_options.compression_level.or(ZipOxideConfig::current().compression_level);
+    let zip_options: FileOptions<'static, K> = match compression_level {
+        Some(level) => zip_options.compression_level(Some(level)),
+        None => zip_options,
+    };
+    let zip_options: FileOptions<'static, K> = match creation_options.strip_metadata {
+        true => zip_options.last_modified_time(DateTime::default()),
+        false => zip_options,
+    };
+    let stores = |entry: &PendingEntry| creation_options.store_compressed_formats && is_compressed_format(&entry.name);
+    let options_for = |entry: &PendingEntry| -> FileOptions<'static, K> {
+        match stores(entry) {
+            true => zip_options.clone().compression_method(CompressionMethod::Stored).compression_level(None),
+            false => zip_options.clone(),
+        }
+    };
+    let timestamped: Option<TimestampedOptions> =
+        match creation_options.preserve_timestamps && !creation_options.strip_metadata {
+            true => Some(TimestampedOptions::new(&zip_options)?),
+            false => None,
+        };
+    let (entries, shared) = match creation_options.deduplicate {
+        true => split_duplicates(entries)?,
+        false => (entries, Vec::new()),
+    };
+    let mut stats: ArchiveStats = ArchiveStats { entries: entries.len() + shared.len(), ..Default::default() };
+    let buffer_size: usize = source_buffer_size(creation_options)?;
+
+    #[cfg(feature = "manifest")]
+    let mut manifest: Option<Manifest> = creation_options.embed_manifest.then(Manifest::default);
+
+    let skip_unreadable: bool = creation_options.on_error == ErrorPolicy::SkipAndCollect;
+    let skip = |stats: &mut ArchiveStats, entry: &PendingEntry| {
+        stats.entries -= 1;
+        stats.skipped_files.push(entry.source_path.clone());
+    };
+    // Raw copies drop extra fields, so timestamped entries are always written sequentially.
+    if creation_options.parallel_compress && timestamped.is_none() {
+        compress_in_parallel(&entries, options_for, buffer_size, skip_unreadable, |entry, compressed| {
+            let Some(mut compressed) = compressed else {
+                skip(&mut stats, entry);
+                return Ok(());
+            };
+            let file: ZipFile<'_, Cursor<Vec<u8>>> = compressed.by_index_raw(0)?;
+            #[cfg(feature = "manifest")]
+            if let Some(manifest) = manifest.as_mut()
+                && entry.kind == EntryKind::File
+            {
+                manifest.record(&entry.name, file.crc32(), file.size());
+            }
+            stats.uncompressed_size += file.size();
+            zip_writer.raw_copy_file(file)?;
+            Ok(())
+        })?;
+    } else {
+        for entry in entries.iter() {
+            if entry.kind == EntryKind::Directory {
+                match &timestamped {
+                    Some(timestamped) => match fs::metadata(&entry.source_path) {
+                        Ok(metadata) => {
+                            let zip_options: FullFileOptions<'static> =
+                                timestamped.for_entry(entry.kind, &metadata, false)?;
+                            zip_writer.add_directory(entry.name.as_str(), zip_options)?;
+                        }
+                        Err(_) if skip_unreadable => skip(&mut stats, entry),
+                        Err(e) => return Err(e.into()),
+                    },
+                    None => zip_writer.add_directory(entry.name.as_str(), zip_options.clone())?,
+                }
+                continue;
+            }
+            let source: File = match File::open(&entry.source_path) {
+                Ok(source) => source,
+                Err(_) if skip_unreadable => {
+                    skip(&mut stats, entry);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            match &timestamped {
+                Some(timestamped) => {
+                    let zip_options: FullFileOptions<'static> =
+                        timestamped.for_entry(entry.kind, &source.metadata()?, stores(entry))?;
+                    zip_writer.start_file(entry.name.as_str(), zip_options)?;
+                }
+                None => zip_writer.start_file(entry.name.as_str(), options_for(entry))?,
+            }
+            let mut f: BufReader<File> = BufReader::with_capacity(buffer_size, source);
+            #[cfg(feature = "manifest")]
+            let copied: io::Result<u64> = match manifest.as_mut() {
+                Some(manifest) => manifest.copy_and_record(&entry.name, &mut f, &mut zip_writer),
+                None => io::copy(&mut f, &mut zip_writer),
+            };
+            #[cfg(not(feature = "manifest"))]
+            let copied: io::Result<u64> = io::copy(&mut f, &mut zip_writer);
+            match copied {
+                Ok(size) => stats.uncompressed_size += size,
+                // A file failing partway (truncated, I/O error) is dropped from the archive again.
+                Err(_) if skip_unreadable => {
+                    zip_writer.abort_file()?;
+                    skip(&mut stats, entry);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    for shared in shared {
+        zip_writer.shallow_copy_file(&shared.original_name, &shared.name)?;
+        #[cfg(feature = "manifest")]
+        if let Some(manifest) = manifest.as_mut() {
+            manifest.record(&shared.name, shared.crc32, shared.size);
+        }
+        #[cfg(not(feature = "manifest"))]
+        let _ = shared.crc32;
+        stats.uncompressed_size += shared.size;
+    }
+
+    #[cfg(feature = "manifest")]
+    if let Some(manifest) = manifest {
+        zip_writer.start_file(MANIFEST_NAME, zip_options)?;
+        zip_writer.write_all(&manifest.to_json().map_err(io::Error::from)?)?;
+        stats.entries += 1;
+    }
+
+    let mut sink: W = zip_writer.finish()?;
+    stats.archive_size = sink.stream_position()?;
+    Ok((sink, stats))
+}
 
 /// Creates a ZIP archive from the contents of a folder, including all nested files and subdirectories.
 ///
 /// # Arguments
-/// - `output_zip_path`: Path where the resulting ZIP archive will be created.  
-/// - `folder_path`: Root folder whose contents (including subdirectories) will be compressed into the ZIP.  
+/// - `output_zip_path`: Path where the resulting ZIP archive will be created.  
+/// - `folder_path`: Root folder whose contents (including subdirectories) will be compressed into the ZIP.  
+/// - `zip_options`: [`zip::write::FileOptions`] specifying compression method, permissions, etc. Any
+///   extension type is accepted: [`SimpleFileOptions`] for plain entries, or
+///   `FileOptions<'static, ExtendedFileOptions>` to attach extra fields to every entry.
+///
+/// # Behavior
+/// - Preserves the relative directory structure inside the archive.  
+/// - Recursively traverses subdirectories; empty subdirectories are stored as directory entries.  
+/// - Source files are read through a 256 KiB `BufReader`, issuing fewer, larger reads for big files.  
+/// - Panics if the output ZIP file already exists.  
+/// - Non-UTF8 file paths will cause a runtime error.  
+///
+/// # Errors
+/// Returns an error if:
+/// - The folder path does not exist or is not a directory ([`ZipoxideError::NotADirectory`]), checked
+///   before the archive is created.  
+/// - The folder or one of its subdirectories cannot be read.  
+/// - A file cannot be opened or read.  
+/// - Writing to the ZIP archive fails.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_from_folder;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     create_zip_from_folder(
+///         "archive.zip".to_string(),
+///         "my_folder".to_string(),
+///         zip::write::SimpleFileOptions::default(),
+///     )?;
+///     Ok(())
+/// }
+/// ```
+#[allow(unused)]
+pub fn create_zip_from_folder<K: FileOptionExtension + Clone + Send + Sync>(
+    output_zip_path: String,
+    folder_path: String,
+    zip_options: FileOptions<'static, K>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if Path::new(&output_zip_path).exists() {
+        panic!("Output zip path already exists.");
+    }
+    create_zip_from_folder_with_options(output_zip_path, folder_path, zip_options, CreationOptions::default())?;
+    Ok(())
+}
+
+/// Same as [`create_zip_from_folder`], with archive-level behaviour controlled by [`CreationOptions`].
+///
+/// # Behavior
+/// - Without `strip_prefix`, entry names are relative to `folder_path` (the folder name itself is not stored).  
+/// - With `strip_prefix`, entry names are the file paths with that prefix removed, e.g. stripping the
+///   parent of `folder_path` keeps the folder name as the top-level directory inside the archive.  
+/// - Unlike [`create_zip_from_folder`], an existing output ZIP file is reported as an error rather than a panic.  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive. With `on_error` set to
+/// [`ErrorPolicy::SkipAndCollect`], [`ArchiveStats::skipped_files`] lists the subdirectories that could
+/// not be listed and the files that could not be read, all left out of the archive.
+///
+/// # Errors
+/// In addition to the errors of [`create_zip_from_folder`], returns [`ZipoxideError::Io`] with
+/// [`io::ErrorKind::AlreadyExists`] if `output_zip_path` already exists, [`ZipoxideError::InvalidPath`] if a
+/// file path does not start with `strip_prefix`, and [`ZipoxideError::SizeLimitExceeded`] if the files
+/// exceed `max_total_uncompressed_bytes`.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::path::PathBuf;
+/// use zipoxide::{create_zip_from_folder_with_options, CreationOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Entries are stored as `my_folder/...`
+///     create_zip_from_folder_with_options(
+///         "archive.zip".to_string(),
+///         "data/my_folder".to_string(),
+///         zip::write::SimpleFileOptions::default(),
+///         CreationOptions { strip_prefix: Some(PathBuf::from("data")), ..Default::default() },
+///     )?;
+///     Ok(())
+/// }
+/// ```
+#[allow(unused)]
+pub fn create_zip_from_folder_with_options<K: FileOptionExtension + Clone + Send + Sync>(
+    output_zip_path: String,
+    folder_path: String,
+    zip_options: FileOptions<'static, K>,
+    creation_options: CreationOptions,
+) -> Result<ArchiveStats, Box<dyn std::error::Error>> {
+    let output_zip_path: &Path = Path::new(&output_zip_path);
+    ensure_output_absent(output_zip_path)?;
+    let folder_path: &Path = Path::new(&folder_path);
+    if !folder_path.is_dir() {
+        return Err(Box::new(ZipoxideError::NotADirectory(folder_path.to_path_buf())));
+    }
+
+    let (found, mut skipped) = walk_folder(folder_path, creation_options.on_error)?;
+    let mut entries: Vec<PendingEntry> = Vec::new();
+    for (path, kind) in found {
+        let name: String = entry_name(&path, path.strip_prefix(folder_path)?, &creation_options)?;
+        entries.push(PendingEntry { source_path: path, name, kind });
+    }
+
+    let mut stats: ArchiveStats = write_archive(output_zip_path, entries, zip_options, &creation_options)?;
+    skipped.append(&mut stats.skipped_files);
+    stats.skipped_files = skipped;
+    Ok(stats)
+}
+
+/// Creates a ZIP archive from a folder like [`create_zip_from_folder`], forcing the same Unix
+/// permission bits on every entry.
+///
+/// # Arguments
+/// - `output_zip_path`: Path where the resulting ZIP archive will be created.  
+/// - `folder_path`: Root folder whose contents will be compressed into the ZIP.  
+/// - `mode`: Permission bits stored for every entry (e.g. `0o644` for a release archive), regardless
+///   of the permissions the files have on disk.  
+/// - `zip_options`: [`zip::write::FileOptions`] for everything but the permissions.  
+///
+/// # Behavior
+/// - Equivalent to passing `zip_options.unix_permissions(mode)` to [`create_zip_from_folder`].  
+/// - Extractors that honour Unix modes, including [`extract_zip`](crate::extract_zip) on Unix, restore
+///   files with these bits.  
+///
+/// # Errors
+/// Same as [`create_zip_from_folder`].
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_from_folder_with_mode;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     create_zip_from_folder_with_mode(
+///         "release.zip".to_string(),
+///         "dist".to_string(),
+///         0o644,
+///         zip::write::SimpleFileOptions::default(),
+///     )?;
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_folder_with_mode<K: FileOptionExtension + Clone + Send + Sync>(
+    output_zip_path: String,
+    folder_path: String,
+    mode: u32,
+    zip_options: FileOptions<'static, K>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    create_zip_from_folder(output_zip_path, folder_path, zip_options.unix_permissions(mode))
+}
+
+/// Creates a ZIP archive from a folder like [`create_zip_from_folder`], picking the compression from
+/// a single `0`–`9` level instead of [`FileOptions`].
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `folder`: Root folder whose contents will be compressed into the ZIP.  
+/// - `level`: `0` stores files uncompressed, `1` is the fastest Deflate and `9` the smallest; values in
+///   between trade speed for size as with `gzip -1` … `gzip -9`. Values above 9 are treated as 9.  
+///
+/// # Behavior
+/// - Entry names are relative to `folder`, as for [`create_zip_from_folder`].  
+/// - The level overrides any level set through [`ZipOxideConfig`](crate::ZipOxideConfig).  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - `folder` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - `level` is not `0` and this build has no Deflate backend ([`ZipoxideError::FeatureDisabled`]).  
+/// - A path is not valid UTF-8, a file cannot be read, or the archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_from_folder_level;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let stats = create_zip_from_folder_level("backup.zip", "my_folder", 9)?;
+///     println!("{} bytes", stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_folder_level(
+    output: impl AsRef<Path>,
+    folder: impl AsRef<Path>,
+    level: u8,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    let folder: &Path = folder.as_ref();
+    ensure_output_absent(output)?;
+    if !folder.is_dir() {
+        return Err(ZipoxideError::NotADirectory(folder.to_path_buf()));
+    }
+    let creation_options: CreationOptions = match level.min(9) {
+        0 => CreationOptions { backend: Some(CompressionBackend::Stored), ..Default::default() },
+        #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+        level => CreationOptions {
+            backend: Some(CompressionBackend::Deflate),
+            compression_level: Some(i64::from(level)),
+            ..Default::default()
+        },
+        #[cfg(not(any(feature = "deflate", feature = "deflate-zlib")))]
+        _ => return Err(ZipoxideError::FeatureDisabled("deflate")),
+    };
+    write_archive(output, folder_entries(folder)?, SimpleFileOptions::default(), &creation_options)
+}
+
+/// Creates a ZIP archive from a folder like [`create_zip_from_folder`], storing already-compressed
+/// files as-is and deflating everything else.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `folder`: Root folder whose contents will be compressed into the ZIP.  
+///
+/// # Behavior
+/// - Files with an extension from [`COMPRESSED_EXTENSIONS`] (`.jpg`, `.png`, `.mp4`, `.zip`, `.gz`, ...)
+///   are stored; Deflate would spend CPU time on them for little or no gain. All other files are
+///   deflated at the default level. Same as setting [`CreationOptions::store_compressed_formats`].  
+/// - Entry names are relative to `folder`, as for [`create_zip_from_folder`].  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - `folder` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - This build has no Deflate backend ([`ZipoxideError::FeatureDisabled`]).  
+/// - A path is not valid UTF-8, a file cannot be read, or the archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_from_folder_smart;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let stats = create_zip_from_folder_smart("photos.zip", "photos")?;
+///     println!("{} entries, {} bytes", stats.entries, stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_folder_smart(
+    output: impl AsRef<Path>,
+    folder: impl AsRef<Path>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    let folder: &Path = folder.as_ref();
+    ensure_output_absent(output)?;
+    if !folder.is_dir() {
+        return Err(ZipoxideError::NotADirectory(folder.to_path_buf()));
+    }
+    #[cfg(not(any(feature = "deflate", feature = "deflate-zlib")))]
+    return Err(ZipoxideError::FeatureDisabled("deflate"));
+    #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+    {
+        let creation_options: CreationOptions = CreationOptions {
+            backend: Some(CompressionBackend::Deflate),
+            store_compressed_formats: true,
+            ..Default::default()
+        };
+        write_archive(output, folder_entries(folder)?, SimpleFileOptions::default(), &creation_options)
+    }
+}
+
+/// Creates a ZIP archive from a folder like [`create_zip_from_folder`], compressing files on all cores.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `folder`: Root folder whose contents will be compressed into the ZIP.  
+/// - `zip_options`: Compression method, level, permissions, etc. applied to every entry.  
+///
+/// # Behavior
+/// - Same as [`create_zip_from_folder_with_options`] with [`CreationOptions::parallel_compress`] set:
+///   each file is compressed into memory on a Rayon worker, and a single writer appends the results in
+///   sorted order, so the archive is identical to the sequential one.  
+/// - Pays off for many files and CPU-bound methods (high Deflate levels, Bzip2, Zstd); for a handful of
+///   small files, or `Stored`, the sequential writers are as fast.  
+/// - Entry names are relative to `folder`.  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - `folder` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - A path is not valid UTF-8, a file cannot be read, or the archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zip::CompressionMethod;
+/// use zip::write::SimpleFileOptions;
+/// use zipoxide::create_zip_from_folder_parallel;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let options = SimpleFileOptions::default().compression_method(CompressionMethod::Bzip2);
+///     let stats = create_zip_from_folder_parallel("dataset.zip", "dataset", options)?;
+///     println!("{} entries, {} bytes", stats.entries, stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_folder_parallel<K: FileOptionExtension + Clone + Send + Sync>(
+    output: impl AsRef<Path>,
+    folder: impl AsRef<Path>,
+    zip_options: FileOptions<'static, K>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    let folder: &Path = folder.as_ref();
+    ensure_output_absent(output)?;
+    if !folder.is_dir() {
+        return Err(ZipoxideError::NotADirectory(folder.to_path_buf()));
+    }
+    let creation_options: CreationOptions = CreationOptions { parallel_compress: true, ..Default::default() };
+    write_archive(output, folder_entries(folder)?, zip_options, &creation_options)
+}
+
+/// Reads the CRC32 of every entry in `baseline`, keyed by entry name (directories keep their trailing `/`).
+pub(crate) fn baseline_checksums(baseline: &Path) -> Result<HashMap<String, u32>, ZipoxideError> {
+    let mut archive: ZipArchive<BufReader<File>> = ZipArchive::new(BufReader::new(File::open(baseline)?))?;
+    let mut checksums: HashMap<String, u32> = HashMap::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        // Raw access reads only the headers, so encrypted baselines need no password.
+        let entry: ZipFile<'_, BufReader<File>> = archive.by_index_raw(i)?;
+        checksums.insert(entry.name().to_string(), entry.crc32());
+    }
+    Ok(checksums)
+}
+
+/// Creates a "diff" archive holding only the files of `source_dir` that are new or changed since
+/// `baseline_zip` was made, e.g. to ship just what changed since the last deployment.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `source_dir`: Folder holding the current version of the files.  
+/// - `baseline_zip`: Earlier archive of the same folder to compare against.  
+/// - `options`: Archive-level settings, as for [`create_zip_from_folder_with_options`]. Entry names are
+///   relative to `source_dir` unless `strip_prefix` is set, and must match the baseline's names.  
+///
+/// # Behavior
+/// - A file is included when the baseline has no entry of the same name, or when the CRC32 of its
+///   contents differs from the one recorded for that entry. Checksums are computed in parallel.  
+/// - Empty directories are included when the baseline lacks them.  
+/// - Files deleted since the baseline are not recorded; the diff archive only adds and replaces.  
+/// - When nothing changed, the archive is still written, with no entries.  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - `source_dir` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - `baseline_zip` cannot be opened or is not a valid ZIP archive.  
+/// - A path does not start with `strip_prefix` or is not valid UTF-8, a file cannot be read, or the
+///   archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{create_incremental_zip, CreationOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let stats = create_incremental_zip("patch.zip", "dist", "release-1.0.zip", CreationOptions::default())?;
+///     println!("{} files changed since 1.0", stats.entries);
+///     Ok(())
+/// }
+/// ```
+pub fn create_incremental_zip(
+    output: impl AsRef<Path>,
+    source_dir: impl AsRef<Path>,
+    baseline_zip: impl AsRef<Path>,
+    options: CreationOptions,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    let source_dir: &Path = source_dir.as_ref();
+    ensure_output_absent(output)?;
+    if !source_dir.is_dir() {
+        return Err(ZipoxideError::NotADirectory(source_dir.to_path_buf()));
+    }
+    let baseline: HashMap<String, u32> = baseline_checksums(baseline_zip.as_ref())?;
+
+    let root: &Path = options.strip_prefix.as_deref().unwrap_or(source_dir);
+    let mut candidates: Vec<PendingEntry> = Vec::new();
+    let (found, mut skipped) = walk_folder(source_dir, options.on_error)?;
+    for (path, kind) in found {
+        let name: String = match path.strip_prefix(root).ok().and_then(Path::to_str) {
+            Some(name) => name.to_string(),
+            None => return Err(invalid_input_path(&path)),
+        };
+        candidates.push(PendingEntry { source_path: path, name, kind });
+    }
+    let changed: Vec<Option<PendingEntry>> = candidates
+        .into_par_iter()
+        .map(|entry| {
+            let is_changed: bool = match entry.kind {
+                EntryKind::Directory => !baseline.contains_key(&format!("{}/", entry.name)),
+                EntryKind::File => baseline.get(&entry.name) != Some(&file_crc32(&entry.source_path)?),
+            };
+            Ok(is_changed.then_some(entry))
+        })
+        .collect::<io::Result<_>>()?;
+    let entries: Vec<PendingEntry> = changed.into_iter().flatten().collect();
+    let mut stats: ArchiveStats = write_archive(output, entries, SimpleFileOptions::default(), &options)?;
+    skipped.append(&mut stats.skipped_files);
+    stats.skipped_files = skipped;
+    Ok(stats)
+}
+
+/// Estimates the size of the archive [`create_zip_from_folder_with_options`] would produce for `root`,
+/// so callers can check free disk space before creating it.
+///
+/// # Arguments
+/// - `root`: Folder that would be archived.  
+/// - `creation_options`: Settings the archive would be created with; `backend` selects the assumed
+///   compression ratio and `strip_prefix` the entry names.  
+///
+/// # Behavior
+/// - Walks the directory tree and stats every file; nothing is read or compressed.  
+/// - Each file contributes its size multiplied by the compression ratio (1.0 for
+///   [`CompressionBackend::Stored`], 0.7 otherwise, including the Deflate default), plus its local header
+///   and central-directory record.  
+/// - Data that does not compress (already-compressed media, random bytes) can exceed the 0.7 estimate;
+///   use `Stored` for a strict upper bound.  
+///
+/// # Errors
+/// Returns an error if:
+/// - `root` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - `root` or one of its subdirectories cannot be read, or a file cannot be stat-ed.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{zip_file_size_estimate, CreationOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let estimate = zip_file_size_estimate("my_folder", &CreationOptions::default())?;
+///     println!("The archive will take about {estimate} bytes");
+///     Ok(())
+/// }
+/// ```
+pub fn zip_file_size_estimate(
+    root: impl AsRef<Path>,
+    creation_options: &CreationOptions,
+) -> Result<u64, ZipoxideError> {
+    let root: &Path = root.as_ref();
+    if !root.is_dir() {
+        return Err(ZipoxideError::NotADirectory(root.to_path_buf()));
+    }
+    let entries: Vec<PendingEntry> = walk_folder(root, creation_options.on_error)?
+        .0
+        .into_iter()
+        .map(|(path, kind)| {
+            let relative_path: &Path = path.strip_prefix(root).unwrap_or(&path);
+            let name: &Path = match &creation_options.strip_prefix {
+                Some(prefix) => path.strip_prefix(prefix).unwrap_or(relative_path),
+                None => relative_path,
+            };
+            let name: String = name.to_string_lossy().into_owned();
+            PendingEntry { source_path: path, name, kind }
+        })
+        .collect();
+    Ok(estimate_archive_size(&entries, creation_options)?)
+}
+
+/// Creates a ZIP archive from a folder, compressing every file with Zstandard.
+///
+/// Equivalent to [`create_zip_from_folder_with_options`] with
+/// [`CompressionBackend::Zstd`] selected. Zstd entries need a reader with zstd support
+/// (zipoxide, recent `zip` crate, 7-Zip), which older unzip tools may lack.
+///
+/// # Errors
+/// Same as [`create_zip_from_folder_with_options`].
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_zstd_from_folder;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     create_zip_zstd_from_folder("archive.zip".to_string(), "my_folder".to_string())?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "zstd")]
+pub fn create_zip_zstd_from_folder(
+    output_zip_path: String,
+    folder_path: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    create_zip_from_folder_with_options(
+        output_zip_path,
+        folder_path,
+        SimpleFileOptions::default(),
+        CreationOptions { backend: Some(CompressionBackend::Zstd), ..Default::default() },
+    )?;
+    Ok(())
+}
+
+/// Name of the entry holding the trained dictionary in archives written by [`create_zip_with_zstd_dict`].
+#[cfg(feature = "zstd")]
+pub const ZSTD_DICTIONARY_NAME: &str = "zstd.dict";
+
+/// Most files read to train the dictionary of [`create_zip_with_zstd_dict`].
+#[cfg(feature = "zstd")]
+const MAX_DICTIONARY_SAMPLES: usize = 100;
+
+/// Creates a ZIP archive from a list of files, compressing each one with a Zstandard dictionary
+/// trained on the files themselves.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `files`: Regular files to include, each stored under its file name.  
+/// - `dict_size`: Maximum size of the trained dictionary, in bytes (e.g. `112_640`, the `zstd` CLI default).  
+///
+/// # Behavior
+/// - Up to 100 files, spread evenly over `files`, are read to train the dictionary with
+///   [`zstd::dict::from_samples`]. Many small files with a shared structure (JSON logs, records)
+///   compress far better this way than one by one.  
+/// - Files are then compressed in parallel, each as an independent Zstandard frame using the dictionary,
+///   at the default Zstandard level.  
+/// - ZIP has no way to attach a dictionary to an entry, so the frames are stored uncompressed under
+///   `<file name>.zst`, after a [`ZSTD_DICTIONARY_NAME`] entry holding the dictionary. Once extracted,
+///   each file decompresses with `zstd -D zstd.dict -d <file name>.zst`.  
+///
+/// # Returns
+/// An [`ArchiveStats`] whose `entries` includes the dictionary entry and whose `uncompressed_size` is
+/// the total size of the input files.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - An input is not a regular file ([`ZipoxideError::NotAFile`]).  
+/// - Training fails, e.g. because `files` is empty or holds too little data for `dict_size`.  
+/// - A file cannot be read or the archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_with_zstd_dict;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let logs: Vec<std::path::PathBuf> = std::fs::read_dir("logs")?
+///         .map(|entry| entry.map(|entry| entry.path()))
+///         .collect::<Result<_, _>>()?;
+///     let stats = create_zip_with_zstd_dict("logs.zip", logs, 112_640)?;
+///     println!("{} bytes -> {} bytes", stats.uncompressed_size, stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "zstd")]
+pub fn create_zip_with_zstd_dict(
+    output: impl AsRef<Path>,
+    files: Vec<impl AsRef<Path>>,
+    dict_size: usize,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    ensure_output_absent(output)?;
+    let entries: Vec<PendingEntry> = file_name_entries(files)?;
+
+    let step: usize = entries.len().div_ceil(MAX_DICTIONARY_SAMPLES).max(1);
+    let samples: Vec<Vec<u8>> =
+        entries.iter().step_by(step).map(|entry| fs::read(&entry.source_path)).collect::<io::Result<_>>()?;
+    let dictionary: Vec<u8> = zstd::dict::from_samples(&samples, dict_size)?;
+    drop(samples);
+
+    let prepared: zstd::dict::EncoderDictionary<'static> =
+        zstd::dict::EncoderDictionary::copy(&dictionary, zstd::DEFAULT_COMPRESSION_LEVEL);
+    let frames: Vec<(u64, Vec<u8>)> = entries
+        .par_iter()
+        .map(|entry| -> Result<(u64, Vec<u8>), ZipoxideError> {
+            let data: Vec<u8> = fs::read(&entry.source_path)?;
+            let mut compressor: zstd::bulk::Compressor<'_> =
+                zstd::bulk::Compressor::with_prepared_dictionary(&prepared)?;
+            Ok((data.len() as u64, compressor.compress(&data)?))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let zip_options: FileOptions<'static, ()> = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut zip_writer: ZipWriter<io::BufWriter<File>> = ZipWriter::new(io::BufWriter::new(File::create(output)?));
+    zip_writer.start_file(ZSTD_DICTIONARY_NAME, zip_options)?;
+    zip_writer.write_all(&dictionary)?;
+    let mut stats: ArchiveStats = ArchiveStats { entries: 1, ..Default::default() };
+    for (entry, (size, frame)) in entries.iter().zip(frames) {
+        let large_file: bool = frame.len() as u64 >= u32::MAX as u64;
+        zip_writer.start_file(format!("{}.zst", entry.name), zip_options.large_file(large_file))?;
+        zip_writer.write_all(&frame)?;
+        stats.entries += 1;
+        stats.uncompressed_size += size;
+    }
+    let mut zip_file: io::BufWriter<File> = zip_writer.finish()?;
+    zip_file.flush()?;
+    stats.archive_size = zip_file.stream_position()?;
+    Ok(stats)
+}
+
+/// Creates a ZIP archive from a list of files, storing them without compression.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `files`: Regular files to add; each is stored under its file name at the archive root.  
+///
+/// # Behavior
+/// - Every entry uses [`CompressionMethod::Stored`], so no `FileOptions` are needed. This is the fastest
+///   way to package data that is already compressed (JPEG, MP4, `.gz`), where Deflate only costs CPU
+///   time and can even grow the data.  
+/// - Unlike the other writer functions, an existing `output` is reported as an error instead of a panic.  
+///
+/// # Returns
+/// An [`ArchiveStats`] with the number of entries, the total data size and the archive size.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - An input is not a regular file ([`ZipoxideError::NotAFile`]).  
+/// - A file cannot be read or the archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_with_stored_entries;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let stats = create_zip_with_stored_entries("photos.zip", vec!["a.jpg", "b.jpg"])?;
+///     println!("Stored {} files in {} bytes", stats.entries, stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_with_stored_entries(
+    output: impl AsRef<Path>,
+    files: Vec<impl AsRef<Path>>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    ensure_output_absent(output)?;
+    let entries: Vec<PendingEntry> = file_name_entries(files)?;
+    let zip_options: FileOptions<'static, ()> = FileOptions::default().compression_method(CompressionMethod::Stored);
+    write_archive(output, entries, zip_options, &CreationOptions::default())
+}
+
+/// Builds a ZIP archive from a list of files entirely in memory and returns its bytes.
+///
+/// # Arguments
+/// - `files`: Regular files to include, each stored under its file name.  
+/// - `zip_options`: [`zip::write::FileOptions`] specifying compression method, permissions, etc.
+///
+/// # Behavior
+/// - The archive is written into a `Cursor<Vec<u8>>`, so no temporary file is created; useful for
+///   handlers that build an archive only to send it (HTTP responses, message queues).  
+/// - The whole archive is held in memory, so this is meant for archives that comfortably fit.  
+/// - [`read_zip_contents_from_bytes`](crate::read_zip_contents_from_bytes) reads the result back.  
+///
+/// # Errors
+/// Returns an error if:
+/// - An input is not a regular file ([`ZipoxideError::NotAFile`]).  
+/// - A file cannot be read or compressing it fails.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::zip_files_to_bytes;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let bytes = zip_files_to_bytes(vec!["report.pdf", "data.csv"], zip::write::SimpleFileOptions::default())?;
+///     println!("Archive is {} bytes", bytes.len());
+///     Ok(())
+/// }
+/// ```
+pub fn zip_files_to_bytes<K: FileOptionExtension + Clone + Send + Sync>(
+    files: Vec<impl AsRef<Path>>,
+    zip_options: FileOptions<'static, K>,
+) -> Result<Vec<u8>, ZipoxideError> {
+    let entries: Vec<PendingEntry> = file_name_entries(files)?;
+    let (buffer, _) = write_archive_to(Cursor::new(Vec::new()), entries, zip_options, &CreationOptions::default())?;
+    Ok(buffer.into_inner())
+}
+
+/// Creates a ZIP archive from in-memory contents keyed by entry name.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `entries`: Entry names mapped to their contents.  
+/// - `zip_options`: [`zip::write::FileOptions`] specifying compression method, permissions, etc.
+///
+/// # Behavior
+/// - Entries are written in the map's order, i.e. sorted lexicographically by name, so the same map
+///   always produces the same entry order.  
+/// - For byte-identical archives (e.g. content-addressed caching), also pin the timestamp with
+///   [`FileOptions::last_modified_time`]; the default is the current time.  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - The archive cannot be written or compressing an entry fails.  
+///
+/// # Example
+/// ```rust,no_run
+/// use std::collections::BTreeMap;
+/// use zipoxide::create_zip_from_map;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut entries = BTreeMap::new();
+///     entries.insert("config.toml".to_string(), b"debug = false\n".to_vec());
+///     entries.insert("README.txt".to_string(), b"Generated bundle\n".to_vec());
+///     let stats = create_zip_from_map("bundle.zip", entries, zip::write::SimpleFileOptions::default())?;
+///     println!("Wrote {} entries", stats.entries);
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_map(
+    output: impl AsRef<Path>,
+    entries: BTreeMap<String, Vec<u8>>,
+    zip_options: FileOptions<'static, ()>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    ensure_output_absent(output)?;
+    let mut zip_writer: ZipWriter<File> = ZipWriter::new(File::create(output)?);
+    let mut stats: ArchiveStats = ArchiveStats { entries: entries.len(), ..Default::default() };
+    for (name, contents) in entries {
+        let large_file: bool = contents.len() as u64 >= u32::MAX as u64;
+        zip_writer.start_file(name, zip_options.large_file(large_file))?;
+        zip_writer.write_all(&contents)?;
+        stats.uncompressed_size += contents.len() as u64;
+    }
+    let mut zip_file: File = zip_writer.finish()?;
+    stats.archive_size = zip_file.stream_position()?;
+    Ok(stats)
+}
+
+/// Creates a ZIP archive from newline-delimited file paths read from `paths`, e.g. the output of
+/// `find . -name '*.rs'`.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `paths`: Reader yielding one path per line.  
+/// - `creation_options`: Archive-level settings, see [`CreationOptions`].  
+///
+/// # Behavior
+/// - Blank lines and trailing `\r` are ignored; directories are skipped, since `find` lists their
+///   files on separate lines anyway.  
+/// - Without `strip_prefix`, each file is stored under the path as given, minus any root and `.`
+///   components (`./src/lib.rs` becomes `src/lib.rs`).  
+/// - Entries are compressed with the [`FileOptions`] defaults unless `creation_options.backend` is set.  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - A listed path does not exist ([`ZipoxideError::InputPathNotFound`]).  
+/// - A listed path is neither a directory nor a regular file ([`ZipoxideError::NotAFile`]).  
+/// - A path is not valid UTF-8 or does not start with `strip_prefix` ([`ZipoxideError::InvalidPath`]).  
+/// - `paths` cannot be read, or writing the archive fails.  
+///
+/// # Example
+/// ```rust,no_run
+/// use std::io::Cursor;
+/// use zipoxide::{create_zip_from_path_list, CreationOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let list = Cursor::new("src/lib.rs\nsrc/main.rs\n");
+///     let stats = create_zip_from_path_list("sources.zip", list, CreationOptions::default())?;
+///     println!("Archived {} files", stats.entries);
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_path_list(
+    output: impl AsRef<Path>,
+    paths: impl BufRead,
+    creation_options: CreationOptions,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    ensure_output_absent(output)?;
+    let mut entries: Vec<PendingEntry> = Vec::new();
+    for line in paths.lines() {
+        let line: String = line?;
+        let line: &str = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+        let path: &Path = Path::new(line);
+        if path.is_dir() {
+            continue;
+        }
+        if !path.exists() {
+            return Err(ZipoxideError::InputPathNotFound(path.to_path_buf()));
+        }
+        if !path.is_file() {
+            return Err(ZipoxideError::NotAFile(path.to_path_buf()));
+        }
+        let relative_path: &Path = match &creation_options.strip_prefix {
+            Some(prefix) => path.strip_prefix(prefix).map_err(|_| invalid_input_path(path))?,
+            None => path,
+        };
+        let name: Option<Vec<&str>> = relative_path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_str()),
+                _ => None,
+            })
+            .collect();
+        let name: String = name.ok_or_else(|| invalid_input_path(path))?.join("/");
+        entries.push(PendingEntry { source_path: path.to_path_buf(), name, kind: EntryKind::File });
+    }
+    write_archive(output, entries, SimpleFileOptions::default(), &creation_options)
+}
+
+/// Same as [`create_zip_from_path_list`], reading the path list from standard input.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::{create_zip_from_stdin, CreationOptions};
+///
+/// // find . -name '*.rs' | my-tool
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     create_zip_from_stdin("sources.zip", CreationOptions::default())?;
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_stdin(
+    output: impl AsRef<Path>,
+    creation_options: CreationOptions,
+) -> Result<ArchiveStats, ZipoxideError> {
+    create_zip_from_path_list(output, io::stdin().lock(), creation_options)
+}
+
+/// Returns the longest leading run of `pattern` components without glob metacharacters, excluding
+/// the last component, which is what the matched paths are stored relative to.
+fn glob_base(pattern: &str) -> PathBuf {
+    let components: Vec<Component<'_>> = Path::new(pattern).components().collect();
+    let literal: usize = components
+        .iter()
+        .take(components.len().saturating_sub(1))
+        .take_while(|component| !component.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .count();
+    components[..literal].iter().collect()
+}
+
+/// Creates a ZIP archive from every file matching a glob pattern such as `src/**/*.rs`.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `pattern`: Glob pattern, expanded with the [`glob`](https://docs.rs/glob/latest/glob/) crate
+///   (`*`, `?`, `[...]` and `**` for any number of directories).  
 /// - `zip_options`: [`zip::write::FileOptions`] specifying compression method, permissions, etc.
 ///
 /// # Behavior
-/// - Preserves the relative directory structure inside the archive.  
-/// - Recursively traverses subdirectories.  
-/// - Panics if the output ZIP file already exists.  
-/// - Non-UTF8 file paths will cause a runtime error.  
+/// - Entry names are relative to the pattern's literal base, the leading components before the first
+///   wildcard: `project/src/**/*.rs` stores `project/src/net/tcp.rs` as `net/tcp.rs`.  
+/// - Matched directories are skipped; use `dir/**/*` to include a directory's files.  
+/// - Files are written in the order the glob yields them (alphabetical).  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
 ///
 /// # Errors
 /// Returns an error if:
-/// - The folder path does not exist or cannot be read.  
-/// - A file cannot be opened or read.  
-/// - Writing to the ZIP archive fails.  
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - `pattern` is not a valid glob ([`ZipoxideError::Io`] with [`io::ErrorKind::InvalidInput`]), or a
+///   matched path is not valid UTF-8 ([`ZipoxideError::InvalidPath`]).  
+/// - A directory cannot be read while expanding the pattern, a file cannot be read, or writing the
+///   archive fails.  
 ///
 /// # Example
 /// ```rust,no_run
-/// use zipoxide::create_zip_from_folder;
+/// use zipoxide::create_zip_from_glob;
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     create_zip_from_folder(
-///         "archive.zip".to_string(),
-///         "my_folder".to_string(),
-///         zip::write::FileOptions::default(),
-///     )?;
+///     let stats = create_zip_from_glob("sources.zip", "src/**/*.rs", zip::write::SimpleFileOptions::default())?;
+///     println!("Archived {} files", stats.entries);
 ///     Ok(())
 /// }
 /// ```
-#[allow(unused)]
-pub fn create_zip_from_folder(
-    output_zip_path: String,
-    folder_path: String,
-    zip_options: FileOptions<'static, ()>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let output_zip_path: &Path = Path::new(&output_zip_path);
-    if output_zip_path.exists() {
-        panic!("Output zip path already exists.");
-    }
-    let folder_path: &Path = Path::new(&folder_path);
+pub fn create_zip_from_glob<K: FileOptionExtension + Clone + Send + Sync>(
+    output: impl AsRef<Path>,
+    pattern: &str,
+    zip_options: FileOptions<'static, K>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    ensure_output_absent(output)?;
+    let paths: glob::Paths =
+        glob::glob(pattern).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    let base: PathBuf = glob_base(pattern);
 
-    let zip_file: File = File::create(output_zip_path)?;
-    let mut zip_writer: ZipWriter<File> = ZipWriter::new(zip_file);
-
-    let mut directories_to_visit: Vec<PathBuf> = vec![folder_path.to_path_buf()];
-
-    while let Some(current_dir) = directories_to_visit.pop() {
-        for entry in fs::read_dir(&current_dir)? {
-            let entry: DirEntry = entry?;
-            let path: PathBuf = entry.path();
-            let relative_path: &Path = path.strip_prefix(folder_path)?;
-
-            if path.is_dir() {
-                directories_to_visit.push(path);
-            } else {
-                zip_writer.start_file(relative_path.to_str().unwrap(), zip_options)?;
-                let mut f: File = File::open(&path)?;
-                io::copy(&mut f, &mut zip_writer)?;
-            }
+    let mut entries: Vec<PendingEntry> = Vec::new();
+    for path in paths {
+        let path: PathBuf = path.map_err(io::Error::from)?;
+        if !path.is_file() {
+            continue;
         }
+        let relative_path: &Path = path.strip_prefix(&base).map_err(|_| invalid_input_path(&path))?;
+        let name: Option<Vec<&str>> = relative_path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_str()),
+                _ => None,
+            })
+            .collect();
+        let name: String = name.ok_or_else(|| invalid_input_path(&path))?.join("/");
+        entries.push(PendingEntry { source_path: path, name, kind: EntryKind::File });
     }
-
-    zip_writer.finish()?;
-    Ok(())
+    write_archive(output, entries, zip_options, &CreationOptions::default())
 }
 
 /// Creates a ZIP archive from a list of files and/or directories.
@@ -83,13 +1698,17 @@ pub fn create_zip_from_folder(
 /// - `zip_options`: [`zip::write::FileOptions`] specifying compression method, permissions, etc.
 ///
 /// # Behavior
-/// - Handles both files and directories.  
+/// - Handles both files and directories; empty directories are stored as directory entries.  
+/// - Source files are read through a 256 KiB `BufReader`, issuing fewer, larger reads for big files.  
 /// - Panics if the output ZIP file already exists.  
 /// - Non-UTF8 file paths will return an error.  
 ///
 /// # Errors
 /// Returns an error if:
-/// - Any input path does not exist or is invalid.  
+/// - An input path does not exist ([`ZipoxideError::InputPathNotFound`]), or is invalid.  
+/// - An input, or anything below an input directory, is neither a regular file nor a directory, e.g. a
+///   broken symlink or a named pipe ([`ZipoxideError::UnsupportedEntryType`]). Symlinks to files and
+///   directories are followed.  
 /// - A file cannot be opened or read.  
 /// - Writing to the ZIP archive fails.  
 ///
@@ -101,47 +1720,636 @@ pub fn create_zip_from_folder(
 ///     create_zip_from_files(
 ///         "files_archive.zip".to_string(),
 ///         vec!["file1.txt".to_string(), "dir1".to_string()],
-///         zip::write::FileOptions::default(),
+///         zip::write::SimpleFileOptions::default(),
 ///     )?;
 ///     Ok(())
 /// }
 /// ```
 #[allow(unused)]
-pub fn create_zip_from_files(
+pub fn create_zip_from_files<K: FileOptionExtension + Clone + Send + Sync>(
     output_zip_path: String,
     files_path: Vec<String>,
-    zip_options: FileOptions<'static, ()>,
+    zip_options: FileOptions<'static, K>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let output_zip_path: &Path = Path::new(&output_zip_path);
-    if output_zip_path.exists() {
+    if Path::new(&output_zip_path).exists() {
         panic!("Output zip path already exists.");
     }
-    let zip_file: File = File::create(output_zip_path)?;
-    let mut zip_writer: ZipWriter<File> = ZipWriter::new(zip_file);
+    create_zip_from_files_with_options(output_zip_path, files_path, zip_options, CreationOptions::default())
+}
+
+/// Creates a ZIP archive containing a single file, stored under its file name.
+///
+/// # Arguments
+/// - `output_zip_path`: Path where the resulting ZIP archive will be created.  
+/// - `file_path`: The regular file to compress.  
+/// - `zip_options`: [`zip::write::FileOptions`] specifying compression method, permissions, etc.
+///
+/// # Behavior
+/// - Thin wrapper over [`create_zip_from_files`] for the one-file case.  
+/// - Panics if the output ZIP file already exists.  
+///
+/// # Errors
+/// Returns an error if:
+/// - `file_path` is not a regular file, e.g. a directory or a missing path ([`ZipoxideError::NotAFile`]).  
+/// - The file cannot be opened or read.  
+/// - Writing to the ZIP archive fails.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_from_file;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     create_zip_from_file(
+///         "report.zip".to_string(),
+///         "report.pdf".to_string(),
+///         zip::write::SimpleFileOptions::default(),
+///     )?;
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_file<K: FileOptionExtension + Clone + Send + Sync>(
+    output_zip_path: String,
+    file_path: String,
+    zip_options: FileOptions<'static, K>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !Path::new(&file_path).is_file() {
+        return Err(ZipoxideError::NotAFile(PathBuf::from(file_path)).into());
+    }
+    create_zip_from_files(output_zip_path, vec![file_path], zip_options)
+}
+
+/// Creates a ZIP archive from a list of files and/or directories, storing each one relative to `base_dir`
+/// instead of flattening it to its file name.
+///
+/// # Arguments
+/// - `output_zip_path`: Path where the resulting ZIP archive will be created.  
+/// - `files_path`: List of file or directory paths under `base_dir`.  
+/// - `base_dir`: Directory the stored paths are relative to; `base/a/b.txt` is stored as `a/b.txt`.  
+/// - `zip_options`: [`zip::write::FileOptions`] specifying compression method, permissions, etc.
+///
+/// # Behavior
+/// - Equivalent to [`create_zip_from_files_with_options`] with [`CreationOptions::strip_prefix`] set to `base_dir`.  
+/// - Avoids collisions between same-named files from different directories.  
+///
+/// # Errors
+/// Same as [`create_zip_from_files_with_options`], including [`ZipoxideError::InvalidPath`] if an input path
+/// is not under `base_dir`.
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::create_zip_from_files_keep_paths;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Stored as `b/c.txt` and `d/c.txt`
+///     create_zip_from_files_keep_paths(
+///         "files_archive.zip".to_string(),
+///         vec!["a/b/c.txt".to_string(), "a/d/c.txt".to_string()],
+///         "a",
+///         zip::write::SimpleFileOptions::default(),
+///     )?;
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_files_keep_paths<K: FileOptionExtension + Clone + Send + Sync>(
+    output_zip_path: String,
+    files_path: Vec<String>,
+    base_dir: &str,
+    zip_options: FileOptions<'static, K>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    create_zip_from_files_with_options(
+        output_zip_path,
+        files_path,
+        zip_options,
+        CreationOptions { strip_prefix: Some(PathBuf::from(base_dir)), ..Default::default() },
+    )
+}
 
+/// Same as [`create_zip_from_files`], with archive-level behaviour controlled by [`CreationOptions`].
+///
+/// # Behavior
+/// - Without `strip_prefix`, each input is rooted at its own file name (`a/b/c.txt` is stored as `c.txt`).  
+/// - With `strip_prefix`, entry names are the input paths with that prefix removed
+///   (`a/b/c.txt` with prefix `a` is stored as `b/c.txt`).  
+/// - Unlike [`create_zip_from_files`], an existing output ZIP file is reported as an error rather than a panic.  
+///
+/// # Errors
+/// In addition to the errors of [`create_zip_from_files`], returns [`ZipoxideError::Io`] with
+/// [`io::ErrorKind::AlreadyExists`] if `output_zip_path` already exists, and [`ZipoxideError::InvalidPath`]
+/// if a file path does not start with `strip_prefix`.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::path::PathBuf;
+/// use zipoxide::{create_zip_from_files_with_options, CreationOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Stored as `src/main.rs` and `src/lib.rs`
+///     create_zip_from_files_with_options(
+///         "sources.zip".to_string(),
+///         vec!["project/src/main.rs".to_string(), "project/src/lib.rs".to_string()],
+///         zip::write::SimpleFileOptions::default(),
+///         CreationOptions { strip_prefix: Some(PathBuf::from("project")), ..Default::default() },
+///     )?;
+///     Ok(())
+/// }
+/// ```
+#[allow(unused)]
+pub fn create_zip_from_files_with_options<K: FileOptionExtension + Clone + Send + Sync>(
+    output_zip_path: String,
+    files_path: Vec<String>,
+    zip_options: FileOptions<'static, K>,
+    creation_options: CreationOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output_zip_path: &Path = Path::new(&output_zip_path);
+    ensure_output_absent(output_zip_path)?;
+    let entries: Vec<PendingEntry> = file_entries(files_path.iter().map(Path::new), &creation_options)?;
+    write_archive(output_zip_path, entries, zip_options, &creation_options)?;
+    Ok(())
+}
+
+/// Collects the entries for the files and directories in `paths`, each rooted at its own file name
+/// unless `creation_options.strip_prefix` is set. Empty directories become directory entries.
+fn file_entries<'a>(
+    paths: impl Iterator<Item = &'a Path>,
+    creation_options: &CreationOptions,
+) -> Result<Vec<PendingEntry>, ZipoxideError> {
+    let mut entries: Vec<PendingEntry> = Vec::new();
     let mut stack: Vec<(PathBuf, PathBuf)> = Vec::new();
 
-    for file_path_str in files_path.iter() {
-        let path: PathBuf = PathBuf::from(file_path_str);
-        let relative_path: std::ffi::OsString = path.file_name().ok_or("Invalid file name")?.to_owned();
-        stack.push((path, PathBuf::from(relative_path)));
+    for path in paths {
+        // A broken symlink exists itself, and is reported as unsupported once the walk reaches it.
+        if fs::symlink_metadata(path).is_err_and(|e| e.kind() == io::ErrorKind::NotFound) {
+            return Err(ZipoxideError::InputPathNotFound(path.to_path_buf()));
+        }
+        let relative_path: std::ffi::OsString = path.file_name().ok_or_else(|| invalid_input_path(path))?.to_owned();
+        stack.push((path.to_path_buf(), PathBuf::from(relative_path)));
     }
 
     while let Some((full_path, relative_path)) = stack.pop() {
         if full_path.is_dir() {
+            let mut is_empty: bool = true;
             for entry in fs::read_dir(&full_path)? {
                 let entry: DirEntry = entry?;
                 let entry_path: PathBuf = entry.path();
                 let entry_relative_path: PathBuf = relative_path.join(entry.file_name());
                 stack.push((entry_path, entry_relative_path));
+                is_empty = false;
+            }
+            if is_empty {
+                let name: String = entry_name(&full_path, &relative_path, creation_options)?;
+                entries.push(PendingEntry { source_path: full_path, name, kind: EntryKind::Directory });
             }
         } else if full_path.is_file() {
-            zip_writer.start_file(relative_path.to_str().unwrap(), zip_options)?;
-            let mut file: File = File::open(&full_path)?;
-            io::copy(&mut file, &mut zip_writer)?;
+            let name: String = entry_name(&full_path, &relative_path, creation_options)?;
+            entries.push(PendingEntry { source_path: full_path, name, kind: EntryKind::File });
+        } else {
+            return Err(ZipoxideError::UnsupportedEntryType(full_path));
+        }
+    }
+    Ok(entries)
+}
+
+/// Creates a ZIP archive from files and folders like [`create_zip_from_files`], reading sources and
+/// writing the archive through buffers of `buf_size` bytes.
+///
+/// # Arguments
+/// - `output`: Path where the resulting ZIP archive will be created.  
+/// - `files`: Files or directories to add; directories are added recursively.  
+/// - `zip_options`: Compression method, level, permissions, etc. applied to every entry.  
+/// - `buf_size`: Size in bytes of the read buffer around each source file and of the write buffer
+///   around the archive.  
+///
+/// # Behavior
+/// - Same as [`create_zip_from_files_with_options`] with [`CreationOptions::buffer_size`] set: each
+///   file is streamed through the compressor in `buf_size` chunks, so memory use stays bounded by
+///   the buffers however large the file is. Larger buffers mean fewer system calls, smaller ones less
+///   memory per file.  
+/// - Each input is rooted at its own file name, as for [`create_zip_from_files`].  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - `buf_size` is zero ([`ZipoxideError::Io`] with [`io::ErrorKind::InvalidInput`]).  
+/// - An input does not exist ([`ZipoxideError::InputPathNotFound`]).  
+/// - An input has no file name or is not valid UTF-8 ([`ZipoxideError::InvalidPath`]).  
+/// - An input is neither a regular file nor a directory ([`ZipoxideError::UnsupportedEntryType`]).  
+/// - A file cannot be read, or the archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zip::write::SimpleFileOptions;
+/// use zipoxide::create_zip_from_files_buffered;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Stream a disk image through 4 MiB buffers.
+///     let options = SimpleFileOptions::default();
+///     let stats = create_zip_from_files_buffered("image.zip", vec!["disk.img"], options, 4 << 20)?;
+///     println!("{} bytes compressed to {}", stats.uncompressed_size, stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+pub fn create_zip_from_files_buffered<K: FileOptionExtension + Clone + Send + Sync>(
+    output: impl AsRef<Path>,
+    files: Vec<impl AsRef<Path>>,
+    zip_options: FileOptions<'static, K>,
+    buf_size: usize,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    ensure_output_absent(output)?;
+    let creation_options: CreationOptions = CreationOptions { buffer_size: Some(buf_size), ..Default::default() };
+    let entries: Vec<PendingEntry> = file_entries(files.iter().map(AsRef::as_ref), &creation_options)?;
+    write_archive(output, entries, zip_options, &creation_options)
+}
+
+/// Room left per entry for fields the writer may add when copying it: a data descriptor and Zip64 extra fields.
+const VOLUME_ENTRY_SLACK: u64 = 64;
+
+/// Upper bound on the bytes a raw-copied entry adds to a volume: local header, compressed data and
+/// central-directory record.
+fn volume_entry_cost<R: io::Read>(entry: &ZipFile<'_, R>) -> u64 {
+    let name_len: u64 = entry.name_raw().len() as u64;
+    let extra_len: u64 = entry.extra_data().map_or(0, <[u8]>::len) as u64;
+    entry.compressed_size()
+        + ENTRY_OVERHEAD
+        + 2 * (name_len + extra_len)
+        + entry.comment().len() as u64
+        + VOLUME_ENTRY_SLACK
+}
+
+/// Splits a ZIP archive into standalone volumes of at most `volume_size` bytes each.
+///
+/// # Arguments
+/// - `input`: Path to the archive to split.  
+/// - `output_dir`: Directory the volumes are written to; created if missing.  
+/// - `volume_size`: Maximum size of each volume, in bytes.  
+/// - `password`: When set, every entry is decrypted and checked against its CRC32 before anything
+///   is written, so a wrong password or a corrupt entry is caught up front.  
+///
+/// # Behavior
+/// - Volumes are named after the input, `<stem>.001.zip`, `<stem>.002.zip`, and so on.  
+/// - Entries are copied in their original order without recompression (encrypted entries stay
+///   encrypted), filling each volume before starting the next. Each volume is a valid ZIP archive
+///   on its own, unlike the multi-disk spanning of the ZIP specification.  
+/// - Volume sizes are planned from a conservative per-entry estimate, so volumes may be somewhat
+///   smaller than `volume_size`, never larger.  
+/// - An empty archive produces a single empty volume.  
+/// - [`merge_split_zips`] puts the volumes back together.  
+///
+/// # Returns
+/// The paths of the written volumes, in order.
+///
+/// # Errors
+/// Returns an error if:
+/// - `input` cannot be opened or parsed.  
+/// - An entry is too large to fit in a volume on its own ([`ZipoxideError::Io`] with
+///   [`io::ErrorKind::InvalidInput`]).  
+/// - `password` is set and an entry cannot be decrypted or fails its CRC32 check.  
+/// - A volume already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]) or cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::split_zip;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Fit under a 25 MB attachment limit.
+///     let volumes = split_zip("backup.zip", "upload", 25_000_000, None)?;
+///     println!("Wrote {} volumes", volumes.len());
+///     Ok(())
+/// }
+/// ```
+pub fn split_zip(
+    input: impl AsRef<Path>,
+    output_dir: impl AsRef<Path>,
+    volume_size: u64,
+    password: Option<&str>,
+) -> Result<Vec<PathBuf>, ZipoxideError> {
+    let input: &Path = input.as_ref();
+    let output_dir: &Path = output_dir.as_ref();
+    let mut source: ZipArchive<BufReader<File>> = ZipArchive::new(BufReader::new(File::open(input)?))?;
+    let stem: &str = input.file_stem().and_then(|stem| stem.to_str()).ok_or_else(|| invalid_input_path(input))?;
+
+    if let Some(password) = password {
+        for index in 0..source.len() {
+            let mut entry: ZipFile<'_, BufReader<File>> = open_entry(&mut source, index, Some(password))?;
+            io::copy(&mut entry, &mut io::sink())?;
+        }
+    }
+
+    fs::create_dir_all(output_dir)?;
+    let volume_path = |number: usize| -> Result<PathBuf, ZipoxideError> {
+        let volume_path: PathBuf = output_dir.join(format!("{stem}.{number:03}.zip"));
+        ensure_output_absent(&volume_path)?;
+        Ok(volume_path)
+    };
+    let mut volumes: Vec<PathBuf> = Vec::new();
+    // The volume being filled and the bytes it is planned to take so far.
+    let mut current: Option<(ZipWriter<File>, u64)> = None;
+    for index in 0..source.len() {
+        let entry: ZipFile<'_, BufReader<File>> = source.by_index_raw(index)?;
+        let cost: u64 = volume_entry_cost(&entry);
+        if END_OF_CENTRAL_DIRECTORY_SIZE + cost > volume_size {
+            let message: String = format!("entry {} does not fit in a volume of {volume_size} bytes", entry.name());
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        }
+        let (zip_writer, used) = match current.take() {
+            Some((zip_writer, used)) if used + cost <= volume_size => (zip_writer, used),
+            full => {
+                if let Some((zip_writer, _)) = full {
+                    zip_writer.finish()?;
+                }
+                volumes.push(volume_path(volumes.len() + 1)?);
+                (ZipWriter::new(File::create(&volumes[volumes.len() - 1])?), END_OF_CENTRAL_DIRECTORY_SIZE)
+            }
+        };
+        let zip_writer: &mut ZipWriter<File> = &mut current.insert((zip_writer, used + cost)).0;
+        zip_writer.raw_copy_file(entry)?;
+    }
+    match current {
+        Some((zip_writer, _)) => {
+            zip_writer.finish()?;
+        }
+        None => {
+            volumes.push(volume_path(1)?);
+            ZipWriter::new(File::create(&volumes[0])?).finish()?;
+        }
+    }
+    Ok(volumes)
+}
+
+/// Creates ZIP archives from a folder like [`create_zip_from_folder`], spreading its files over as many
+/// archives as needed to keep the input size of each under `max_bytes`.
+///
+/// # Arguments
+/// - `output_prefix`: Path prefix of the archives; `_000.zip`, `_001.zip`, ... is appended to it.  
+/// - `folder`: Root folder whose contents will be compressed into the archives.  
+/// - `max_bytes`: Maximum total uncompressed size of the files in each archive, in bytes.  
+/// - `zip_options`: Compression method, level, permissions, etc. applied to every entry.  
+///
+/// # Behavior
+/// - Files are taken in the same sorted order as [`create_zip_from_folder`] and added to the current
+///   archive until the next one would take it over `max_bytes`; that file starts a new archive. A file
+///   is never split across archives.  
+/// - The budget counts input bytes, not compressed ones, so each archive usually ends up well under
+///   `max_bytes` on disk, but incompressible data (or `Stored`) can exceed it by the ZIP headers.  
+/// - Entry names are relative to `folder` in every archive, so extracting all of them into the same
+///   directory restores the folder. Empty directories take no budget.  
+/// - An empty folder produces a single empty archive.  
+///
+/// # Returns
+/// The paths of the written archives, in order.
+///
+/// # Errors
+/// Returns an error if:
+/// - `folder` does not exist or is not a directory ([`ZipoxideError::NotADirectory`]).  
+/// - A file is larger than `max_bytes` on its own ([`ZipoxideError::Io`] with
+///   [`io::ErrorKind::InvalidInput`]).  
+/// - One of the archives already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]);
+///   checked before any archive is written.  
+/// - A path is not valid UTF-8, a file cannot be read, or an archive cannot be written.  
+///
+/// # Example
+/// ```rust,no_run
+/// use zip::write::SimpleFileOptions;
+/// use zipoxide::create_split_zips_from_folder;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Fit under a 25 MB upload limit: photos_000.zip, photos_001.zip, ...
+///     let zip_options = SimpleFileOptions::default();
+///     let archives = create_split_zips_from_folder("upload/photos", "photos", 25_000_000, zip_options)?;
+///     println!("Wrote {} archives", archives.len());
+///     Ok(())
+/// }
+/// ```
+pub fn create_split_zips_from_folder<K: FileOptionExtension + Clone + Send + Sync>(
+    output_prefix: impl AsRef<Path>,
+    folder: impl AsRef<Path>,
+    max_bytes: u64,
+    zip_options: FileOptions<'static, K>,
+) -> Result<Vec<PathBuf>, ZipoxideError> {
+    let output_prefix: &Path = output_prefix.as_ref();
+    let folder: &Path = folder.as_ref();
+    if !folder.is_dir() {
+        return Err(ZipoxideError::NotADirectory(folder.to_path_buf()));
+    }
+
+    // Each group holds the entries of one archive and their total input size.
+    let mut groups: Vec<(Vec<PendingEntry>, u64)> = vec![(Vec::new(), 0)];
+    for entry in folder_entries(folder)? {
+        let size: u64 = match entry.kind {
+            EntryKind::File => fs::metadata(&entry.source_path)?.len(),
+            EntryKind::Directory => 0,
+        };
+        if size > max_bytes {
+            let path: &Path = &entry.source_path;
+            let message: String = format!("{} does not fit in an archive of {max_bytes} bytes", path.display());
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+        }
+        match groups.last_mut() {
+            Some((group, used)) if *used + size <= max_bytes => {
+                group.push(entry);
+                *used += size;
+            }
+            _ => groups.push((vec![entry], size)),
         }
     }
 
-    zip_writer.finish()?;
+    let archives: Vec<PathBuf> = (0..groups.len())
+        .map(|number| {
+            let mut archive: OsString = output_prefix.as_os_str().to_owned();
+            archive.push(format!("_{number:03}.zip"));
+            PathBuf::from(archive)
+        })
+        .collect();
+    for archive in &archives {
+        ensure_output_absent(archive)?;
+    }
+    for ((entries, _), archive) in groups.into_iter().zip(&archives) {
+        write_archive(archive, entries, zip_options.clone(), &CreationOptions::default())?;
+    }
+    Ok(archives)
+}
+
+/// Reassembles volumes written by [`split_zip`] into a single archive.
+///
+/// # Arguments
+/// - `output`: Path where the merged archive will be created.  
+/// - `volumes`: Volume paths, in order (as returned by [`split_zip`]).  
+///
+/// # Behavior
+/// - Entries are copied without recompression, volume by volume, so the merged archive lists them in
+///   the original order with the original compressed data.  
+/// - Works for any standalone archives, not only volumes of one split.  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the merged archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - A volume cannot be opened or parsed.  
+/// - Two volumes contain an entry with the same name ([`ZipoxideError::Zip`]).  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::merge_split_zips;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     merge_split_zips("backup.zip", vec!["upload/backup.001.zip", "upload/backup.002.zip"])?;
+///     Ok(())
+/// }
+/// ```
+pub fn merge_split_zips(
+    output: impl AsRef<Path>,
+    volumes: Vec<impl AsRef<Path>>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    ensure_output_absent(output)?;
+    let mut zip_writer: ZipWriter<File> = ZipWriter::new(File::create(output)?);
+    let mut stats: ArchiveStats = ArchiveStats::default();
+    for volume in volumes {
+        let mut source: ZipArchive<BufReader<File>> = ZipArchive::new(BufReader::new(File::open(volume.as_ref())?))?;
+        for index in 0..source.len() {
+            let entry: ZipFile<'_, BufReader<File>> = source.by_index_raw(index)?;
+            stats.entries += 1;
+            stats.uncompressed_size += entry.size();
+            zip_writer.raw_copy_file(entry)?;
+        }
+    }
+    let mut zip_file: File = zip_writer.finish()?;
+    stats.archive_size = zip_file.stream_position()?;
+    Ok(stats)
+}
+
+/// Rewrites an archive with every file entry compressed with a different method, e.g. to deflate a
+/// `Stored` archive or to store one that will be compressed again as a whole.
+///
+/// # Arguments
+/// - `input`: Path to the archive to repackage.  
+/// - `output`: Path where the recompressed archive will be created.  
+/// - `method`: Compression method for every file entry.  
+/// - `level`: Compression level for `method`, or `None` for its default. Ignored for
+///   [`CompressionMethod::Stored`].  
+///
+/// # Behavior
+/// - Entries are decompressed one at a time and streamed into `output`, in their original order.  
+/// - Names, modification times and Unix permissions are kept; directory entries are copied as they
+///   are. Extra fields and entry comments are not carried over.  
+/// - Entries of 4 GiB or more are written as Zip64.  
+///
+/// # Returns
+/// An [`ArchiveStats`] describing the written archive.
+///
+/// # Errors
+/// Returns an error if:
+/// - `input` cannot be opened or parsed.  
+/// - `output` already exists ([`ZipoxideError::Io`] with [`io::ErrorKind::AlreadyExists`]).  
+/// - An entry is encrypted, fails to decompress or fails its CRC32 check.  
+/// - `method` or `level` is not supported by this build of the `zip` crate ([`ZipoxideError::Zip`]).  
+///
+/// # Example
+/// ```rust,no_run
+/// use zip::CompressionMethod;
+/// use zipoxide::recompress_zip;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let stats = recompress_zip("stored.zip", "deflated.zip", CompressionMethod::Deflated, Some(9))?;
+///     println!("{} bytes after recompression", stats.archive_size);
+///     Ok(())
+/// }
+/// ```
+pub fn recompress_zip(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    method: CompressionMethod,
+    level: Option<i64>,
+) -> Result<ArchiveStats, ZipoxideError> {
+    let output: &Path = output.as_ref();
+    let mut source: ZipArchive<BufReader<File>> = ZipArchive::new(BufReader::new(File::open(input.as_ref())?))?;
+    ensure_output_absent(output)?;
+    let level: Option<i64> = if method == CompressionMethod::Stored { None } else { level };
+    let mut zip_writer: ZipWriter<File> = ZipWriter::new(File::create(output)?);
+    let mut stats: ArchiveStats = ArchiveStats { entries: source.len(), ..Default::default() };
+    for index in 0..source.len() {
+        let mut entry: ZipFile<'_, BufReader<File>> = source.by_index(index)?;
+        let mut zip_options: SimpleFileOptions = SimpleFileOptions::default();
+        if let Some(last_modified) = entry.last_modified() {
+            zip_options = zip_options.last_modified_time(last_modified);
+        }
+        if let Some(mode) = entry.unix_mode() {
+            zip_options = zip_options.unix_permissions(mode);
+        }
+        if entry.is_dir() {
+            zip_writer.add_directory(entry.name(), zip_options)?;
+            continue;
+        }
+        let zip_options: SimpleFileOptions = zip_options
+            .compression_method(method)
+            .compression_level(level)
+            .large_file(entry.size() >= u32::MAX as u64);
+        zip_writer.start_file(entry.name(), zip_options)?;
+        stats.uncompressed_size += io::copy(&mut entry, &mut zip_writer)?;
+    }
+    let mut zip_file: File = zip_writer.finish()?;
+    stats.archive_size = zip_file.stream_position()?;
+    Ok(stats)
+}
+
+/// Replaces the archive-level comment of an existing ZIP file in place.
+///
+/// # Arguments
+/// - `zip_path`: Path to the ZIP file to update.  
+/// - `comment`: New comment; an empty string removes the comment.  
+///
+/// # Behavior
+/// - The comment is the last field of the end-of-central-directory record, so only that record is
+///   rewritten and the file is truncated or grown to fit the new comment. Entries are neither read nor
+///   recompressed, making this cheap even for very large archives.  
+/// - [`read_zip_archive_comment`](crate::read_zip_archive_comment) reads the comment back.  
+///
+/// # Errors
+/// Returns an error if:
+/// - `comment` is longer than the 65,535 bytes the ZIP format allows ([`ZipoxideError::Io`] with
+///   [`io::ErrorKind::InvalidInput`]); the file is left untouched.  
+/// - The file cannot be opened for writing or is not a valid ZIP archive.  
+/// - Data follows the end-of-central-directory record, so it cannot be located at the end of the file
+///   ([`ZipoxideError::Io`] with [`io::ErrorKind::InvalidData`]).  
+///
+/// # Example
+/// ```rust,no_run
+/// use zipoxide::set_zip_archive_comment;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     set_zip_archive_comment("release.zip", "v1.2.0, built from 4f2a9c1")?;
+///     Ok(())
+/// }
+/// ```
+pub fn set_zip_archive_comment(zip_path: impl AsRef<Path>, comment: &str) -> Result<(), ZipoxideError> {
+    let comment_length: u16 = u16::try_from(comment.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "archive comment exceeds 65535 bytes"))?;
+    let mut zip_file: File = fs::OpenOptions::new().read(true).write(true).open(zip_path.as_ref())?;
+    let old_comment_length: u64 = ZipArchive::new(BufReader::new(&zip_file))?.comment().len() as u64;
+
+    let misplaced =
+        || io::Error::new(io::ErrorKind::InvalidData, "end of central directory record is not at the end of the file");
+    let record_offset: u64 = zip_file
+        .metadata()?
+        .len()
+        .checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE + old_comment_length)
+        .ok_or_else(misplaced)?;
+    let mut signature: [u8; 4] = [0; 4];
+    zip_file.seek(io::SeekFrom::Start(record_offset))?;
+    zip_file.read_exact(&mut signature)?;
+    if signature != END_OF_CENTRAL_DIRECTORY_SIGNATURE {
+        return Err(misplaced().into());
+    }
+
+    // The comment length is the record's last fixed field, right before the comment itself.
+    zip_file.seek(io::SeekFrom::Start(record_offset + END_OF_CENTRAL_DIRECTORY_SIZE - 2))?;
+    zip_file.write_all(&comment_length.to_le_bytes())?;
+    zip_file.write_all(comment.as_bytes())?;
+    zip_file.set_len(record_offset + END_OF_CENTRAL_DIRECTORY_SIZE + u64::from(comment_length))?;
     Ok(())
 }
@@ -2,11 +2,80 @@
 mod tests {
     use zipoxide::{
         read_zip_contents_into_buffer, create_zip_from_folder, create_zip_from_files, extract_zip,
+        create_zip_from_folder_with_options, create_zip_from_files_with_options, CreationOptions,
+        ZipoxideError, peek_entry, list_zip_entries, EntryKind,
+        CompressionBackend, read_zip_contents_lenient, create_zip_from_file,
+        create_zip_from_files_keep_paths,
+        read_zip_contents_bounded,
+        is_zip_file,
+        ExtractionOptions, extract_zip_with_options,
+        zip_file_size_estimate,
+        extract_zip_to,
+        read_zip_entry_range,
+        create_zip_with_stored_entries,
+        read_zip_contents_as_pathmap,
+        create_zip_from_path_list,
+        zip_contains,
+        create_zip_from_folder_with_mode,
+        ZipOxideConfig,
+        create_zip_from_glob,
+        total_uncompressed_size,
+        ArchiveFormat, extract_streaming,
+        split_zip, merge_split_zips,
+        list_zip_entries_with_encoding,
+        zip_files_to_bytes, read_zip_contents_from_bytes,
+        ZipReader,
+        read_zip_contents_ordered,
+        zip_entry_names,
+        create_zip_from_folder_level,
+        read_zip_entries_chunked,
+        extract_zip_since,
+        recover_partial_zip,
+        create_incremental_zip,
+        extract_zip_map,
+        read_zip_archive_comment,
+        EntrySink, read_zip_into_sink,
+        set_zip_archive_comment,
+        zip_entry_count,
+        read_zip_contents_with_options,
+        create_zip_from_folder_parallel,
+        encryption_summary,
+        ZipEntry, ZipEntryHandle, ArchiveStats, ExtractionReport,
+        create_zip_from_files_buffered,
+        create_zip_from_map,
+        open_zip,
+        OpenArchive,
+        extract_zip_in_place, OverwritePolicy,
+        extract_zip_routed,
+        validate_entries, ValidationReport,
+        read_zip_range,
+        is_valid_zip,
+        read_zip_entry_as_string,
+        extract_zips,
+        ErrorPolicy,
+        create_split_zips_from_folder,
     };
-    use std::fs::{self, File};
-    use std::io::Write;
-    use std::path::PathBuf;
+    #[cfg(any(feature = "zstd", feature = "deflate", feature = "deflate-zlib"))]
+    use zipoxide::read_zip_entry_raw;
+    #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+    use zipoxide::{create_zip_from_folder_smart, recompress_zip};
+    #[cfg(feature = "zstd")]
+    use zipoxide::{ZSTD_DICTIONARY_NAME, create_zip_with_zstd_dict, create_zip_zstd_from_folder};
+    #[cfg(feature = "gzip")]
+    use zipoxide::gzip::{gunzip_file, gzip_file};
+    #[cfg(feature = "notify")]
+    use zipoxide::{WatchHandle, watch_and_zip};
+    #[cfg(feature = "tar")]
+    use zipoxide::{create_tar_from_folder, extract_tar, extract_zip_to_tar_gz, read_tar_contents_into_buffer};
+    use std::fs::{self, File, FileTimes};
+    use std::collections::{BTreeMap, HashMap};
+    use std::io::{Read, Write};
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, SystemTime};
     use tempfile::tempdir;
+    use zip::result::ZipError;
     use zip::write::FileOptions;
 
     fn default_options() -> FileOptions<'static, ()> {
@@ -201,6 +270,39 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_create_zip_with_options_rejects_existing_zip() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("folder");
+        fs::create_dir(&folder).unwrap();
+        let file = folder.join("file.txt");
+        fs::write(&file, b"data").unwrap();
+        let zip_path = dir.path().join("exists.zip");
+        fs::write(&zip_path, b"keep me").unwrap();
+        let is_already_exists = |error: Box<dyn std::error::Error>| {
+            matches!(error.downcast_ref(), Some(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists)
+        };
+
+        let error = create_zip_from_folder_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+            CreationOptions::default(),
+        )
+        .unwrap_err();
+        assert!(is_already_exists(error));
+
+        let error = create_zip_from_files_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            vec![file.to_str().unwrap().to_string()],
+            default_options(),
+            CreationOptions::default(),
+        )
+        .unwrap_err();
+        assert!(is_already_exists(error));
+        assert_eq!(fs::read(&zip_path).unwrap(), b"keep me");
+    }
+
     #[test]
     fn test_read_and_extract_password_protected_zip() {
         let project_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -230,5 +332,2871 @@ mod tests {
         assert_eq!(content, b"This is password protection read test.");
     }
 
-    
+    #[test]
+    fn test_create_zip_from_folder_with_strip_prefix() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("my_folder");
+        fs::create_dir_all(folder.join("sub")).unwrap();
+        fs::write(folder.join("a.txt"), b"Hello").unwrap();
+        fs::write(folder.join("sub").join("b.txt"), b"World").unwrap();
+
+        let zip_path = dir.path().join("prefixed.zip");
+        create_zip_from_folder_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+            CreationOptions { strip_prefix: Some(dir.path().to_path_buf()), ..Default::default() },
+        )
+        .unwrap();
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["my_folder/a.txt"], b"Hello");
+        assert_eq!(contents["my_folder/sub/b.txt"], b"World");
+    }
+
+    #[test]
+    fn test_create_zip_from_files_with_strip_prefix() {
+        let dir = tempdir().unwrap();
+        let subdir = dir.path().join("src");
+        fs::create_dir(&subdir).unwrap();
+        fs::write(subdir.join("main.rs"), b"fn main() {}").unwrap();
+        fs::write(dir.path().join("Cargo.toml"), b"[package]").unwrap();
+
+        let zip_path = dir.path().join("project.zip");
+        create_zip_from_files_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            vec![
+                subdir.to_str().unwrap().to_string(),
+                dir.path().join("Cargo.toml").to_str().unwrap().to_string(),
+            ],
+            default_options(),
+            CreationOptions { strip_prefix: Some(dir.path().to_path_buf()), ..Default::default() },
+        )
+        .unwrap();
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["src/main.rs"], b"fn main() {}");
+        assert_eq!(contents["Cargo.toml"], b"[package]");
+    }
+
+    #[test]
+    fn test_create_zip_from_files_with_strip_prefix_outside_input() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("file.txt");
+        fs::write(&file, b"data").unwrap();
+
+        let zip_path = dir.path().join("outside.zip");
+        let result = create_zip_from_files_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            vec![file.to_str().unwrap().to_string()],
+            default_options(),
+            CreationOptions { strip_prefix: Some(PathBuf::from("/definitely/not/a/prefix")), ..Default::default() },
+        );
+        let error = result.unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(ZipoxideError::InvalidPath(path)) if *path == file), "{error}");
+    }
+
+    #[test]
+    fn test_read_and_extract_aes256_protected_zip() {
+        let project_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let zip_path = project_root.join("tests/aes256.zip");
+        let password = "test";
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), Some(password.to_string()))
+            .expect("Failed to read AES-256 protected ZIP");
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents["aes.txt"], b"This is AES-256 protection read test.");
+
+        let temp_dir = tempdir().unwrap();
+        extract_zip(
+            zip_path.to_str().unwrap().to_string(),
+            temp_dir.path().to_str().unwrap().to_string(),
+            Some(password.to_string()),
+        )
+        .expect("Failed to extract AES-256 protected ZIP");
+        let content = fs::read(temp_dir.path().join("aes.txt")).unwrap();
+        assert_eq!(content, b"This is AES-256 protection read test.");
+    }
+
+    #[test]
+    fn test_aes_unsupported_error_is_surfaced() {
+        let error = ZipoxideError::from(ZipError::UnsupportedArchive(
+            "AES encrypted files cannot be decrypted without the aes-crypto feature.",
+        ));
+        assert!(matches!(error, ZipoxideError::AesUnsupported));
+
+        let error = ZipoxideError::from(ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED));
+        assert!(matches!(error, ZipoxideError::Zip(_)));
+    }
+
+    #[test]
+    fn test_peek_entry_reads_only_header() {
+        let dir = tempdir().unwrap();
+        let png_signature: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        let mut image = png_signature.to_vec();
+        image.extend(std::iter::repeat_n(0xAB, 64 * 1024));
+        let file = dir.path().join("image.png");
+        fs::write(&file, &image).unwrap();
+
+        let zip_path = dir.path().join("images.zip");
+        create_zip_from_files(
+            zip_path.to_str().unwrap().to_string(),
+            vec![file.to_str().unwrap().to_string()],
+            default_options(),
+        )
+        .unwrap();
+
+        let header = peek_entry(&zip_path, "image.png", 4, None).unwrap();
+        assert_eq!(header, &png_signature[..4]);
+
+        let missing = peek_entry(&zip_path, "missing.png", 4, None);
+        assert!(matches!(missing, Err(ZipoxideError::EntryNotFound(name)) if name == "missing.png"));
+    }
+
+    #[test]
+    #[cfg(feature = "manifest")]
+    fn test_create_zip_from_folder_embeds_manifest() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("deploy");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("app.bin"), b"binary").unwrap();
+        fs::write(folder.join("config.toml"), b"key = 1").unwrap();
+
+        let zip_path = dir.path().join("deploy.zip");
+        create_zip_from_folder_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+            CreationOptions { embed_manifest: true, ..Default::default() },
+        )
+        .unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 3);
+        assert_eq!(archive.by_index(2).unwrap().name(), "MANIFEST.json");
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        let manifest: serde_json::Value = serde_json::from_slice(&contents["MANIFEST.json"]).unwrap();
+        let files = manifest["files"].as_array().unwrap();
+        assert_eq!(files.len(), 2);
+        for file in files {
+            let name = file["name"].as_str().unwrap();
+            assert_eq!(file["size"].as_u64().unwrap(), contents[name].len() as u64);
+            assert_eq!(file["crc32"].as_u64().unwrap(), crc32fast::hash(&contents[name]) as u64);
+        }
+    }
+
+    #[test]
+    fn test_list_zip_entries_distinguishes_directories() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("tree");
+        fs::create_dir_all(folder.join("empty")).unwrap();
+        fs::write(folder.join("file.txt"), b"content").unwrap();
+
+        let zip_path = dir.path().join("tree.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let entries = list_zip_entries(&zip_path).unwrap();
+        assert_eq!(entries.len(), 2);
+        let directory = entries.iter().find(|entry| entry.name == "empty/").unwrap();
+        assert!(directory.is_directory());
+        assert_eq!(directory.kind, EntryKind::Directory);
+        let file = entries.iter().find(|entry| entry.name == "file.txt").unwrap();
+        assert!(!file.is_directory());
+        assert_eq!(file.size, 7);
+
+        // Directory markers are recreated on extraction and skipped by the buffer reader.
+        let extract_dir = dir.path().join("out");
+        extract_zip(
+            zip_path.to_str().unwrap().to_string(),
+            extract_dir.to_str().unwrap().to_string(),
+            None,
+        )
+        .unwrap();
+        assert!(extract_dir.join("empty").is_dir());
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_create_zip_zstd_from_folder_round_trip() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("logs");
+        fs::create_dir(&folder).unwrap();
+        let log = "level=info msg=request served\n".repeat(200);
+        fs::write(folder.join("app.log"), &log).unwrap();
+        fs::write(folder.join("small.txt"), b"tiny").unwrap();
+
+        let zip_path = dir.path().join("logs.zip");
+        create_zip_zstd_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+        )
+        .unwrap();
+
+        for entry in list_zip_entries(&zip_path).unwrap() {
+            assert_eq!(entry.compression_method, zip::CompressionMethod::Zstd);
+        }
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["app.log"], log.as_bytes());
+        assert_eq!(contents["small.txt"], b"tiny");
+    }
+
+    #[test]
+    fn test_compression_backend_overrides_file_options() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("data.txt");
+        fs::write(&file, "repetitive ".repeat(100)).unwrap();
+
+        let zip_path = dir.path().join("stored.zip");
+        create_zip_from_files_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            vec![file.to_str().unwrap().to_string()],
+            default_options(),
+            CreationOptions { backend: Some(CompressionBackend::Stored), ..Default::default() },
+        )
+        .unwrap();
+
+        let entries = list_zip_entries(&zip_path).unwrap();
+        assert_eq!(entries[0].compression_method, zip::CompressionMethod::Stored);
+        assert_eq!(entries[0].compressed_size, entries[0].size);
+    }
+
+    #[test]
+    fn test_read_zip_contents_lenient_separates_failures() {
+        let dir = tempdir().unwrap();
+        let good = dir.path().join("good.txt");
+        let bad = dir.path().join("bad.txt");
+        fs::write(&good, b"GOOD-CONTENT").unwrap();
+        fs::write(&bad, b"BAD-CONTENT-WILL-BE-CORRUPTED").unwrap();
+
+        let zip_path = dir.path().join("partial.zip");
+        create_zip_from_files(
+            zip_path.to_str().unwrap().to_string(),
+            vec![good.to_str().unwrap().to_string(), bad.to_str().unwrap().to_string()],
+            default_options().compression_method(zip::CompressionMethod::Stored),
+        )
+        .unwrap();
+
+        // Flip the stored bytes of one entry so its CRC32 check fails.
+        let mut bytes = fs::read(&zip_path).unwrap();
+        let needle: &[u8] = b"BAD-CONTENT-WILL-BE-CORRUPTED";
+        let position = bytes.windows(needle.len()).position(|window| window == needle).unwrap();
+        bytes[position] ^= 0xFF;
+        fs::write(&zip_path, &bytes).unwrap();
+
+        let (contents, failures) = read_zip_contents_lenient(&zip_path, None).unwrap();
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents["good.txt"], b"GOOD-CONTENT");
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "bad.txt");
+        assert!(!failures[0].1.is_empty());
+
+        // The strict reader aborts on the same archive.
+        assert!(read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_create_zip_from_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("single.txt");
+        fs::write(&file, b"only me").unwrap();
+
+        let zip_path = dir.path().join("single.zip");
+        create_zip_from_file(
+            zip_path.to_str().unwrap().to_string(),
+            file.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let entries = list_zip_entries(&zip_path).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "single.txt");
+    }
+
+    #[test]
+    fn test_create_zip_from_file_rejects_directory() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("folder");
+        fs::create_dir(&folder).unwrap();
+
+        let zip_path = dir.path().join("folder.zip");
+        let error = create_zip_from_file(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap_err();
+        assert!(matches!(error.downcast_ref::<ZipoxideError>(), Some(ZipoxideError::NotAFile(path)) if path == &folder));
+        assert!(!zip_path.exists());
+    }
+
+    #[test]
+    fn test_create_zip_with_pre_allocate() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("a.bin"), vec![7u8; 100_000]).unwrap();
+        fs::write(folder.join("b.txt"), b"small").unwrap();
+
+        let zip_path = dir.path().join("preallocated.zip");
+        create_zip_from_folder_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+            CreationOptions { pre_allocate: true, ..Default::default() },
+        )
+        .unwrap();
+
+        // Unused pre-allocated space is truncated, so the archive ends with its EOCD record.
+        let bytes = fs::read(&zip_path).unwrap();
+        assert_eq!(&bytes[bytes.len() - 22..bytes.len() - 18], b"PK\x05\x06");
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["a.bin"], vec![7u8; 100_000]);
+        assert_eq!(contents["b.txt"], b"small");
+    }
+
+    #[test]
+    fn test_create_zip_from_files_keep_paths() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().join("a");
+        fs::create_dir_all(base.join("b")).unwrap();
+        fs::create_dir_all(base.join("d")).unwrap();
+        fs::write(base.join("b").join("c.txt"), b"from b").unwrap();
+        fs::write(base.join("d").join("c.txt"), b"from d").unwrap();
+
+        let zip_path = dir.path().join("kept.zip");
+        create_zip_from_files_keep_paths(
+            zip_path.to_str().unwrap().to_string(),
+            vec![
+                base.join("b").join("c.txt").to_str().unwrap().to_string(),
+                base.join("d").join("c.txt").to_str().unwrap().to_string(),
+            ],
+            base.to_str().unwrap(),
+            default_options(),
+        )
+        .unwrap();
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["b/c.txt"], b"from b");
+        assert_eq!(contents["d/c.txt"], b"from d");
+    }
+
+    #[test]
+    fn test_read_zip_contents_bounded_single_buffer() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("many");
+        fs::create_dir(&folder).unwrap();
+        for index in 0..16 {
+            fs::write(folder.join(format!("file_{index}.txt")), format!("content {index}").repeat(100)).unwrap();
+        }
+
+        let zip_path = dir.path().join("many.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let bounded = read_zip_contents_bounded(&zip_path, None, 1).unwrap();
+        let unbounded = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(bounded.len(), 16);
+        assert_eq!(bounded, unbounded);
+    }
+
+    #[test]
+    fn test_create_zip_from_folder_parallel_compress() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("parallel");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::create_dir_all(folder.join("empty")).unwrap();
+        for index in 0..32 {
+            fs::write(folder.join(format!("file_{index}.txt")), format!("line {index}\n").repeat(500)).unwrap();
+        }
+        fs::write(folder.join("nested").join("deep.txt"), b"deep").unwrap();
+
+        let sequential_path = dir.path().join("sequential.zip");
+        create_zip_from_folder(
+            sequential_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+        let parallel_path = dir.path().join("parallel.zip");
+        create_zip_from_folder_with_options(
+            parallel_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+            CreationOptions { parallel_compress: true, ..Default::default() },
+        )
+        .unwrap();
+
+        let sequential_names: Vec<String> = list_zip_entries(&sequential_path).unwrap().into_iter().map(|e| e.name).collect();
+        let parallel_names: Vec<String> = list_zip_entries(&parallel_path).unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(parallel_names, sequential_names);
+        assert_eq!(
+            read_zip_contents_into_buffer(parallel_path.to_str().unwrap().to_string(), None).unwrap(),
+            read_zip_contents_into_buffer(sequential_path.to_str().unwrap().to_string(), None).unwrap()
+        );
+
+        let shortcut_path = dir.path().join("shortcut.zip");
+        let stats = create_zip_from_folder_parallel(&shortcut_path, &folder, default_options()).unwrap();
+        assert_eq!(stats.entries, sequential_names.len());
+        assert_eq!(
+            read_zip_contents_into_buffer(shortcut_path.to_str().unwrap().to_string(), None).unwrap(),
+            read_zip_contents_into_buffer(parallel_path.to_str().unwrap().to_string(), None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_zip_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("file.txt");
+        fs::write(&file, b"plain text, not a zip").unwrap();
+        let tiny = dir.path().join("tiny.bin");
+        fs::write(&tiny, b"PK").unwrap();
+
+        let zip_path = dir.path().join("real.zip");
+        create_zip_from_file(
+            zip_path.to_str().unwrap().to_string(),
+            file.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+        let empty_zip_path = dir.path().join("empty.zip");
+        zip::ZipWriter::new(File::create(&empty_zip_path).unwrap()).finish().unwrap();
+
+        assert!(is_zip_file(&zip_path).unwrap());
+        assert!(is_zip_file(&empty_zip_path).unwrap());
+        assert!(!is_zip_file(&file).unwrap());
+        assert!(!is_zip_file(&tiny).unwrap());
+        assert!(is_zip_file(dir.path().join("missing.zip")).is_err());
+    }
+
+    fn write_zip_with_names(zip_path: &std::path::Path, names: &[&str]) {
+        let mut writer = zip::ZipWriter::new(File::create(zip_path).unwrap());
+        for name in names {
+            writer.start_file(*name, default_options()).unwrap();
+            writer.write_all(name.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_extract_zip_sanitizes_windows_reserved_names() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("reserved.zip");
+        write_zip_with_names(&zip_path, &["CON", "docs/aux.txt", "trailing. ", "plain.txt"]);
+
+        let extract_dir = dir.path().join("out");
+        let options = ExtractionOptions { sanitize_windows: true, ..Default::default() };
+        let report = extract_zip_with_options(&zip_path, &extract_dir, options).unwrap();
+
+        assert_eq!(fs::read(extract_dir.join("_CON")).unwrap(), b"CON");
+        assert_eq!(fs::read(extract_dir.join("docs").join("_aux.txt")).unwrap(), b"docs/aux.txt");
+        assert!(extract_dir.join("trailing__").is_file());
+        assert!(extract_dir.join("plain.txt").is_file());
+        assert_eq!(report.extracted.len(), 4);
+        assert_eq!(
+            report.renamed,
+            vec![
+                ("CON".to_string(), extract_dir.join("_CON")),
+                ("docs/aux.txt".to_string(), extract_dir.join("docs").join("_aux.txt")),
+                ("trailing. ".to_string(), extract_dir.join("trailing__")),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_extract_zip_sanitizes_windows_reserved_names_by_default() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("reserved.zip");
+        write_zip_with_names(&zip_path, &["CON", "nul.txt"]);
+
+        let extract_dir = dir.path().join("out");
+        let report = extract_zip_with_options(&zip_path, &extract_dir, ExtractionOptions::default()).unwrap();
+
+        assert_eq!(fs::read(extract_dir.join("_CON")).unwrap(), b"CON");
+        assert_eq!(fs::read(extract_dir.join("_nul.txt")).unwrap(), b"nul.txt");
+        assert_eq!(report.renamed.len(), 2);
+    }
+
+    #[test]
+    fn test_zip_file_size_estimate() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::write(folder.join("a.bin"), vec![7u8; 10_000]).unwrap();
+        fs::write(folder.join("nested").join("b.bin"), vec![9u8; 5_000]).unwrap();
+
+        let stored = CreationOptions { backend: Some(CompressionBackend::Stored), ..Default::default() };
+        let stored_estimate = zip_file_size_estimate(&folder, &stored).unwrap();
+        let deflated_estimate = zip_file_size_estimate(&folder, &CreationOptions::default()).unwrap();
+        assert!(stored_estimate >= 15_000);
+        assert!(deflated_estimate < stored_estimate);
+
+        // A stored archive never exceeds the estimate.
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+            stored,
+        )
+        .unwrap();
+        assert!(fs::metadata(&zip_path).unwrap().len() <= stored_estimate);
+    }
+
+    #[test]
+    fn test_extract_zip_to_callback_matches_buffered_reader() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested").join("empty")).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(folder.join("nested").join("b.txt"), b"beta".repeat(1000)).unwrap();
+
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let mut routed: HashMap<String, Vec<u8>> = HashMap::new();
+        extract_zip_to(&zip_path, None, |name, reader| {
+            let mut buffer = Vec::new();
+            reader.read_to_end(&mut buffer)?;
+            routed.insert(name.to_string(), buffer);
+            Ok(())
+        })
+        .unwrap();
+
+        let buffered = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(routed, buffered);
+    }
+
+    #[test]
+    fn test_read_zip_entry_range() {
+        let dir = tempdir().unwrap();
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let file_path = dir.path().join("data.bin");
+        fs::write(&file_path, &data).unwrap();
+
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_files(
+            zip_path.to_str().unwrap().to_string(),
+            vec![file_path.to_str().unwrap().to_string()],
+            default_options(),
+        )
+        .unwrap();
+
+        let range = read_zip_entry_range(&zip_path, "data.bin", 50_000, 1_000, None).unwrap();
+        assert_eq!(range, &data[50_000..51_000]);
+
+        // Ranges running past the end are truncated.
+        let tail = read_zip_entry_range(&zip_path, "data.bin", 99_990, 1_000, None).unwrap();
+        assert_eq!(tail, &data[99_990..]);
+        assert!(read_zip_entry_range(&zip_path, "data.bin", 200_000, 10, None).unwrap().is_empty());
+
+        let missing = read_zip_entry_range(&zip_path, "missing.bin", 0, 10, None);
+        assert!(matches!(missing, Err(ZipoxideError::EntryNotFound(name)) if name == "missing.bin"));
+    }
+
+    #[test]
+    fn test_create_zip_from_folder_rejects_file() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("file.txt");
+        fs::write(&file_path, b"not a folder").unwrap();
+
+        let zip_path = dir.path().join("file.zip");
+        let error = create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            file_path.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap_err();
+        let not_a_directory = error.downcast_ref::<ZipoxideError>();
+        assert!(matches!(not_a_directory, Some(ZipoxideError::NotADirectory(path)) if path == &file_path));
+        assert!(error.to_string().starts_with("not a directory"));
+        assert!(!zip_path.exists());
+    }
+
+    #[test]
+    fn test_extract_zip_falls_back_to_seekable_reads_above_mmap_threshold() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(folder.join("nested").join("b.txt"), b"beta".repeat(10_000)).unwrap();
+
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        // Any non-empty archive exceeds a zero threshold, forcing the file-handle path.
+        let extract_dir = dir.path().join("out");
+        let options = ExtractionOptions { mmap_threshold: 0, ..Default::default() };
+        let report = extract_zip_with_options(&zip_path, &extract_dir, options).unwrap();
+        assert_eq!(report.extracted.len(), 2);
+        assert_eq!(fs::read(extract_dir.join("a.txt")).unwrap(), b"alpha");
+        assert_eq!(fs::read(extract_dir.join("nested").join("b.txt")).unwrap(), b"beta".repeat(10_000));
+    }
+
+    /// Rewrites the entry counts of the end-of-central-directory record of an archive without a comment.
+    fn overstate_entry_count(zip_path: &std::path::Path, count: u16) {
+        let mut bytes = fs::read(zip_path).unwrap();
+        let eocd = bytes.len() - 22;
+        assert_eq!(&bytes[eocd..eocd + 4], b"PK\x05\x06");
+        bytes[eocd + 8..eocd + 10].copy_from_slice(&count.to_le_bytes());
+        bytes[eocd + 10..eocd + 12].copy_from_slice(&count.to_le_bytes());
+        fs::write(zip_path, &bytes).unwrap();
+    }
+
+    #[test]
+    fn test_overstated_entry_count_is_an_error() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("overstated.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "b.txt"]);
+        overstate_entry_count(&zip_path, 5);
+
+        assert!(read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).is_err());
+        let extract_dir = dir.path().join("out");
+        let result = extract_zip(
+            zip_path.to_str().unwrap().to_string(),
+            extract_dir.to_str().unwrap().to_string(),
+            None,
+        );
+        assert!(result.is_err());
+        assert!(!extract_dir.join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_extract_zip_propagates_entry_errors() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("corrupt.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "b.txt"]);
+
+        // Break the local file header signature of the second entry.
+        let mut bytes = fs::read(&zip_path).unwrap();
+        let mut headers = bytes.windows(4).enumerate().filter(|(_, window)| *window == b"PK\x03\x04");
+        let second_header = headers.nth(1).unwrap().0;
+        bytes[second_header + 3] = 0;
+        fs::write(&zip_path, &bytes).unwrap();
+
+        let result = extract_zip(
+            zip_path.to_str().unwrap().to_string(),
+            dir.path().join("out").to_str().unwrap().to_string(),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_zip_with_stored_entries() {
+        let dir = tempdir().unwrap();
+        let photo = dir.path().join("photo.jpg");
+        let log = dir.path().join("app.log.gz");
+        fs::write(&photo, vec![0xffu8; 4_000]).unwrap();
+        fs::write(&log, vec![0x1fu8; 1_000]).unwrap();
+
+        let zip_path = dir.path().join("stored.zip");
+        let stats = create_zip_with_stored_entries(&zip_path, vec![&photo, &log]).unwrap();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.uncompressed_size, 5_000);
+        assert_eq!(stats.archive_size, fs::metadata(&zip_path).unwrap().len());
+
+        let entries = list_zip_entries(&zip_path).unwrap();
+        assert!(entries.iter().all(|entry| entry.compression_method == zip::CompressionMethod::Stored));
+        assert!(entries.iter().all(|entry| entry.compressed_size == entry.size));
+
+        // Existing outputs and non-files are errors rather than panics.
+        let existing = create_zip_with_stored_entries(&zip_path, vec![&photo]);
+        assert!(matches!(existing, Err(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists));
+        let not_a_file = create_zip_with_stored_entries(dir.path().join("other.zip"), vec![dir.path()]);
+        assert!(matches!(not_a_file, Err(ZipoxideError::NotAFile(_))));
+    }
+
+    #[test]
+    fn test_extract_zip_strict_names_rejects_absolute_entries() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("absolute.zip");
+        write_zip_with_names(&zip_path, &["safe.txt", "/etc/evil.txt"]);
+
+        let extract_dir = dir.path().join("strict");
+        let options = ExtractionOptions { strict_names: true, ..Default::default() };
+        let result = extract_zip_with_options(&zip_path, &extract_dir, options);
+        assert!(matches!(result, Err(ZipoxideError::UnsafeEntryName(name)) if name == "/etc/evil.txt"));
+        assert!(!extract_dir.join("safe.txt").exists());
+
+        // Without strict mode the entry is kept inside the extraction directory.
+        let extract_dir = dir.path().join("lenient");
+        let report = extract_zip_with_options(&zip_path, &extract_dir, ExtractionOptions::default()).unwrap();
+        assert!(extract_dir.join("etc").join("evil.txt").is_file());
+        assert_eq!(report.renamed, vec![("/etc/evil.txt".to_string(), extract_dir.join("etc").join("evil.txt"))]);
+    }
+
+    #[test]
+    fn test_extract_zip_strict_names_accepts_clean_archive() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("clean.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "nested/b.txt"]);
+
+        let extract_dir = dir.path().join("out");
+        let options = ExtractionOptions { strict_names: true, ..Default::default() };
+        let report = extract_zip_with_options(&zip_path, &extract_dir, options).unwrap();
+        assert_eq!(report.extracted.len(), 2);
+        assert_eq!(fs::read(extract_dir.join("nested").join("b.txt")).unwrap(), b"nested/b.txt");
+    }
+
+    #[test]
+    fn test_extract_zip_with_options_contains_parent_dir_entries() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("traversal.zip");
+        write_zip_with_names(&zip_path, &["../escape.txt"]);
+
+        let extract_dir = dir.path().join("out");
+        extract_zip_with_options(&zip_path, &extract_dir, ExtractionOptions::default()).unwrap();
+        assert!(extract_dir.join("escape.txt").is_file());
+        assert!(!dir.path().join("escape.txt").exists());
+
+        let options = ExtractionOptions { strict_names: true, ..Default::default() };
+        let result = extract_zip_with_options(&zip_path, dir.path().join("strict"), options);
+        assert!(matches!(result, Err(ZipoxideError::UnsafeEntryName(_))));
+    }
+
+    #[test]
+    fn test_read_zip_contents_as_pathmap() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("paths.zip");
+        write_zip_with_names(&zip_path, &["src/lib.rs", "src/nested/mod.rs", "README.md"]);
+
+        let contents = read_zip_contents_as_pathmap(&zip_path, None).unwrap();
+        assert_eq!(contents.len(), 3);
+        let nested: PathBuf = ["src", "nested", "mod.rs"].iter().collect();
+        assert_eq!(contents[&nested], b"src/nested/mod.rs");
+        let rust_files = contents.keys().filter(|path| path.extension().is_some_and(|ext| ext == "rs")).count();
+        assert_eq!(rust_files, 2);
+        assert!(contents.keys().any(|path| path == &PathBuf::from("README.md")));
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_gzip_round_trip() {
+        let dir = tempdir().unwrap();
+        let original = "timestamp=0 level=info msg=started\n".repeat(1_000);
+        let input = dir.path().join("app.log");
+        fs::write(&input, &original).unwrap();
+
+        let compressed = dir.path().join("app.log.gz");
+        assert_eq!(gzip_file(&input, &compressed).unwrap(), original.len() as u64);
+        assert!(fs::metadata(&compressed).unwrap().len() < original.len() as u64);
+        assert_eq!(&fs::read(&compressed).unwrap()[..2], b"\x1f\x8b");
+
+        let restored = dir.path().join("restored.log");
+        assert_eq!(gunzip_file(&compressed, &restored).unwrap(), original.len() as u64);
+        assert_eq!(fs::read(&restored).unwrap(), original.as_bytes());
+    }
+
+    #[test]
+    fn test_create_zip_from_path_list() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), b"pub mod a;").unwrap();
+        fs::write(src.join("a.rs"), b"pub fn a() {}").unwrap();
+
+        // Same shape as `find <dir> -name '*.rs'` output, including the directory itself and a blank line.
+        let list = format!("{}\n{}\n\n{}\r\n", src.display(), src.join("lib.rs").display(), src.join("a.rs").display());
+        let zip_path = dir.path().join("sources.zip");
+        let creation_options = CreationOptions { strip_prefix: Some(dir.path().to_path_buf()), ..Default::default() };
+        let stats = create_zip_from_path_list(&zip_path, std::io::Cursor::new(list), creation_options).unwrap();
+        assert_eq!(stats.entries, 2);
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["src/lib.rs"], b"pub mod a;");
+        assert_eq!(contents["src/a.rs"], b"pub fn a() {}");
+
+        let missing = create_zip_from_path_list(
+            dir.path().join("missing.zip"),
+            std::io::Cursor::new("does/not/exist.rs\n"),
+            CreationOptions::default(),
+        );
+        assert!(matches!(missing, Err(ZipoxideError::InputPathNotFound(path)) if path == Path::new("does/not/exist.rs")));
+    }
+
+    #[test]
+    fn test_extract_zip_with_thread_override() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir(&folder).unwrap();
+        for i in 0..20 {
+            fs::write(folder.join(format!("file_{i}.txt")), format!("content {i}")).unwrap();
+        }
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let extract_dir = dir.path().join("out");
+        let options = ExtractionOptions { threads: Some(2), ..Default::default() };
+        let report = extract_zip_with_options(&zip_path, &extract_dir, options).unwrap();
+        assert_eq!(report.threads, 2);
+        assert_eq!(report.extracted.len(), 20);
+        for i in 0..20 {
+            let content = fs::read_to_string(extract_dir.join(format!("file_{i}.txt"))).unwrap();
+            assert_eq!(content, format!("content {i}"));
+        }
+    }
+
+    #[test]
+    fn test_zip_contains() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("names.zip");
+        write_zip_with_names(&zip_path, &["bin/tool", "README.md"]);
+
+        assert!(zip_contains(&zip_path, "bin/tool").unwrap());
+        assert!(zip_contains(&zip_path, "README.md").unwrap());
+        assert!(!zip_contains(&zip_path, "bin/other").unwrap());
+        assert!(!zip_contains(&zip_path, "readme.md").unwrap());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_create_zip_from_folder_with_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("release");
+        fs::create_dir(&folder).unwrap();
+        let binary = folder.join("tool");
+        fs::write(&binary, b"#!/bin/sh\n").unwrap();
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let zip_path = dir.path().join("release.zip");
+        create_zip_from_folder_with_mode(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            0o600,
+            default_options(),
+        )
+        .unwrap();
+
+        let extract_dir = dir.path().join("out");
+        extract_zip(
+            zip_path.to_str().unwrap().to_string(),
+            extract_dir.to_str().unwrap().to_string(),
+            None,
+        )
+        .unwrap();
+        let mode = fs::metadata(extract_dir.join("tool")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_zipoxide_config_thread_local_limits() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("three.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "b.txt", "c.txt"]);
+
+        let limited = ZipOxideConfig { max_entries: Some(2), ..Default::default() };
+        let result = ZipOxideConfig::with_thread_local(limited, || {
+            extract_zip_with_options(&zip_path, dir.path().join("limited"), ExtractionOptions::default())
+        });
+        assert!(matches!(result, Err(ZipoxideError::TooManyEntries(3))));
+        assert!(!dir.path().join("limited").join("a.txt").exists());
+
+        // Explicit options win over the thread-local config, and the config is restored after the scope.
+        let options = ExtractionOptions { max_entries: Some(3), ..Default::default() };
+        let limited = ZipOxideConfig { max_entries: Some(2), ..Default::default() };
+        let report = ZipOxideConfig::with_thread_local(limited, || {
+            extract_zip_with_options(&zip_path, dir.path().join("explicit"), options)
+        });
+        assert_eq!(report.unwrap().extracted.len(), 3);
+        assert_eq!(ZipOxideConfig::current(), ZipOxideConfig::default());
+    }
+
+    #[test]
+    fn test_extraction_rejects_excessive_compression_ratio() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("bomb");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("zeros.bin"), vec![0u8; 1_000_000]).unwrap();
+        let zip_path = dir.path().join("bomb.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let options = ExtractionOptions { max_compression_ratio: Some(100.0), ..Default::default() };
+        let result = extract_zip_with_options(&zip_path, dir.path().join("out"), options);
+        assert!(matches!(result, Err(ZipoxideError::CompressionRatioExceeded(name)) if name == "zeros.bin"));
+    }
+
+    #[test]
+    fn test_zipoxide_config_compression_level() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir(&folder).unwrap();
+        let text: String = (0..20_000).map(|i| format!("line {} of {}\n", i % 97, i % 13)).collect();
+        fs::write(folder.join("data.txt"), &text).unwrap();
+
+        let create = |name: &str| {
+            let zip_path = dir.path().join(name);
+            create_zip_from_folder(
+                zip_path.to_str().unwrap().to_string(),
+                folder.to_str().unwrap().to_string(),
+                default_options(),
+            )
+            .unwrap();
+            list_zip_entries(&zip_path).unwrap()[0].compressed_size
+        };
+        let fastest = ZipOxideConfig::with_thread_local(
+            ZipOxideConfig { compression_level: Some(1), ..Default::default() },
+            || create("fastest.zip"),
+        );
+        let best = ZipOxideConfig::with_thread_local(
+            ZipOxideConfig { compression_level: Some(9), ..Default::default() },
+            || create("best.zip"),
+        );
+        assert!(best < fastest);
+    }
+
+    #[test]
+    fn test_resumed_extraction_skips_matching_files() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("resume.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "nested/b.txt", "c.txt"]);
+
+        let extract_dir = dir.path().join("out");
+        let options = ExtractionOptions { resume: true, ..Default::default() };
+        let first = extract_zip_with_options(&zip_path, &extract_dir, options.clone()).unwrap();
+        assert_eq!(first.extracted.len(), 3);
+        assert!(first.skipped.is_empty());
+
+        let second = extract_zip_with_options(&zip_path, &extract_dir, options.clone()).unwrap();
+        assert!(second.extracted.is_empty());
+        assert_eq!(second.skipped.len(), 3);
+
+        // A damaged file is rewritten, the others are still skipped.
+        fs::write(extract_dir.join("c.txt"), b"c.tx!").unwrap();
+        let third = extract_zip_with_options(&zip_path, &extract_dir, options).unwrap();
+        assert_eq!(third.extracted, vec![extract_dir.join("c.txt")]);
+        assert_eq!(third.skipped.len(), 2);
+        assert_eq!(fs::read(extract_dir.join("c.txt")).unwrap(), b"c.txt");
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn test_tar_round_trip() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::create_dir_all(folder.join("empty")).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(folder.join("nested").join("b.txt"), b"bravo").unwrap();
+
+        for name in ["plain.tar", "packed.tar.gz"] {
+            let tar_path = dir.path().join(name);
+            let stats = create_tar_from_folder(&tar_path, &folder).unwrap();
+            assert_eq!(stats.entries, 3);
+            assert_eq!(stats.uncompressed_size, 10);
+            assert_eq!(stats.archive_size, fs::metadata(&tar_path).unwrap().len());
+            assert_eq!(fs::read(&tar_path).unwrap().starts_with(b"\x1f\x8b"), name.ends_with(".gz"));
+
+            let contents = read_tar_contents_into_buffer(&tar_path).unwrap();
+            assert_eq!(contents.len(), 2);
+            assert_eq!(contents["a.txt"], b"alpha");
+            assert_eq!(contents["nested/b.txt"], b"bravo");
+
+            let out = dir.path().join(format!("{name}.out"));
+            let report = extract_tar(&tar_path, &out, ExtractionOptions::default()).unwrap();
+            assert_eq!(report.extracted.len(), 3);
+            assert_eq!(report.threads, 1);
+            assert_eq!(fs::read(out.join("nested").join("b.txt")).unwrap(), b"bravo");
+            assert!(out.join("empty").is_dir());
+
+            let limited = ExtractionOptions { max_entries: Some(2), ..Default::default() };
+            let result = extract_tar(&tar_path, dir.path().join("limited"), limited);
+            assert!(matches!(result, Err(ZipoxideError::TooManyEntries(3))));
+        }
+        assert!(matches!(
+            create_tar_from_folder(dir.path().join("missing.tar"), dir.path().join("missing")),
+            Err(ZipoxideError::NotADirectory(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_zip_from_glob() {
+        let dir = tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        fs::create_dir_all(docs.join("notes.txt.d")).unwrap();
+        fs::write(docs.join("a.txt"), b"a").unwrap();
+        fs::write(docs.join("b.txt"), b"bb").unwrap();
+        fs::write(docs.join("c.md"), b"c").unwrap();
+        fs::write(docs.join("notes.txt.d").join("d.txt"), b"d").unwrap();
+
+        let zip_path = dir.path().join("texts.zip");
+        let pattern = format!("{}/*.txt", docs.display());
+        let stats = create_zip_from_glob(&zip_path, &pattern, default_options()).unwrap();
+        assert_eq!(stats.entries, 2);
+
+        let mut names: Vec<String> = list_zip_entries(&zip_path).unwrap().into_iter().map(|entry| entry.name).collect();
+        names.sort();
+        assert_eq!(names, ["a.txt", "b.txt"]);
+
+        let nested_zip = dir.path().join("nested.zip");
+        create_zip_from_glob(&nested_zip, &format!("{}/**/*.txt", docs.display()), default_options()).unwrap();
+        assert!(zip_contains(&nested_zip, "notes.txt.d/d.txt").unwrap());
+    }
+
+    #[test]
+    fn test_total_uncompressed_size() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::create_dir_all(folder.join("empty")).unwrap();
+        fs::write(folder.join("zeros.bin"), vec![0u8; 10_000]).unwrap();
+        fs::write(folder.join("nested").join("small.txt"), b"12345").unwrap();
+        fs::write(folder.join("blank.txt"), b"").unwrap();
+
+        let zip_path = dir.path().join("sizes.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+        assert_eq!(total_uncompressed_size(&zip_path).unwrap(), 10_005);
+        assert!(fs::metadata(&zip_path).unwrap().len() < 10_005);
+    }
+
+    #[test]
+    fn test_archive_format_detect_malformed_headers() {
+        assert_eq!(ArchiveFormat::default(), ArchiveFormat::Auto);
+        assert_eq!(ArchiveFormat::detect(b"PK\x03\x04rest"), Some(ArchiveFormat::Zip));
+        assert_eq!(ArchiveFormat::detect(b"\x1f\x8b\x08"), Some(ArchiveFormat::TarGz));
+        assert_eq!(ArchiveFormat::detect(b"BZh9"), Some(ArchiveFormat::TarBz2));
+        let malformed: [&[u8]; 9] =
+            [b"", b"P", b"PK", b"PK\x03", b"PK\x05\x06", b"\x1f", b"BZ", b"\x00\x00\x00\x00", b"\xff\xfe"];
+        for header in malformed {
+            assert_eq!(ArchiveFormat::detect(header), None, "{header:?}");
+        }
+
+        let dir = tempdir().unwrap();
+        for stream in [&b""[..], b"PK", b"hello world", b"PK\x03\x04\x00\x00"] {
+            assert!(extract_streaming(stream, dir.path(), ArchiveFormat::Auto).is_err(), "{stream:?}");
+        }
+        assert!(extract_streaming(&b"not a zip at all"[..], dir.path(), ArchiveFormat::Zip).is_err());
+        #[cfg(feature = "tar")]
+        assert!(extract_streaming(&b"\x1f\x8b\x08garbage"[..], dir.path(), ArchiveFormat::Auto).is_err());
+    }
+
+    #[test]
+    fn test_extract_streaming() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(folder.join("nested").join("b.txt"), b"bravo").unwrap();
+
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+        let out = dir.path().join("from_zip");
+        let report = extract_streaming(File::open(&zip_path).unwrap(), &out, ArchiveFormat::default()).unwrap();
+        assert_eq!(report.extracted.len(), 2);
+        assert_eq!(fs::read(out.join("nested").join("b.txt")).unwrap(), b"bravo");
+
+        #[cfg(feature = "tar")]
+        {
+            let tar_path = dir.path().join("data.tar.gz");
+            create_tar_from_folder(&tar_path, &folder).unwrap();
+            let out = dir.path().join("from_tar");
+            extract_streaming(File::open(&tar_path).unwrap(), &out, ArchiveFormat::Auto).unwrap();
+            assert_eq!(fs::read(out.join("a.txt")).unwrap(), b"alpha");
+        }
+    }
+
+    #[test]
+    fn test_create_zip_deduplicate() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("copy")).unwrap();
+        // Xorshift noise, so the payload does not compress and the saving is easy to measure.
+        let mut state: u32 = 0x9E37_79B9;
+        let payload: Vec<u8> = (0..50_000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        fs::write(folder.join("original.bin"), &payload).unwrap();
+        fs::write(folder.join("copy").join("duplicate.bin"), &payload).unwrap();
+        let mut different = payload.clone();
+        different[25_000] ^= 1;
+        fs::write(folder.join("same_size.bin"), &different).unwrap();
+
+        let build = |name: &str, deduplicate: bool| {
+            let zip_path = dir.path().join(name);
+            create_zip_from_folder_with_options(
+                zip_path.to_str().unwrap().to_string(),
+                folder.to_str().unwrap().to_string(),
+                default_options(),
+                CreationOptions { deduplicate, ..Default::default() },
+            )
+            .unwrap();
+            zip_path
+        };
+        let plain = build("plain.zip", false);
+        let deduplicated = build("deduplicated.zip", true);
+        let saved = fs::metadata(&plain).unwrap().len() - fs::metadata(&deduplicated).unwrap().len();
+        assert!(saved > 40_000, "saved only {saved} bytes");
+        assert_eq!(list_zip_entries(&deduplicated).unwrap().len(), 3);
+
+        let out = dir.path().join("out");
+        extract_zip(deduplicated.to_str().unwrap().to_string(), out.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(fs::read(out.join("original.bin")).unwrap(), payload);
+        assert_eq!(fs::read(out.join("copy").join("duplicate.bin")).unwrap(), payload);
+        assert_eq!(fs::read(out.join("same_size.bin")).unwrap(), different);
+    }
+
+    #[test]
+    fn test_read_zip_contents_worker_error_is_returned() {
+        let dir = tempdir().unwrap();
+        let files: Vec<PathBuf> = (0..16).map(|i| dir.path().join(format!("file{i:02}.bin"))).collect();
+        for file in &files {
+            fs::write(file, format!("contents of {}", file.display()).repeat(64)).unwrap();
+        }
+        let zip_path = dir.path().join("stored.zip");
+        create_zip_with_stored_entries(&zip_path, files).unwrap();
+
+        // Flip a byte inside the last entry's data so its CRC32 check fails on one worker.
+        let mut bytes = fs::read(&zip_path).unwrap();
+        let needle = b"contents of";
+        let offset = bytes.windows(needle.len()).rposition(|window| window == needle).unwrap();
+        bytes[offset] ^= 0xff;
+        fs::write(&zip_path, bytes).unwrap();
+
+        let result = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None);
+        let error = result.expect_err("corrupt entry must be reported");
+        assert!(error.to_string().contains("checksum"), "{error}");
+
+        // A poisoned lock surfaces as a concurrency error instead of a panic.
+        let mutex = std::sync::Mutex::new(0);
+        let _ = std::panic::catch_unwind(|| {
+            let _guard = mutex.lock().unwrap();
+            panic!("worker panicked while holding the lock");
+        });
+        assert!(matches!(ZipoxideError::from(mutex.lock().unwrap_err()), ZipoxideError::ConcurrencyError(_)));
+    }
+
+    #[test]
+    fn test_split_and_merge_zip() {
+        let dir = tempdir().unwrap();
+        let files: Vec<PathBuf> = (0..10).map(|i| dir.path().join(format!("part{i}.bin"))).collect();
+        for (i, file) in files.iter().enumerate() {
+            fs::write(file, vec![i as u8; 3_000 + i * 100]).unwrap();
+        }
+        let zip_path = dir.path().join("backup.zip");
+        create_zip_with_stored_entries(&zip_path, files).unwrap();
+        let original = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+
+        let volume_size = 8_000;
+        let volumes = split_zip(&zip_path, dir.path().join("volumes"), volume_size, None).unwrap();
+        assert!(volumes.len() >= 5, "{volumes:?}");
+        assert!(volumes[0].ends_with("backup.001.zip"));
+        let mut split_entries = 0;
+        for volume in &volumes {
+            assert!(fs::metadata(volume).unwrap().len() <= volume_size);
+            split_entries += list_zip_entries(volume).unwrap().len();
+        }
+        assert_eq!(split_entries, 10);
+
+        let merged_path = dir.path().join("merged.zip");
+        let stats = merge_split_zips(&merged_path, volumes).unwrap();
+        assert_eq!(stats.entries, 10);
+        let merged = read_zip_contents_into_buffer(merged_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(merged, original);
+
+        let too_small = split_zip(&zip_path, dir.path().join("tiny"), 1_000, None);
+        assert!(matches!(too_small, Err(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+    }
+
+    /// Writes a single stored entry whose name is `raw_name` verbatim, without the UTF-8 flag, the way
+    /// legacy tools do.
+    fn write_zip_with_raw_name(zip_path: &std::path::Path, raw_name: &[u8], data: &[u8]) {
+        let crc = crc32fast::hash(data);
+        let mut local = Vec::new();
+        local.extend_from_slice(b"PK\x03\x04");
+        local.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // version, flags, method, time, date
+        local.extend_from_slice(&crc.to_le_bytes());
+        local.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        local.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        local.extend_from_slice(&(raw_name.len() as u16).to_le_bytes());
+        local.extend_from_slice(&[0, 0]);
+        local.extend_from_slice(raw_name);
+        local.extend_from_slice(data);
+
+        let mut central = Vec::new();
+        central.extend_from_slice(b"PK\x01\x02");
+        central.extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // versions, flags, method, time, date
+        central.extend_from_slice(&crc.to_le_bytes());
+        central.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        central.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        central.extend_from_slice(&(raw_name.len() as u16).to_le_bytes());
+        central.extend_from_slice(&[0; 16]); // extra, comment, disk, attributes, local header offset
+        central.extend_from_slice(raw_name);
+
+        let mut bytes = local.clone();
+        bytes.extend_from_slice(&central);
+        bytes.extend_from_slice(b"PK\x05\x06\x00\x00\x00\x00\x01\x00\x01\x00");
+        bytes.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(local.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        fs::write(zip_path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_legacy_entry_name_encodings() {
+        let dir = tempdir().unwrap();
+
+        // CP437 is the ZIP default for unflagged names: 0x82 is 'é'.
+        let cp437_zip = dir.path().join("cp437.zip");
+        write_zip_with_raw_name(&cp437_zip, b"Caf\x82.txt", b"menu");
+        assert_eq!(list_zip_entries_with_encoding(&cp437_zip, None).unwrap()[0].name, "Café.txt");
+        let out = dir.path().join("cp437");
+        extract_zip_with_options(&cp437_zip, &out, ExtractionOptions::default()).unwrap();
+        assert_eq!(fs::read(out.join("Café.txt")).unwrap(), b"menu");
+
+        // Shift_JIS names come out as mojibake unless the encoding is given.
+        let (sjis_name, _, _) = encoding_rs::SHIFT_JIS.encode("資料/報告.txt");
+        let sjis_zip = dir.path().join("sjis.zip");
+        write_zip_with_raw_name(&sjis_zip, &sjis_name, b"report");
+        assert_ne!(list_zip_entries(&sjis_zip).unwrap()[0].name, "資料/報告.txt");
+        let entries = list_zip_entries_with_encoding(&sjis_zip, Some(encoding_rs::SHIFT_JIS)).unwrap();
+        assert_eq!(entries[0].name, "資料/報告.txt");
+
+        let out = dir.path().join("sjis");
+        let options = ExtractionOptions { name_encoding: Some(encoding_rs::SHIFT_JIS), ..Default::default() };
+        extract_zip_with_options(&sjis_zip, &out, options).unwrap();
+        assert_eq!(fs::read(out.join("資料").join("報告.txt")).unwrap(), b"report");
+
+        // Names with the UTF-8 flag are never re-decoded.
+        let utf8_zip = dir.path().join("utf8.zip");
+        write_zip_with_names(&utf8_zip, &["naïve.txt"]);
+        let entries = list_zip_entries_with_encoding(&utf8_zip, Some(encoding_rs::SHIFT_JIS)).unwrap();
+        assert_eq!(entries[0].name, "naïve.txt");
+    }
+
+    #[test]
+    fn test_zip_files_to_bytes_round_trip() {
+        let dir = tempdir().unwrap();
+        let report = dir.path().join("report.txt");
+        let data = dir.path().join("data.csv");
+        fs::write(&report, "quarterly numbers\n".repeat(100)).unwrap();
+        fs::write(&data, b"a,b\n1,2\n").unwrap();
+
+        let bytes = zip_files_to_bytes(vec![&report, &data], default_options()).unwrap();
+        assert!(bytes.starts_with(b"PK\x03\x04"));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2, "no archive written to disk");
+
+        let contents = read_zip_contents_from_bytes(&bytes, None).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["report.txt"], fs::read(&report).unwrap());
+        assert_eq!(contents["data.csv"], b"a,b\n1,2\n");
+
+        assert!(matches!(zip_files_to_bytes(vec![dir.path()], default_options()), Err(ZipoxideError::NotAFile(_))));
+        assert!(read_zip_contents_from_bytes(b"not a zip", None).is_err());
+    }
+
+    #[test]
+    fn test_zip_reader_entries_sorted_by_size() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("medium.txt"), vec![b'm'; 500]).unwrap();
+        fs::write(folder.join("large.txt"), vec![b'l'; 5_000]).unwrap();
+        fs::write(folder.join("small.txt"), b"s").unwrap();
+
+        let zip_path = dir.path().join("sorted.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let reader = ZipReader::open(&zip_path).unwrap();
+        assert_eq!(reader.len(), 3);
+        let descending: Vec<String> = reader.entries_sorted_by_size(true).map(|handle| handle.entry.name).collect();
+        assert_eq!(descending, vec!["large.txt", "medium.txt", "small.txt"]);
+        let ascending: Vec<u64> = reader.entries_sorted_by_size(false).map(|handle| handle.entry.size).collect();
+        assert_eq!(ascending, vec![1, 500, 5_000]);
+
+        let largest = reader.entries_sorted_by_size(true).next().unwrap();
+        assert_eq!(reader.read_entry(&largest, None).unwrap(), vec![b'l'; 5_000]);
+
+        let medium = reader.entry_by_name("medium.txt").unwrap();
+        assert_eq!((medium.index, medium.entry.size), (1, 500));
+        assert_eq!(reader.read_entry(&medium, None).unwrap(), vec![b'm'; 500]);
+        assert!(reader.entry_by_name("missing.txt").is_none());
+
+        // Extraction schedules the largest entries first but still reports central-directory order.
+        let report = extract_zip_with_options(&zip_path, dir.path().join("out"), ExtractionOptions::default()).unwrap();
+        let expected: Vec<PathBuf> =
+            reader.entries().map(|handle| dir.path().join("out").join(handle.entry.name)).collect();
+        assert_eq!(report.extracted, expected);
+    }
+
+    #[test]
+    fn test_read_zip_contents_ordered() {
+        let dir = tempdir().unwrap();
+        let names = ["zebra.txt", "apple.txt", "mango.txt"];
+        let files: Vec<PathBuf> = names
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                fs::write(&path, name.as_bytes()).unwrap();
+                path
+            })
+            .collect();
+
+        let zip_path = dir.path().join("ordered.zip");
+        create_zip_from_files(
+            zip_path.to_str().unwrap().to_string(),
+            files.iter().map(|path| path.to_str().unwrap().to_string()).collect(),
+            default_options(),
+        )
+        .unwrap();
+
+        let contents = read_zip_contents_ordered(&zip_path, None).unwrap();
+        let expected: Vec<(String, Vec<u8>)> = list_zip_entries(&zip_path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.name.clone(), entry.name.into_bytes()))
+            .collect();
+        assert_eq!(contents, expected);
+        assert_eq!(contents.len(), names.len());
+    }
+
+    #[test]
+    fn test_create_zip_from_folder_sorted_entry_order() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        // Created out of order so that filesystem order is unlikely to match by accident.
+        for relative in ["zeta.txt", "b/y.txt", "a/2.txt", "b/x.txt", "alpha.txt", "a/1.txt"] {
+            let path = folder.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, relative).unwrap();
+        }
+        fs::create_dir_all(folder.join("c_empty")).unwrap();
+
+        let mut listings = Vec::new();
+        for run in 0..2 {
+            let zip_path = dir.path().join(format!("run{run}.zip"));
+            create_zip_from_folder(
+                zip_path.to_str().unwrap().to_string(),
+                folder.to_str().unwrap().to_string(),
+                default_options(),
+            )
+            .unwrap();
+            listings.push(zip_entry_names(&zip_path).unwrap());
+        }
+        assert_eq!(listings[0], listings[1]);
+        assert_eq!(
+            listings[0],
+            vec!["a/1.txt", "a/2.txt", "alpha.txt", "b/x.txt", "b/y.txt", "c_empty/", "zeta.txt"]
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_create_zip_with_zstd_dict() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("logs");
+        fs::create_dir_all(&folder).unwrap();
+        let mut files = Vec::new();
+        for i in 0..300u32 {
+            let path = folder.join(format!("event-{i:03}.json"));
+            let record = format!(
+                "{{\"timestamp\":\"2024-05-{:02}T12:{:02}:00Z\",\"level\":\"info\",\"service\":\"checkout\",\
+                 \"message\":\"order processed\",\"order_id\":{},\"amount_cents\":{}}}\n",
+                i % 28 + 1,
+                i % 60,
+                100_000 + i * 7,
+                i * 13 % 9_999
+            );
+            fs::write(&path, record).unwrap();
+            files.push(path);
+        }
+
+        let dict_zip = dir.path().join("dict.zip");
+        let stats = create_zip_with_zstd_dict(&dict_zip, files.clone(), 4_096).unwrap();
+        assert_eq!(stats.entries, 301);
+        assert_eq!(stats.uncompressed_size, files.iter().map(|path| fs::metadata(path).unwrap().len()).sum::<u64>());
+
+        let plain_zip = dir.path().join("plain.zip");
+        create_zip_zstd_from_folder(
+            plain_zip.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+        )
+        .unwrap();
+        assert!(stats.archive_size < fs::metadata(&plain_zip).unwrap().len());
+
+        let contents = read_zip_contents_into_buffer(dict_zip.to_str().unwrap().to_string(), None).unwrap();
+        let dictionary = &contents[ZSTD_DICTIONARY_NAME];
+        let frame = &contents["event-042.json.zst"];
+        let mut decompressor = zstd::bulk::Decompressor::with_dictionary(dictionary).unwrap();
+        let restored = decompressor.decompress(frame, 1 << 20).unwrap();
+        assert_eq!(restored, fs::read(folder.join("event-042.json")).unwrap());
+
+        assert!(create_zip_with_zstd_dict(dir.path().join("empty.zip"), Vec::<PathBuf>::new(), 4_096).is_err());
+    }
+
+    #[test]
+    fn test_create_zip_from_folder_level() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        let text: String = (0..20_000).map(|i| format!("line {} of {}\n", i % 97, i % 13)).collect();
+        fs::write(folder.join("log.txt"), &text).unwrap();
+        fs::write(folder.join("nested").join("copy.txt"), &text).unwrap();
+
+        let sizes: Vec<u64> = [0u8, 1, 9]
+            .iter()
+            .map(|&level| {
+                let zip_path = dir.path().join(format!("level{level}.zip"));
+                let stats = create_zip_from_folder_level(&zip_path, &folder, level).unwrap();
+                assert_eq!(stats.entries, 2);
+                assert_eq!(stats.archive_size, fs::metadata(&zip_path).unwrap().len());
+                stats.archive_size
+            })
+            .collect();
+        assert!(sizes[2] <= sizes[1], "level 9 ({}) larger than level 1 ({})", sizes[2], sizes[1]);
+        assert!(sizes[1] < sizes[0]);
+
+        let stored = list_zip_entries(dir.path().join("level0.zip")).unwrap();
+        assert!(stored.iter().all(|entry| entry.compression_method == zip::CompressionMethod::Stored));
+        let contents = read_zip_contents_into_buffer(dir.path().join("level9.zip").to_str().unwrap().to_string(), None)
+            .unwrap();
+        assert_eq!(contents["log.txt"], text.as_bytes());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_create_zip_from_folder_symlink_loop() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("tree");
+        fs::create_dir_all(folder.join("sub")).unwrap();
+        fs::write(folder.join("root.txt"), b"root").unwrap();
+        fs::write(folder.join("sub").join("leaf.txt"), b"leaf").unwrap();
+        // `sub/loop` points back at the folder being zipped, and `sub/self` at its own directory.
+        symlink(&folder, folder.join("sub").join("loop")).unwrap();
+        symlink(folder.join("sub"), folder.join("sub").join("self")).unwrap();
+
+        let zip_path = dir.path().join("loop.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+        assert_eq!(zip_entry_names(&zip_path).unwrap(), vec!["root.txt", "sub/leaf.txt"]);
+    }
+
+    #[test]
+    fn test_create_zip_with_extended_file_options() {
+        use zip::write::ExtendedFileOptions;
+
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(folder.join("b.txt"), b"beta").unwrap();
+
+        let mut options: FileOptions<'static, ExtendedFileOptions> = FileOptions::default();
+        options.add_extra_data(0xCAFE, vec![1, 2, 3, 4].into_boxed_slice(), false).unwrap();
+        let zip_path = dir.path().join("extended.zip");
+        create_zip_from_folder_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            options,
+            CreationOptions::default(),
+        )
+        .unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        for index in 0..archive.len() {
+            let entry = archive.by_index(index).unwrap();
+            let extra = entry.extra_data().unwrap();
+            assert!(extra.windows(2).any(|id| id == 0xCAFEu16.to_le_bytes()), "{}", entry.name());
+        }
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["a.txt"], b"alpha");
+        assert_eq!(contents["b.txt"], b"beta");
+    }
+
+    #[test]
+    fn test_read_zip_entries_chunked() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("empty")).unwrap();
+        for i in 0..7 {
+            fs::write(folder.join(format!("file{i}.txt")), format!("contents {i}")).unwrap();
+        }
+        let zip_path = dir.path().join("chunks.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let batches: Vec<HashMap<String, Vec<u8>>> =
+            read_zip_entries_chunked(&zip_path, 3, None).collect::<Result<_, _>>().unwrap();
+        assert_eq!(batches.iter().map(HashMap::len).collect::<Vec<_>>(), vec![3, 3, 1]);
+        let merged: HashMap<String, Vec<u8>> = batches.into_iter().flatten().collect();
+        assert_eq!(merged, read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap());
+
+        assert_eq!(read_zip_entries_chunked(&zip_path, 0, None).count(), 7);
+        let mut missing = read_zip_entries_chunked(dir.path().join("missing.zip"), 3, None);
+        assert!(matches!(missing.next(), Some(Err(ZipoxideError::Io(_)))));
+        assert!(missing.next().is_none());
+    }
+
+    #[test]
+    fn test_create_zip_with_sync() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("ledger.txt"), b"balance: 42\n").unwrap();
+
+        let zip_path = dir.path().join("durable.zip");
+        create_zip_from_folder_with_options(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+            CreationOptions { sync: true, pre_allocate: true, ..Default::default() },
+        )
+        .unwrap();
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["ledger.txt"], b"balance: 42\n");
+    }
+
+    #[test]
+    fn test_extract_zip_since() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("stamped.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let stamps = [("old.txt", 2020), ("new.txt", 2024), ("dir/", 2019)];
+        for (name, year) in stamps {
+            let stamp = zip::DateTime::from_date_and_time(year, 3, 15, 10, 30, 0).unwrap();
+            let options = default_options().last_modified_time(stamp);
+            if name.ends_with('/') {
+                writer.add_directory(name, options).unwrap();
+            } else {
+                writer.start_file(name, options).unwrap();
+                writer.write_all(name.as_bytes()).unwrap();
+            }
+        }
+        writer.finish().unwrap();
+
+        let since = time::OffsetDateTime::from_unix_timestamp(1_640_995_200).unwrap(); // 2022-01-01 UTC
+        let extract_dir = dir.path().join("restore");
+        let report = extract_zip_since(&zip_path, &extract_dir, since, None).unwrap();
+        assert_eq!(report.extracted, vec![extract_dir.join("new.txt")]);
+        assert_eq!(report.skipped, vec![extract_dir.join("old.txt"), extract_dir.join("dir/")]);
+        assert_eq!(fs::read(extract_dir.join("new.txt")).unwrap(), b"new.txt");
+        assert!(!extract_dir.join("old.txt").exists());
+        assert!(!extract_dir.join("dir").exists());
+    }
+
+    #[test]
+    fn test_recover_partial_zip() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("complete.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let files =
+            [("one.txt", "first file ".repeat(50)), ("two.txt", "second".to_string()), ("three.txt", "x".repeat(500))];
+        for (name, data) in &files {
+            let options = default_options().with_aes_encryption(zip::AesMode::Aes256, "secret");
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(data.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+        let bytes = fs::read(&zip_path).unwrap();
+        let mut archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        let directory_start = archive.central_directory_start() as usize;
+        let third_entry_start = archive.by_index_raw(2).unwrap().header_start() as usize;
+
+        // Central directory lost: every entry comes back.
+        let no_directory = dir.path().join("no_directory.zip");
+        fs::write(&no_directory, &bytes[..directory_start]).unwrap();
+        assert!(read_zip_contents_into_buffer(no_directory.to_str().unwrap().to_string(), None).is_err());
+        let recovered = recover_partial_zip(&no_directory, Some("secret")).unwrap();
+        assert_eq!(recovered.len(), 3);
+        for (name, data) in &files {
+            assert_eq!(recovered[*name], data.as_bytes());
+        }
+
+        // Cut in the middle of the last entry: the complete ones come back.
+        let truncated = dir.path().join("truncated.zip");
+        fs::write(&truncated, &bytes[..third_entry_start + 60]).unwrap();
+        let recovered = recover_partial_zip(&truncated, Some("secret")).unwrap();
+        let mut names: Vec<&String> = recovered.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["one.txt", "two.txt"]);
+
+        // Intact archives read normally; files without any entry report the parse error.
+        assert_eq!(recover_partial_zip(&zip_path, Some("secret")).unwrap().len(), 3);
+        let garbage = dir.path().join("garbage.zip");
+        fs::write(&garbage, b"not an archive at all").unwrap();
+        assert!(matches!(recover_partial_zip(&garbage, None), Err(ZipoxideError::Zip(_))));
+    }
+
+    #[test]
+    fn test_create_incremental_zip() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("dist");
+        fs::create_dir_all(folder.join("assets")).unwrap();
+        fs::write(folder.join("index.html"), b"<html>v1</html>").unwrap();
+        fs::write(folder.join("assets").join("app.js"), b"console.log(1)").unwrap();
+        fs::write(folder.join("README"), b"unchanged").unwrap();
+        let baseline = dir.path().join("v1.zip");
+        create_zip_from_folder_level(&baseline, &folder, 6).unwrap();
+
+        fs::write(folder.join("index.html"), b"<html>v2</html>").unwrap();
+        fs::write(folder.join("assets").join("style.css"), b"body {}").unwrap();
+        fs::create_dir(folder.join("uploads")).unwrap();
+
+        let patch = dir.path().join("patch.zip");
+        let stats = create_incremental_zip(&patch, &folder, &baseline, CreationOptions::default()).unwrap();
+        assert_eq!(stats.entries, 3);
+        let names: Vec<String> = list_zip_entries(&patch).unwrap().into_iter().map(|entry| entry.name).collect();
+        assert_eq!(names, vec!["assets/style.css", "index.html", "uploads/"]);
+        let contents = read_zip_contents_into_buffer(patch.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["index.html"], b"<html>v2</html>");
+
+        // Nothing changed since the patch was applied on top of the baseline.
+        let current = dir.path().join("v2.zip");
+        create_zip_from_folder_level(&current, &folder, 6).unwrap();
+        let empty = dir.path().join("empty.zip");
+        let stats = create_incremental_zip(&empty, &folder, &current, CreationOptions::default()).unwrap();
+        assert_eq!(stats.entries, 0);
+
+        let missing = dir.path().join("missing.zip");
+        assert!(matches!(
+            create_incremental_zip(dir.path().join("x.zip"), &folder, missing, CreationOptions::default()),
+            Err(ZipoxideError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_extract_zip_map() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("Docs")).unwrap();
+        fs::write(folder.join("README.TXT"), b"readme").unwrap();
+        fs::write(folder.join("Docs").join("Guide.MD"), b"guide").unwrap();
+        fs::write(folder.join("Thumbs.db"), b"junk").unwrap();
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder_level(&zip_path, &folder, 6).unwrap();
+
+        let output = dir.path().join("out");
+        let report = extract_zip_map(&zip_path, &output, None, |name| {
+            (name != "Thumbs.db").then(|| name.to_lowercase())
+        })
+        .unwrap();
+        assert_eq!(report.extracted.len(), 2);
+        assert_eq!(report.skipped, vec![output.join("Thumbs.db")]);
+        let file_names = |path: PathBuf| -> Vec<String> {
+            let mut names: Vec<String> =
+                fs::read_dir(path).unwrap().map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(file_names(output.clone()), vec!["docs", "readme.txt"]);
+        assert_eq!(file_names(output.join("docs")), vec!["guide.md"]);
+        assert_eq!(fs::read(output.join("docs").join("guide.md")).unwrap(), b"guide");
+
+        // Remapped names cannot escape the extraction directory.
+        let escaped = dir.path().join("escaped");
+        extract_zip_map(&zip_path, &escaped, None, |name| Some(format!("../../{name}"))).unwrap();
+        assert!(escaped.join("README.TXT").is_file());
+        assert!(!dir.path().join("README.TXT").exists());
+    }
+
+    #[test]
+    fn test_read_zip_archive_comment() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("commented.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.set_comment("build 1234 – nightly");
+        writer.start_file("a.txt", default_options()).unwrap();
+        writer.write_all(b"a").unwrap();
+        writer.finish().unwrap();
+        assert_eq!(read_zip_archive_comment(&zip_path).unwrap().as_deref(), Some("build 1234 – nightly"));
+
+        let plain_path = dir.path().join("plain.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&plain_path).unwrap());
+        writer.set_raw_comment(b"caf\xe9".to_vec().into_boxed_slice());
+        writer.finish().unwrap();
+        assert_eq!(read_zip_archive_comment(&plain_path).unwrap().as_deref(), Some("caf\u{fffd}"));
+
+        let empty_path = dir.path().join("empty.zip");
+        zip::ZipWriter::new(File::create(&empty_path).unwrap()).finish().unwrap();
+        assert_eq!(read_zip_archive_comment(&empty_path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_zip_into_sink() {
+        struct ByteCounter(AtomicUsize);
+
+        impl EntrySink for ByteCounter {
+            fn accept(&self, _name: &str, data: Vec<u8>) {
+                self.0.fetch_add(data.len(), Ordering::Relaxed);
+            }
+        }
+
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(folder.join("nested").join("empty")).unwrap();
+        for i in 0..20 {
+            fs::write(folder.join("nested").join(format!("{i}.txt")), "x".repeat(i * 100)).unwrap();
+        }
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder_level(&zip_path, &folder, 6).unwrap();
+
+        let counter = ByteCounter(AtomicUsize::new(0));
+        read_zip_into_sink(&zip_path, None, &counter).unwrap();
+        assert_eq!(counter.0.load(Ordering::Relaxed) as u64, total_uncompressed_size(&zip_path).unwrap());
+
+        let collected: Mutex<HashMap<String, Vec<u8>>> = Mutex::new(HashMap::new());
+        read_zip_into_sink(&zip_path, None, &collected).unwrap();
+        let collected = collected.into_inner().unwrap();
+        assert_eq!(collected.len(), 20);
+        assert_eq!(collected["nested/3.txt"], "x".repeat(300).into_bytes());
+    }
+
+    #[test]
+    fn test_set_zip_archive_comment() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        let zip_path = dir.path().join("data.zip");
+        create_zip_from_folder_level(&zip_path, &folder, 6).unwrap();
+        let size = fs::metadata(&zip_path).unwrap().len();
+
+        set_zip_archive_comment(&zip_path, "a fairly long first comment").unwrap();
+        assert_eq!(read_zip_archive_comment(&zip_path).unwrap().as_deref(), Some("a fairly long first comment"));
+        set_zip_archive_comment(&zip_path, "short").unwrap();
+        assert_eq!(read_zip_archive_comment(&zip_path).unwrap().as_deref(), Some("short"));
+        assert_eq!(fs::metadata(&zip_path).unwrap().len(), size + 5);
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["a.txt"], b"alpha");
+
+        set_zip_archive_comment(&zip_path, "").unwrap();
+        assert_eq!(read_zip_archive_comment(&zip_path).unwrap(), None);
+        assert_eq!(fs::metadata(&zip_path).unwrap().len(), size);
+
+        let too_long = "x".repeat(65_536);
+        match set_zip_archive_comment(&zip_path, &too_long) {
+            Err(ZipoxideError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput),
+            other => panic!("expected InvalidInput, got {other:?}"),
+        }
+        set_zip_archive_comment(&zip_path, &too_long[..65_535]).unwrap();
+        assert_eq!(read_zip_archive_comment(&zip_path).unwrap().unwrap().len(), 65_535);
+    }
+
+    #[test]
+    fn test_read_mixed_encrypted_archive() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("mixed.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.start_file("public.txt", default_options()).unwrap();
+        writer.write_all(b"anyone can read this").unwrap();
+        let encrypted = default_options().with_aes_encryption(zip::AesMode::Aes256, "hunter2");
+        writer.start_file("secret.txt", encrypted).unwrap();
+        writer.write_all(b"only with the password").unwrap();
+        writer.add_directory("docs/", default_options()).unwrap();
+        writer.start_file("docs/readme.md", default_options()).unwrap();
+        writer.write_all(b"# readme").unwrap();
+        writer.finish().unwrap();
+
+        let contents =
+            read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), Some("hunter2".to_string())).unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents["public.txt"], b"anyone can read this");
+        assert_eq!(contents["secret.txt"], b"only with the password");
+        assert_eq!(contents["docs/readme.md"], b"# readme");
+        let ordered = read_zip_contents_ordered(&zip_path, Some("hunter2")).unwrap();
+        assert_eq!(ordered.len(), 3);
+        assert_eq!(peek_entry(&zip_path, "public.txt", 6, Some("hunter2")).unwrap(), b"anyone");
+
+        let output = dir.path().join("out");
+        let (zip_str, output_str) = (zip_path.to_str().unwrap().to_string(), output.to_str().unwrap().to_string());
+        extract_zip(zip_str, output_str, Some("hunter2".to_string())).unwrap();
+        assert_eq!(fs::read(output.join("public.txt")).unwrap(), b"anyone can read this");
+        assert_eq!(fs::read(output.join("secret.txt")).unwrap(), b"only with the password");
+    }
+
+    #[test]
+    fn test_zip_entry_count() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("small.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.add_directory("docs/", default_options()).unwrap();
+        for name in ["a.txt", "docs/b.txt", "docs/c.txt"] {
+            writer.start_file(name, default_options()).unwrap();
+            writer.write_all(name.as_bytes()).unwrap();
+        }
+        // A comment that looks like another end-of-central-directory record must not confuse the scan.
+        writer.set_raw_comment(b"PK\x05\x06 not a record".to_vec().into_boxed_slice());
+        writer.finish().unwrap();
+        assert_eq!(zip_entry_count(&zip_path).unwrap(), 4);
+
+        let empty_path = dir.path().join("empty.zip");
+        zip::ZipWriter::new(File::create(&empty_path).unwrap()).finish().unwrap();
+        assert_eq!(zip_entry_count(&empty_path).unwrap(), 0);
+
+        // More than 65,535 entries needs the Zip64 record.
+        let zip64_path = dir.path().join("many.zip");
+        let mut writer = zip::ZipWriter::new(std::io::BufWriter::new(File::create(&zip64_path).unwrap()));
+        let stored = default_options().compression_method(zip::CompressionMethod::Stored);
+        for i in 0..70_000 {
+            writer.start_file(format!("{i}"), stored).unwrap();
+        }
+        writer.finish().unwrap();
+        assert_eq!(zip_entry_count(&zip64_path).unwrap(), 70_000);
+
+        let not_zip = dir.path().join("not.zip");
+        fs::write(&not_zip, b"hello").unwrap();
+        assert!(matches!(zip_entry_count(&not_zip), Err(ZipoxideError::Zip(_))));
+    }
+
+    #[test]
+    fn test_extract_zip_failed_entry_leaves_no_partial_file() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("corrupt.zip");
+        let stored = default_options().compression_method(zip::CompressionMethod::Stored);
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.start_file("big.txt", stored).unwrap();
+        writer.write_all(&[b'b'; 4096]).unwrap();
+        writer.start_file("medium.txt", stored).unwrap();
+        writer.write_all(&[b'm'; 1024]).unwrap();
+        writer.start_file("broken.txt", stored).unwrap();
+        writer.write_all(b"CORRUPTED-LATER").unwrap();
+        writer.finish().unwrap();
+
+        // Flip a byte of the stored data so the CRC32 check fails after the whole entry was copied.
+        let mut bytes = fs::read(&zip_path).unwrap();
+        let at = bytes.windows(15).position(|window| window == b"CORRUPTED-LATER").unwrap();
+        bytes[at] = b'X';
+        fs::write(&zip_path, bytes).unwrap();
+
+        // One thread extracts the largest entries first, so the broken entry comes last.
+        let output = dir.path().join("out");
+        let options = ExtractionOptions { threads: Some(1), ..Default::default() };
+        assert!(extract_zip_with_options(&zip_path, &output, options).is_err());
+        assert_eq!(fs::read(output.join("big.txt")).unwrap(), [b'b'; 4096]);
+        assert_eq!(fs::read(output.join("medium.txt")).unwrap(), [b'm'; 1024]);
+        assert!(!output.join("broken.txt").exists());
+        assert!(!output.join("broken.txt.part").exists());
+    }
+
+    #[test]
+    #[cfg(feature = "tar")]
+    fn test_extract_zip_to_tar_gz() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("release.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.add_directory("bin/", default_options()).unwrap();
+        writer.start_file("bin/tool", default_options().unix_permissions(0o755)).unwrap();
+        writer.write_all(b"#!/bin/sh\necho hi\n").unwrap();
+        writer.start_file("docs/big.txt", default_options().unix_permissions(0o600)).unwrap();
+        writer.write_all(&vec![b'z'; 300_000]).unwrap();
+        writer.add_directory("empty/", default_options()).unwrap();
+        writer.finish().unwrap();
+
+        let tar_path = dir.path().join("release.tar.gz");
+        extract_zip_to_tar_gz(&zip_path, &tar_path, None).unwrap();
+        assert!(fs::read(&tar_path).unwrap().starts_with(b"\x1f\x8b"));
+        let contents = read_tar_contents_into_buffer(&tar_path).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["bin/tool"], b"#!/bin/sh\necho hi\n");
+        assert_eq!(contents["docs/big.txt"].len(), 300_000);
+
+        let out = dir.path().join("out");
+        extract_tar(&tar_path, &out, ExtractionOptions::default()).unwrap();
+        assert!(out.join("empty").is_dir());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: PathBuf| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(out.join("bin").join("tool")), 0o755);
+            assert_eq!(mode(out.join("docs").join("big.txt")), 0o600);
+        }
+
+        let result = extract_zip_to_tar_gz(&zip_path, &tar_path, None);
+        assert!(matches!(result, Err(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists));
+    }
+
+    #[test]
+    #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+    fn test_create_zip_from_folder_smart() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("site");
+        fs::create_dir_all(&folder).unwrap();
+        let blob: Vec<u8> = (0..50_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        fs::write(folder.join("logo.png"), &blob).unwrap();
+        fs::write(folder.join("PHOTO.JPG"), &blob).unwrap();
+        fs::write(folder.join("notes.txt"), "compress me ".repeat(1_000)).unwrap();
+        let zip_path = dir.path().join("site.zip");
+        let stats = create_zip_from_folder_smart(&zip_path, &folder).unwrap();
+        assert_eq!(stats.entries, 3);
+
+        let methods: HashMap<String, zip::CompressionMethod> = list_zip_entries(&zip_path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.name, entry.compression_method))
+            .collect();
+        assert_eq!(methods["logo.png"], zip::CompressionMethod::Stored);
+        assert_eq!(methods["PHOTO.JPG"], zip::CompressionMethod::Stored);
+        assert_eq!(methods["notes.txt"], zip::CompressionMethod::Deflated);
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["logo.png"], blob);
+
+        // The same policy through CreationOptions, with parallel compression.
+        let parallel_path = dir.path().join("parallel.zip");
+        let creation_options =
+            CreationOptions { store_compressed_formats: true, parallel_compress: true, ..Default::default() };
+        let (output, source) = (parallel_path.to_str().unwrap().to_string(), folder.to_str().unwrap().to_string());
+        create_zip_from_folder_with_options(output, source, default_options(), creation_options).unwrap();
+        let entries = list_zip_entries(&parallel_path).unwrap();
+        let png = entries.iter().find(|entry| entry.name == "logo.png").unwrap();
+        assert_eq!(png.compression_method, zip::CompressionMethod::Stored);
+    }
+
+    #[test]
+    #[cfg(feature = "notify")]
+    fn test_watch_and_zip() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("logs");
+        fs::create_dir_all(folder.join("app")).unwrap();
+        fs::write(folder.join("app").join("a.log"), b"first line\n").unwrap();
+        // The archive lives inside the watched folder and must not archive itself.
+        let zip_path = folder.join("logs.zip");
+
+        let watcher = watch_and_zip(&folder, &zip_path, CreationOptions::default()).unwrap();
+        assert_eq!(zip_entry_names(&zip_path).unwrap(), vec!["app/a.log"]);
+
+        fs::write(folder.join("app").join("a.log"), b"first line\nsecond line\n").unwrap();
+        fs::write(folder.join("b.log"), b"new file\n").unwrap();
+        watcher.stop().unwrap();
+
+        let mut names = zip_entry_names(&zip_path).unwrap();
+        names.sort();
+        assert_eq!(names, vec!["app/a.log", "b.log"]);
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["app/a.log"], b"first line\nsecond line\n");
+        assert!(!folder.join("logs.zip.tmp").exists());
+
+        // Restarting against the up-to-date archive leaves it untouched.
+        let modified = fs::metadata(&zip_path).unwrap().modified().unwrap();
+        watch_and_zip(&folder, &zip_path, CreationOptions::default()).unwrap().stop().unwrap();
+        assert_eq!(fs::metadata(&zip_path).unwrap().modified().unwrap(), modified);
+
+        assert!(matches!(
+            watch_and_zip(dir.path().join("missing"), dir.path().join("x.zip"), CreationOptions::default()),
+            Err(ZipoxideError::NotADirectory(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "notify")]
+    fn test_watch_and_zip_survives_deleted_files() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("tmp");
+        fs::create_dir_all(folder.join("cache")).unwrap();
+        fs::write(folder.join("keep.txt"), b"kept").unwrap();
+        let zip_path = dir.path().join("tmp.zip");
+
+        let watcher = watch_and_zip(&folder, &zip_path, CreationOptions::default()).unwrap();
+        // Files and a whole directory come and go while the watcher handles their events.
+        for round in 0..20 {
+            let scratch = folder.join(format!("scratch-{round}.tmp"));
+            fs::write(&scratch, b"short-lived").unwrap();
+            fs::write(folder.join("cache").join("entry"), b"cached").unwrap();
+            fs::remove_file(&scratch).unwrap();
+            fs::remove_dir_all(folder.join("cache")).unwrap();
+            fs::create_dir_all(folder.join("cache")).unwrap();
+        }
+        fs::write(folder.join("new.txt"), b"added").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        fs::write(folder.join("keep.txt"), b"kept, then changed").unwrap();
+        watcher.stop().unwrap();
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["keep.txt"], b"kept, then changed");
+        assert_eq!(contents["new.txt"], b"added");
+    }
+
+    #[test]
+    fn test_zip_reader_open_entry() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("wanted.txt"), b"read me lazily").unwrap();
+        fs::write(folder.join("other.txt"), vec![b'o'; 10_000]).unwrap();
+
+        let zip_path = dir.path().join("lazy.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let mut reader = ZipReader::open(&zip_path).unwrap();
+        let mut contents = String::new();
+        reader.open_entry("wanted.txt", None).unwrap().unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "read me lazily");
+        assert!(reader.open_entry("missing.txt", None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_extract_zip_report_in_archive_order() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("data");
+        fs::create_dir_all(&folder).unwrap();
+        for i in 0..50 {
+            // Sizes vary against the name order, so the largest-first schedule differs from the archive order.
+            fs::write(folder.join(format!("file_{i:02}.txt")), vec![b'x'; (i * 37) % 50 * 100]).unwrap();
+        }
+
+        let zip_path = dir.path().join("ordered.zip");
+        create_zip_from_folder(
+            zip_path.to_str().unwrap().to_string(),
+            folder.to_str().unwrap().to_string(),
+            default_options(),
+        )
+        .unwrap();
+
+        let options = ExtractionOptions { threads: Some(4), ..Default::default() };
+        let report = extract_zip_with_options(&zip_path, dir.path().join("out"), options).unwrap();
+        let reader = ZipReader::open(&zip_path).unwrap();
+        let expected: Vec<PathBuf> =
+            reader.entries().map(|handle| dir.path().join("out").join(handle.entry.name)).collect();
+        assert_eq!(expected.len(), 50);
+        assert_eq!(report.extracted, expected);
+    }
+
+    #[test]
+    fn test_read_zip_contents_with_options() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("options.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "nested/b.txt", "../escape.txt"]);
+
+        let options = ExtractionOptions { threads: Some(2), ..Default::default() };
+        let contents = read_zip_contents_with_options(&zip_path, options).unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents, read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap());
+
+        let strict = ExtractionOptions { strict_names: true, ..Default::default() };
+        let result = read_zip_contents_with_options(&zip_path, strict);
+        assert!(matches!(result, Err(ZipoxideError::UnsafeEntryName(name)) if name == "../escape.txt"));
+
+        let limited = ExtractionOptions { max_entries: Some(2), ..Default::default() };
+        assert!(matches!(read_zip_contents_with_options(&zip_path, limited), Err(ZipoxideError::TooManyEntries(3))));
+    }
+
+    #[test]
+    fn test_read_zip_contents_unknown_entry_sizes() {
+        let dir = tempdir().unwrap();
+        let payload: Vec<u8> = (0..200_000u32).flat_map(|i| (i % 251).to_le_bytes()).collect();
+
+        // Streamed archives put sizes and CRC32 in a data descriptor after each entry's data.
+        let zip_path = dir.path().join("streamed.zip");
+        let mut writer = zip::ZipWriter::new_stream(File::create(&zip_path).unwrap());
+        writer.start_file("data.bin", default_options()).unwrap();
+        writer.write_all(&payload).unwrap();
+        writer.start_file("small.txt", default_options()).unwrap();
+        writer.write_all(b"tail").unwrap();
+        writer.finish().unwrap();
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["data.bin"], payload);
+        assert_eq!(contents["small.txt"], b"tail");
+
+        // Declared sizes of zero or of several gigabytes are only capacity hints.
+        let bytes = fs::read(&zip_path).unwrap();
+        let central = bytes.windows(4).position(|window| window == b"PK\x01\x02").unwrap();
+        for declared in [0u32, 0xFFFF_FFF0] {
+            let mut patched = bytes.clone();
+            patched[central + 24..central + 28].copy_from_slice(&declared.to_le_bytes());
+            let patched_path = dir.path().join(format!("declared_{declared}.zip"));
+            fs::write(&patched_path, &patched).unwrap();
+            let contents = read_zip_contents_with_options(&patched_path, ExtractionOptions::default()).unwrap();
+            assert_eq!(contents["data.bin"], payload);
+            let ordered = read_zip_contents_ordered(&patched_path, None).unwrap();
+            assert_eq!(ordered[0].1, payload);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+    fn test_recompress_zip() {
+        let dir = tempdir().unwrap();
+        let stored_path = dir.path().join("stored.zip");
+        let stored = default_options().compression_method(zip::CompressionMethod::Stored).unix_permissions(0o600);
+        let mut writer = zip::ZipWriter::new(File::create(&stored_path).unwrap());
+        writer.add_directory("docs/", stored).unwrap();
+        writer.start_file("docs/readme.txt", stored).unwrap();
+        writer.write_all(&b"compress me please ".repeat(500)).unwrap();
+        writer.start_file("empty.txt", stored).unwrap();
+        writer.finish().unwrap();
+
+        let deflated_path = dir.path().join("deflated.zip");
+        let stats = recompress_zip(&stored_path, &deflated_path, zip::CompressionMethod::Deflated, Some(9)).unwrap();
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.uncompressed_size, 19 * 500);
+        assert!(stats.archive_size < fs::metadata(&stored_path).unwrap().len());
+
+        let mut archive = zip::ZipArchive::new(File::open(&deflated_path).unwrap()).unwrap();
+        assert!(archive.by_name("docs/").unwrap().is_dir());
+        for name in ["docs/readme.txt", "empty.txt"] {
+            let entry = archive.by_name(name).unwrap();
+            assert_eq!(entry.compression(), zip::CompressionMethod::Deflated);
+            assert_eq!(entry.unix_mode().unwrap() & 0o777, 0o600);
+        }
+        assert_eq!(
+            read_zip_contents_into_buffer(deflated_path.to_str().unwrap().to_string(), None).unwrap(),
+            read_zip_contents_into_buffer(stored_path.to_str().unwrap().to_string(), None).unwrap()
+        );
+
+        // Levels are ignored when storing, and existing outputs are refused.
+        let restored_path = dir.path().join("restored.zip");
+        recompress_zip(&deflated_path, &restored_path, zip::CompressionMethod::Stored, Some(9)).unwrap();
+        let mut archive = zip::ZipArchive::new(File::open(&restored_path).unwrap()).unwrap();
+        assert_eq!(archive.by_name("docs/readme.txt").unwrap().compression(), zip::CompressionMethod::Stored);
+        let result = recompress_zip(&stored_path, &restored_path, zip::CompressionMethod::Deflated, None);
+        assert!(matches!(result, Err(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists));
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_read_zip_entry_raw() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("raw.zip");
+        let payload = b"decompress me yourself ".repeat(200);
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.start_file("stored.txt", default_options().compression_method(zip::CompressionMethod::Stored)).unwrap();
+        writer.write_all(b"as-is").unwrap();
+        writer.start_file("packed.txt", default_options().compression_method(zip::CompressionMethod::Zstd)).unwrap();
+        writer.write_all(&payload).unwrap();
+        writer.finish().unwrap();
+
+        let (method, data) = read_zip_entry_raw(&zip_path, "stored.txt").unwrap();
+        assert_eq!((method, data.as_slice()), (zip::CompressionMethod::Stored, &b"as-is"[..]));
+
+        let (method, data) = read_zip_entry_raw(&zip_path, "packed.txt").unwrap();
+        assert_eq!(method, zip::CompressionMethod::Zstd);
+        assert!(data.len() < payload.len());
+        assert_eq!(zstd::decode_all(data.as_slice()).unwrap(), payload);
+
+        assert!(matches!(read_zip_entry_raw(&zip_path, "missing.txt"), Err(ZipoxideError::EntryNotFound(_))));
+    }
+
+    #[test]
+    #[cfg(any(feature = "deflate", feature = "deflate-zlib"))]
+    fn test_read_zip_entry_raw_deflated() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("raw.zip");
+        let payload = b"decompress me yourself ".repeat(200);
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let deflated = default_options().compression_method(zip::CompressionMethod::Deflated);
+        writer.start_file("deflated.txt", deflated).unwrap();
+        writer.write_all(&payload).unwrap();
+        writer.finish().unwrap();
+
+        // Deflated entries come back as a raw Deflate stream, without a zlib header.
+        let (method, data) = read_zip_entry_raw(&zip_path, "deflated.txt").unwrap();
+        assert_eq!(method, zip::CompressionMethod::Deflated);
+        assert_ne!(data, payload);
+        let mut inflated = Vec::new();
+        flate2::read::DeflateDecoder::new(data.as_slice()).read_to_end(&mut inflated).unwrap();
+        assert_eq!(inflated, payload);
+    }
+
+    #[test]
+    fn test_extract_into_existing_file_is_refused() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("archive.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "nested/b.txt"]);
+        let target = dir.path().join("target");
+        fs::write(&target, b"not a directory").unwrap();
+
+        let result = extract_zip_with_options(&zip_path, &target, ExtractionOptions::default());
+        assert!(matches!(result, Err(ZipoxideError::ExtractTargetNotADirectory(path)) if path == target));
+
+        let error = extract_zip(zip_path.to_str().unwrap().to_string(), target.to_str().unwrap().to_string(), None)
+            .unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(ZipoxideError::ExtractTargetNotADirectory(_))));
+
+        let stream = File::open(&zip_path).unwrap();
+        let result = extract_streaming(stream, &target, ArchiveFormat::Zip);
+        assert!(matches!(result, Err(ZipoxideError::ExtractTargetNotADirectory(_))));
+        assert_eq!(fs::read(&target).unwrap(), b"not a directory");
+    }
+
+    #[test]
+    fn test_encryption_summary() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("mixed.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.add_directory("docs/", default_options()).unwrap();
+        writer.start_file("docs/public.txt", default_options()).unwrap();
+        writer.write_all(b"anyone can read this").unwrap();
+        let encrypted = default_options().with_aes_encryption(zip::AesMode::Aes256, "hunter2");
+        writer.start_file("secret.txt", encrypted).unwrap();
+        writer.write_all(b"only with the password").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(encryption_summary(&zip_path).unwrap(), (1, 1));
+    }
+
+    fn assert_clone_send_sync<T: Clone + Send + Sync>() {}
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_public_types_are_clone_send_sync() {
+        assert_clone_send_sync::<ZipEntry>();
+        assert_clone_send_sync::<ZipEntryHandle>();
+        assert_clone_send_sync::<EntryKind>();
+        assert_clone_send_sync::<ArchiveStats>();
+        assert_clone_send_sync::<CreationOptions>();
+        assert_clone_send_sync::<CompressionBackend>();
+        assert_clone_send_sync::<ExtractionOptions>();
+        assert_clone_send_sync::<ExtractionReport>();
+        assert_clone_send_sync::<ArchiveFormat>();
+        assert_clone_send_sync::<ZipOxideConfig>();
+        assert_clone_send_sync::<ZipReader>();
+        assert_clone_send_sync::<OpenArchive>();
+        assert_clone_send_sync::<OverwritePolicy>();
+        assert_clone_send_sync::<ValidationReport>();
+        // Errors wrap `std::io::Error` and handles own a thread, so neither can be cloned.
+        assert_send_sync::<ZipoxideError>();
+        #[cfg(feature = "notify")]
+        assert_send_sync::<WatchHandle>();
+    }
+
+    #[test]
+    fn test_create_zip_from_files_buffered() {
+        let dir = tempdir().unwrap();
+        let large = dir.path().join("large.bin");
+        let payload: Vec<u8> = (0..3_000_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        fs::write(&large, &payload).unwrap();
+        let folder = dir.path().join("folder");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("small.txt"), b"small").unwrap();
+
+        let zip_path = dir.path().join("buffered.zip");
+        let stats = create_zip_from_files_buffered(&zip_path, vec![&large, &folder], default_options(), 512).unwrap();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.uncompressed_size, payload.len() as u64 + 5);
+
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["large.bin"], payload);
+        assert_eq!(contents["folder/small.txt"], b"small");
+
+        let zero = create_zip_from_files_buffered(dir.path().join("zero.zip"), vec![&large], default_options(), 0);
+        assert!(matches!(zero, Err(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+        assert!(!dir.path().join("zero.zip").exists());
+    }
+
+    #[test]
+    fn test_extract_degenerate_entry_names() {
+        let dir = tempdir().unwrap();
+        for name in ["", ".", "../.."] {
+            let zip_path = dir.path().join("degenerate.zip");
+            write_zip_with_names(&zip_path, &["ok.txt", name]);
+
+            let output = dir.path().join("out");
+            let result = extract_zip_with_options(&zip_path, &output, ExtractionOptions::default());
+            let refused = matches!(&result, Err(ZipoxideError::DegenerateEntryName(found)) if found == name);
+            assert!(refused, "{name:?} {result:?}");
+            assert!(!output.join("ok.txt").exists(), "nothing is written before the name is refused");
+
+            let error = extract_zip(zip_path.to_str().unwrap().to_string(), output.to_str().unwrap().to_string(), None)
+                .unwrap_err();
+            assert!(matches!(error.downcast_ref(), Some(ZipoxideError::DegenerateEntryName(_))), "{error}");
+            fs::remove_file(&zip_path).unwrap();
+            let _ = fs::remove_dir_all(&output);
+        }
+
+        // A bare `/` is a directory entry naming the extraction directory itself, which is harmless.
+        let zip_path = dir.path().join("root.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.add_directory("/", default_options()).unwrap();
+        writer.finish().unwrap();
+        extract_zip_with_options(&zip_path, dir.path().join("root"), ExtractionOptions::default()).unwrap();
+    }
+
+    #[test]
+    fn test_create_zip_from_map() {
+        let dir = tempdir().unwrap();
+        let mut entries = BTreeMap::new();
+        entries.insert("b.txt".to_string(), b"second".to_vec());
+        entries.insert("a/nested.txt".to_string(), b"first".to_vec());
+        entries.insert("c.bin".to_string(), Vec::new());
+        let zip_options = default_options().last_modified_time(zip::DateTime::default());
+
+        let zip_path = dir.path().join("map.zip");
+        let stats = create_zip_from_map(&zip_path, entries.clone(), zip_options).unwrap();
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.uncompressed_size, 11);
+        assert_eq!(stats.archive_size, fs::metadata(&zip_path).unwrap().len());
+        let names: Vec<String> = list_zip_entries(&zip_path).unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, ["a/nested.txt", "b.txt", "c.bin"]);
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["b.txt"], b"second");
+
+        let again_path = dir.path().join("again.zip");
+        create_zip_from_map(&again_path, entries.clone(), zip_options).unwrap();
+        assert_eq!(fs::read(&again_path).unwrap(), fs::read(&zip_path).unwrap());
+
+        let existing = create_zip_from_map(&zip_path, entries, zip_options);
+        assert!(matches!(existing, Err(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists));
+    }
+
+    #[test]
+    fn test_open_zip_list_then_read() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("two_phase.zip");
+        write_zip_with_names(&zip_path, &["a.txt", "docs/b.md", "docs/c.csv"]);
+
+        let archive = open_zip(&zip_path, None).unwrap();
+        assert_eq!(archive.names().collect::<Vec<_>>(), ["a.txt", "docs/b.md", "docs/c.csv"]);
+        let sizes: Vec<u64> = archive.info().map(|entry| entry.size).collect();
+        assert_eq!(sizes, [5, 9, 10]);
+
+        // Every read comes from the mapping made by `open_zip`, so the file itself is no longer needed.
+        fs::remove_file(&zip_path).unwrap();
+        let picked: Vec<&str> = archive.names().filter(|name| name.starts_with("docs/")).collect();
+        let contents = archive.read_many(&picked).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["docs/b.md"], b"docs/b.md");
+        assert_eq!(contents["docs/c.csv"], b"docs/c.csv");
+        assert_eq!(archive.read("a.txt").unwrap(), b"a.txt");
+        let missing = archive.read("missing.txt");
+        assert!(matches!(missing, Err(ZipoxideError::EntryNotFound(name)) if name == "missing.txt"));
+    }
+
+    #[test]
+    fn test_extract_zip_in_place() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("photos.zip");
+        write_zip_with_names(&zip_path, &["a.jpg", "album/b.jpg"]);
+
+        let first = extract_zip_in_place(&zip_path, None, OverwritePolicy::Error).unwrap();
+        assert_eq!(first, dir.path().join("photos"));
+        assert_eq!(fs::read(first.join("album/b.jpg")).unwrap(), b"album/b.jpg");
+
+        let existing = extract_zip_in_place(&zip_path, None, OverwritePolicy::Error);
+        assert!(matches!(existing, Err(ZipoxideError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists));
+
+        let renamed = extract_zip_in_place(&zip_path, None, OverwritePolicy::Rename).unwrap();
+        assert_eq!(renamed, dir.path().join("photos (2)"));
+        let renamed_again = extract_zip_in_place(&zip_path, None, OverwritePolicy::Rename).unwrap();
+        assert_eq!(renamed_again, dir.path().join("photos (3)"));
+        assert!(renamed_again.join("a.jpg").is_file());
+
+        fs::write(first.join("a.jpg"), b"edited").unwrap();
+        fs::write(first.join("notes.txt"), b"mine").unwrap();
+        let merged = extract_zip_in_place(&zip_path, None, OverwritePolicy::Overwrite).unwrap();
+        assert_eq!(merged, first);
+        assert_eq!(fs::read(first.join("a.jpg")).unwrap(), b"a.jpg");
+        assert_eq!(fs::read(first.join("notes.txt")).unwrap(), b"mine");
+    }
+
+    #[test]
+    fn test_strip_metadata_is_reproducible() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("inputs");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::create_dir_all(folder.join("empty")).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(folder.join("nested/b.txt"), b"beta").unwrap();
+        let creation_options = CreationOptions { strip_metadata: true, ..Default::default() };
+
+        // A timestamp passed in `zip_options` is overridden too, so builds at any time agree.
+        let stamp = zip::DateTime::from_date_and_time(2024, 6, 1, 12, 30, 0).unwrap();
+        let mut archives = Vec::new();
+        let builds = [("first.zip", default_options()), ("second.zip", default_options().last_modified_time(stamp))];
+        for (name, zip_options) in builds {
+            let zip_path = dir.path().join(name);
+            create_zip_from_folder_with_options(
+                zip_path.to_str().unwrap().to_string(),
+                folder.to_str().unwrap().to_string(),
+                zip_options,
+                creation_options.clone(),
+            )
+            .unwrap();
+            archives.push(fs::read(&zip_path).unwrap());
+            for entry in list_zip_entries(&zip_path).unwrap() {
+                assert_eq!(entry.last_modified, Some(zip::DateTime::default()), "{}", entry.name);
+            }
+        }
+        assert_eq!(archives[0], archives[1]);
+    }
+
+    #[test]
+    fn test_extract_zip_routed() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("mixed.zip");
+        write_zip_with_names(&zip_path, &["notes.txt", "data/blob.bin", "deep/more.txt", "../escape.bin", "skip.md"]);
+        let texts = dir.path().join("texts");
+        let binaries = dir.path().join("binaries");
+
+        let report = extract_zip_routed(&zip_path, None, |name| match Path::new(name).extension()?.to_str()? {
+            "txt" => Some(texts.clone()),
+            "bin" => Some(binaries.clone()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(fs::read(texts.join("notes.txt")).unwrap(), b"notes.txt");
+        assert_eq!(fs::read(texts.join("deep/more.txt")).unwrap(), b"deep/more.txt");
+        assert_eq!(fs::read(binaries.join("data/blob.bin")).unwrap(), b"data/blob.bin");
+        // `..` is dropped below the routed directory, as it would be below `extract_path`.
+        assert_eq!(fs::read(binaries.join("escape.bin")).unwrap(), b"../escape.bin");
+        assert!(!dir.path().join("escape.bin").exists());
+        assert!(!texts.join("data").exists() && !binaries.join("notes.txt").exists());
+        assert_eq!(report.extracted.len(), 4);
+        assert_eq!(report.skipped, [PathBuf::from("skip.md")]);
+
+        fs::write(dir.path().join("blocked"), b"").unwrap();
+        let blocked = extract_zip_routed(&zip_path, None, |_| Some(dir.path().join("blocked")));
+        assert!(matches!(blocked, Err(ZipoxideError::ExtractTargetNotADirectory(_))));
+    }
+
+    #[test]
+    fn test_validate_entries() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("release.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.add_directory("bin/", default_options()).unwrap();
+        writer.start_file("bin/app", default_options().with_aes_encryption(zip::AesMode::Aes256, "hunter2")).unwrap();
+        writer.write_all(b"binary").unwrap();
+        writer.start_file(".env", default_options()).unwrap();
+        writer.write_all(b"TOKEN=1").unwrap();
+        writer.finish().unwrap();
+
+        let required = ["bin/app", "config.toml", "bin/"];
+        let report = validate_entries(&zip_path, &required, &[".env", "secrets.json"], None).unwrap();
+        assert_eq!(report.missing, ["config.toml"]);
+        assert_eq!(report.forbidden, [".env"]);
+        assert!(!report.is_valid());
+
+        let passing = validate_entries(&zip_path, &["bin/app"], &["secrets.json"], Some("hunter2")).unwrap();
+        assert!(passing.is_valid());
+        assert!(validate_entries(&zip_path, &["bin/app"], &[], Some("wrong")).is_err());
+    }
+
+    #[test]
+    fn test_read_zip_range() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("shards.zip");
+        write_zip_with_names(&zip_path, &["first.txt", "second.txt"]);
+
+        let shard = read_zip_range(&zip_path, 0, 1, None).unwrap();
+        assert_eq!(shard.len(), 1);
+        assert_eq!(shard["first.txt"], b"first.txt");
+        let rest = read_zip_range(&zip_path, 1, 10, None).unwrap();
+        assert_eq!(rest.keys().collect::<Vec<_>>(), ["second.txt"]);
+        assert!(read_zip_range(&zip_path, 2, 1, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_is_valid_zip() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("real.zip");
+        write_zip_with_names(&zip_path, &["a.txt"]);
+        assert!(is_valid_zip(&zip_path));
+
+        let random = dir.path().join("random.bin");
+        fs::write(&random, (0..4096u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect::<Vec<u8>>())
+            .unwrap();
+        assert!(!is_valid_zip(&random));
+
+        // Starts like a ZIP, but the central directory was never written.
+        let truncated = dir.path().join("truncated.zip");
+        let bytes = fs::read(&zip_path).unwrap();
+        fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(is_zip_file(&truncated).unwrap());
+        assert!(!is_valid_zip(&truncated));
+        assert!(!is_valid_zip(dir.path().join("missing.zip")));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_create_zip_from_files_unsupported_entry_type() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("inputs");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("kept.txt"), b"kept").unwrap();
+        let dangling = folder.join("dangling");
+        symlink(dir.path().join("gone.txt"), &dangling).unwrap();
+
+        // Directly as an input, and found while walking an input directory.
+        for (name, input) in [("direct.zip", &dangling), ("nested.zip", &folder)] {
+            let zip_path = dir.path().join(name);
+            let result = create_zip_from_files(
+                zip_path.to_str().unwrap().to_string(),
+                vec![input.to_str().unwrap().to_string()],
+                default_options(),
+            );
+            let error = result.unwrap_err();
+            assert!(
+                matches!(error.downcast_ref(), Some(ZipoxideError::UnsupportedEntryType(path)) if *path == dangling),
+                "{error}"
+            );
+            assert!(!zip_path.exists());
+        }
+
+        // A symlink to a regular file is followed.
+        symlink(folder.join("kept.txt"), folder.join("dangling.txt")).unwrap();
+        fs::remove_file(&dangling).unwrap();
+        let zip_path = dir.path().join("followed.zip");
+        create_zip_from_files(
+            zip_path.to_str().unwrap().to_string(),
+            vec![folder.to_str().unwrap().to_string()],
+            default_options(),
+        )
+        .unwrap();
+        let contents = read_zip_contents_into_buffer(zip_path.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(contents["inputs/dangling.txt"], b"kept");
+    }
+
+    #[test]
+    fn test_extract_creates_missing_extract_path() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("archive.zip");
+        write_zip_with_names(&zip_path, &["a.txt"]);
+
+        let nested = dir.path().join("missing/parents/out");
+        extract_zip(zip_path.to_str().unwrap().to_string(), nested.to_str().unwrap().to_string(), None).unwrap();
+        assert_eq!(fs::read(nested.join("a.txt")).unwrap(), b"a.txt");
+
+        // A parent that is a file cannot be turned into a directory; the error says which directory failed.
+        fs::write(dir.path().join("blocker"), b"").unwrap();
+        let unreachable = dir.path().join("blocker/out");
+        let error = extract_zip(zip_path.to_str().unwrap().to_string(), unreachable.to_str().unwrap().to_string(), None)
+            .unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(ZipoxideError::Io(_))), "{error}");
+        assert!(error.to_string().contains("could not create extraction directory"), "{error}");
+        let error = extract_zip_with_options(&zip_path, &unreachable, ExtractionOptions::default()).unwrap_err();
+        assert!(error.to_string().contains("could not create extraction directory"), "{error}");
+    }
+
+    #[test]
+    fn test_preserve_timestamps_round_trip() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("src");
+        fs::create_dir(&folder).unwrap();
+        let file_path = folder.join("notes.txt");
+        fs::write(&file_path, b"hello").unwrap();
+        // An odd second with a sub-second part, which the 2-second DOS timestamp cannot represent.
+        let modified = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_001, 123_456_700);
+        let file = File::options().write(true).open(&file_path).unwrap();
+        file.set_times(FileTimes::new().set_modified(modified)).unwrap();
+
+        // The other options still apply: the parent is kept as the top-level directory, files are stored.
+        let zip_path = dir.path().join("times.zip");
+        let creation_options = CreationOptions {
+            preserve_timestamps: true,
+            parallel_compress: true,
+            strip_prefix: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let zip_options = default_options().compression_method(zip::CompressionMethod::Stored);
+        let (output, source) = (zip_path.to_str().unwrap().to_string(), folder.to_str().unwrap().to_string());
+        let stats = create_zip_from_folder_with_options(output, source, zip_options, creation_options).unwrap();
+        assert_eq!(stats.entries, 1);
+        let entries = list_zip_entries(&zip_path).unwrap();
+        assert_eq!(entries[0].name, "src/notes.txt");
+        assert_eq!(entries[0].compression_method, zip::CompressionMethod::Stored);
+
+        let out = dir.path().join("out");
+        extract_zip_with_options(&zip_path, &out, ExtractionOptions::default()).unwrap();
+        let restored = fs::metadata(out.join("src/notes.txt")).unwrap().modified().unwrap();
+        assert_eq!(restored, modified);
+
+        // Lists of files take the option too.
+        let files_zip = dir.path().join("files.zip");
+        let creation_options = CreationOptions { preserve_timestamps: true, ..Default::default() };
+        let output = files_zip.to_str().unwrap().to_string();
+        let files = vec![file_path.to_str().unwrap().to_string()];
+        create_zip_from_files_with_options(output, files, default_options(), creation_options).unwrap();
+        let files_out = dir.path().join("files");
+        extract_zip_with_options(&files_zip, &files_out, ExtractionOptions::default()).unwrap();
+        assert_eq!(fs::metadata(files_out.join("notes.txt")).unwrap().modified().unwrap(), modified);
+
+        // Archives without the extra field keep getting the extraction time.
+        let plain_zip = dir.path().join("plain.zip");
+        let folder_str = folder.to_str().unwrap().to_string();
+        create_zip_from_folder(plain_zip.to_str().unwrap().to_string(), folder_str, default_options()).unwrap();
+        let plain_out = dir.path().join("plain");
+        extract_zip_with_options(&plain_zip, &plain_out, ExtractionOptions::default()).unwrap();
+        assert_ne!(fs::metadata(plain_out.join("notes.txt")).unwrap().modified().unwrap(), modified);
+    }
+
+    #[test]
+    fn test_max_total_uncompressed_bytes() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("inputs");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::write(folder.join("a.txt"), vec![b'a'; 600]).unwrap();
+        fs::write(folder.join("nested/b.txt"), vec![b'b'; 500]).unwrap();
+        let folder_str = folder.to_str().unwrap().to_string();
+
+        let zip_path = dir.path().join("too_big.zip");
+        let limited = CreationOptions { max_total_uncompressed_bytes: Some(1000), ..Default::default() };
+        let zip_path_str = zip_path.to_str().unwrap().to_string();
+        let result = create_zip_from_folder_with_options(zip_path_str, folder_str.clone(), default_options(), limited);
+        let error = result.unwrap_err();
+        assert!(
+            matches!(error.downcast_ref(), Some(ZipoxideError::SizeLimitExceeded { actual: 1100, limit: 1000 })),
+            "{error}"
+        );
+        assert!(!zip_path.exists());
+
+        // Exactly at the limit is allowed.
+        let zip_path = dir.path().join("fits.zip");
+        let creation_options = CreationOptions { max_total_uncompressed_bytes: Some(1100), ..Default::default() };
+        let zip_path_str = zip_path.to_str().unwrap().to_string();
+        create_zip_from_folder_with_options(zip_path_str, folder_str, default_options(), creation_options).unwrap();
+        assert_eq!(list_zip_entries(&zip_path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_create_zip_from_files_missing_input() {
+        let dir = tempdir().unwrap();
+        let present = dir.path().join("present.txt");
+        fs::write(&present, b"present").unwrap();
+        let missing = dir.path().join("missing.txt");
+
+        let zip_path = dir.path().join("archive.zip");
+        let inputs = vec![present.to_str().unwrap().to_string(), missing.to_str().unwrap().to_string()];
+        let result = create_zip_from_files(zip_path.to_str().unwrap().to_string(), inputs, default_options());
+        let error = result.unwrap_err();
+        assert!(
+            matches!(error.downcast_ref(), Some(ZipoxideError::InputPathNotFound(path)) if *path == missing),
+            "{error}"
+        );
+        assert!(!zip_path.exists());
+    }
+
+    #[test]
+    fn test_read_zip_entry_as_string() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("text.zip");
+        let mut entries = BTreeMap::new();
+        entries.insert("README.md".to_string(), "# Hello, wörld\n".as_bytes().to_vec());
+        entries.insert("latin1.txt".to_string(), vec![b'c', b'a', b'f', 0xe9]);
+        create_zip_from_map(&zip_path, entries, default_options()).unwrap();
+
+        assert_eq!(read_zip_entry_as_string(&zip_path, "README.md", None).unwrap(), "# Hello, wörld\n");
+        let error = read_zip_entry_as_string(&zip_path, "latin1.txt", None).unwrap_err();
+        assert!(matches!(&error, ZipoxideError::InvalidUtf8(bytes) if bytes == b"caf\xe9"), "{error}");
+        let error = read_zip_entry_as_string(&zip_path, "missing.txt", None).unwrap_err();
+        assert!(matches!(error, ZipoxideError::EntryNotFound(_)));
+    }
+
+    #[test]
+    fn test_extract_zips_into_shared_directory() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.zip");
+        let second = dir.path().join("second.zip");
+        write_zip_with_names(&first, &["a.txt", "docs/b.txt"]);
+        write_zip_with_names(&second, &["c.txt", "docs/d.txt"]);
+
+        let out = dir.path().join("out");
+        let inputs = vec![(first.to_str().unwrap().to_string(), None), (second.to_str().unwrap().to_string(), None)];
+        extract_zips(inputs, &out).unwrap();
+        for name in ["a.txt", "docs/b.txt", "c.txt", "docs/d.txt"] {
+            assert_eq!(fs::read(out.join(name)).unwrap(), name.as_bytes());
+        }
+
+        // The same file in two archives is refused before anything is written.
+        let clashing = dir.path().join("clashing.zip");
+        write_zip_with_names(&clashing, &["e.txt", "docs/b.txt"]);
+        let fresh = dir.path().join("fresh");
+        let inputs = vec![(first.to_str().unwrap().to_string(), None), (clashing.to_str().unwrap().to_string(), None)];
+        let error = extract_zips(inputs, &fresh).unwrap_err();
+        assert!(matches!(&error, ZipoxideError::EntryCollision(path) if path.ends_with("docs/b.txt")), "{error}");
+        assert!(!fresh.exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_create_zip_from_folder_skips_unreadable_files() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("inputs");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(folder.join("nested/b.txt"), b"beta").unwrap();
+        // Listed by the folder walk, but fails to open, like a file deleted after the listing.
+        let dangling = folder.join("nested/gone.txt");
+        symlink(dir.path().join("missing.txt"), &dangling).unwrap();
+
+        let zip_path = dir.path().join("best_effort.zip");
+        let zip_path_str = zip_path.to_str().unwrap().to_string();
+        let folder_str = folder.to_str().unwrap().to_string();
+        let skipping = CreationOptions { on_error: ErrorPolicy::SkipAndCollect, ..Default::default() };
+        let stats =
+            create_zip_from_folder_with_options(zip_path_str, folder_str.clone(), default_options(), skipping).unwrap();
+        assert_eq!(stats.skipped_files, vec![dangling.clone()]);
+        assert_eq!(stats.entries, 2);
+        assert_eq!(zip_entry_names(&zip_path).unwrap(), ["a.txt", "nested/b.txt"]);
+
+        // The parallel writer skips the same way, and the default policy still aborts.
+        let parallel = CreationOptions {
+            parallel_compress: true,
+            on_error: ErrorPolicy::SkipAndCollect,
+            ..Default::default()
+        };
+        let zip_path = dir.path().join("parallel.zip");
+        let zip_path_str = zip_path.to_str().unwrap().to_string();
+        create_zip_from_folder_with_options(zip_path_str, folder_str.clone(), default_options(), parallel).unwrap();
+        assert_eq!(zip_entry_names(&zip_path).unwrap(), ["a.txt", "nested/b.txt"]);
+
+        let zip_path = dir.path().join("abort.zip");
+        let zip_path_str = zip_path.to_str().unwrap().to_string();
+        let aborting = CreationOptions::default();
+        assert!(create_zip_from_folder_with_options(zip_path_str, folder_str, default_options(), aborting).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_create_zip_from_folder_skips_unreadable_directories() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("inputs");
+        let locked = folder.join("locked");
+        fs::create_dir_all(&locked).unwrap();
+        fs::write(folder.join("a.txt"), b"alpha").unwrap();
+        fs::write(locked.join("secret.txt"), b"hidden").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Permission bits do not stop root; there is nothing to skip then.
+        let readable = fs::read_dir(&locked).is_ok();
+
+        let folder_str = folder.to_str().unwrap().to_string();
+        let zip_path = dir.path().join("best_effort.zip");
+        let zip_path_str = zip_path.to_str().unwrap().to_string();
+        let skipping = CreationOptions { on_error: ErrorPolicy::SkipAndCollect, ..Default::default() };
+        let result = create_zip_from_folder_with_options(zip_path_str, folder_str.clone(), default_options(), skipping);
+
+        let zip_path = dir.path().join("abort.zip");
+        let zip_path_str = zip_path.to_str().unwrap().to_string();
+        let aborting = CreationOptions::default();
+        let aborted = create_zip_from_folder_with_options(zip_path_str, folder_str, default_options(), aborting);
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let stats = result.unwrap();
+        if !readable {
+            assert_eq!(stats.skipped_files, vec![locked.clone()]);
+            assert_eq!(zip_entry_names(dir.path().join("best_effort.zip")).unwrap(), ["a.txt"]);
+            assert!(aborted.is_err());
+        }
+    }
+
+    #[test]
+    fn test_extract_preserve_dir_timestamps() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("dated.zip");
+        let older = zip::DateTime::from_date_and_time(2020, 1, 2, 3, 4, 6).unwrap();
+        let newer = zip::DateTime::from_date_and_time(2021, 5, 6, 7, 8, 10).unwrap();
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer.add_directory("docs/", default_options().last_modified_time(older)).unwrap();
+        writer.start_file("docs/a.txt", default_options().last_modified_time(older)).unwrap();
+        writer.write_all(b"a").unwrap();
+        writer.start_file("docs/sub/b.txt", default_options().last_modified_time(newer)).unwrap();
+        writer.write_all(b"b").unwrap();
+        writer.finish().unwrap();
+        let as_system_time = |date: zip::DateTime| SystemTime::from(time::OffsetDateTime::try_from(date).unwrap());
+
+        let out = dir.path().join("out");
+        let options = ExtractionOptions { preserve_dir_timestamps: true, ..Default::default() };
+        extract_zip_with_options(&zip_path, &out, options).unwrap();
+        let modified = |path: &str| fs::metadata(out.join(path)).unwrap().modified().unwrap();
+        assert_eq!(modified("docs"), as_system_time(newer));
+        assert_eq!(modified("docs/sub"), as_system_time(newer));
+    }
+
+    #[test]
+    fn test_create_split_zips_from_folder() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("inputs");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        let files = [("a.bin", 400), ("b.bin", 300), ("c.bin", 500), ("nested/d.bin", 200), ("nested/e.bin", 700)];
+        for (name, size) in files {
+            fs::write(folder.join(name), vec![b'x'; size]).unwrap();
+        }
+
+        let prefix = dir.path().join("part");
+        let archives = create_split_zips_from_folder(&prefix, &folder, 1000, default_options()).unwrap();
+        assert!(archives.len() > 1, "{archives:?}");
+        assert_eq!(archives[0], dir.path().join("part_000.zip"));
+        let mut names = Vec::new();
+        for archive in &archives {
+            let entries = list_zip_entries(archive).unwrap();
+            assert!(entries.iter().map(|entry| entry.size).sum::<u64>() <= 1000);
+            names.extend(entries.into_iter().map(|entry| entry.name));
+        }
+        names.sort();
+        assert_eq!(names, ["a.bin", "b.bin", "c.bin", "nested/d.bin", "nested/e.bin"]);
+
+        // A single file over the budget cannot be placed without splitting it.
+        let tiny = dir.path().join("tiny");
+        let error = create_split_zips_from_folder(&tiny, &folder, 600, default_options()).unwrap_err();
+        assert!(matches!(&error, ZipoxideError::Io(e) if e.kind() == std::io::ErrorKind::InvalidInput), "{error}");
+        assert!(!dir.path().join("tiny_000.zip").exists());
+    }
 }